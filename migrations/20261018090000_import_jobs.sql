@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS import_jobs (
+    game text NOT NULL,
+    uid integer NOT NULL,
+    url text NOT NULL,
+    ignore_timestamps boolean NOT NULL,
+    status text NOT NULL,
+    gacha_type text,
+    attempts integer NOT NULL DEFAULT 0,
+    error text,
+    updated_at timestamp with time zone NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE ONLY import_jobs
+    ADD CONSTRAINT import_jobs_pkey PRIMARY KEY (game, uid);
+
+CREATE TABLE IF NOT EXISTS import_jobs_cursors (
+    game text NOT NULL,
+    uid integer NOT NULL,
+    gacha_type text NOT NULL,
+    end_id text NOT NULL,
+    latest_timestamp timestamp with time zone,
+    count integer NOT NULL DEFAULT 0,
+    finished boolean NOT NULL DEFAULT FALSE
+);
+
+ALTER TABLE ONLY import_jobs_cursors
+    ADD CONSTRAINT import_jobs_cursors_pkey PRIMARY KEY (game, uid, gacha_type);
+
+ALTER TABLE ONLY import_jobs_cursors
+    ADD CONSTRAINT import_jobs_cursors_game_uid_fkey FOREIGN KEY (game, uid) REFERENCES import_jobs (game, uid) ON DELETE CASCADE;
+
@@ -0,0 +1,42 @@
+ALTER TABLE warps_stats_standard
+    ADD COLUMN likelihood_4 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_standard
+    ADD COLUMN likelihood_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_standard
+    ADD COLUMN expected_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_standard
+    ADD COLUMN actual_5 integer NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN likelihood_4 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN likelihood_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN likelihood_win double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN expected_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN actual_5 integer NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN likelihood_4 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN likelihood_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN likelihood_win double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN expected_5 double precision NOT NULL DEFAULT 0;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN actual_5 integer NOT NULL DEFAULT 0;
+
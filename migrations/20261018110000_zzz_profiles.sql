@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS zzz_profiles (
+    uid integer NOT NULL,
+    region text NOT NULL,
+    name text NOT NULL,
+    level integer NOT NULL,
+    signature text NOT NULL,
+    avatar_id integer NOT NULL,
+    achievement_count integer NOT NULL,
+    timestamp timestamp with time zone NOT NULL,
+    updated_at timestamp with time zone NOT NULL
+);
+
+ALTER TABLE ONLY zzz_profiles
+    ADD CONSTRAINT zzz_profiles_pkey PRIMARY KEY (uid);
+
+ALTER TABLE ONLY zzz_profiles
+    ADD CONSTRAINT zzz_profiles_uid_fkey FOREIGN KEY (uid) REFERENCES zzz_uids (uid) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS zzz_profiles_achievement_count_timestamp ON zzz_profiles (achievement_count DESC, timestamp);
+CREATE INDEX IF NOT EXISTS zzz_profiles_level_uid ON zzz_profiles (level DESC, uid);
@@ -0,0 +1,29 @@
+ALTER TABLE gi_profiles
+    ADD COLUMN IF NOT EXISTS region text NOT NULL DEFAULT 'cn',
+    ADD COLUMN IF NOT EXISTS level integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS signature text NOT NULL DEFAULT '',
+    ADD COLUMN IF NOT EXISTS name_card integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS profile_picture integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS achievement_count integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS timestamp timestamp with time zone NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS updated_at timestamp with time zone;
+
+UPDATE
+    gi_profiles
+SET
+    region = CASE uid / 100000000 % 10
+    WHEN 6 THEN
+        'na'
+    WHEN 7 THEN
+        'eu'
+    WHEN 8 THEN
+        'asia'
+    WHEN 9 THEN
+        'asia'
+    ELSE
+        'cn'
+    END;
+
+CREATE INDEX IF NOT EXISTS gi_profiles_achievement_count_timestamp ON gi_profiles (achievement_count DESC, timestamp)
+WHERE
+    updated_at IS NOT NULL;
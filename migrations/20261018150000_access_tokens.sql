@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS access_tokens (
+    id uuid PRIMARY KEY NOT NULL,
+    username text NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    hash text NOT NULL UNIQUE,
+    scopes text[] NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT NOW(),
+    last_used_at timestamp with time zone
+);
+
+CREATE INDEX IF NOT EXISTS access_tokens_username_idx ON access_tokens (username);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS user_tokens (
+    hash text PRIMARY KEY NOT NULL,
+    username text NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    created_at timestamp with time zone NOT NULL DEFAULT NOW(),
+    expires_at timestamp with time zone NOT NULL,
+    used_at timestamp with time zone
+);
+
+CREATE INDEX IF NOT EXISTS user_tokens_username_idx ON user_tokens (username);
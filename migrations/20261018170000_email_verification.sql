@@ -0,0 +1,14 @@
+ALTER TABLE user_tokens
+    ADD COLUMN IF NOT EXISTS kind text NOT NULL DEFAULT 'login',
+    ADD COLUMN IF NOT EXISTS email text;
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS email_verified boolean NOT NULL DEFAULT FALSE;
+
+-- Addresses set before verification existed keep working for recovery
+UPDATE
+    users
+SET
+    email_verified = TRUE
+WHERE
+    email IS NOT NULL;
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS users_totp (
+    username text PRIMARY KEY NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    secret text NOT NULL,
+    enabled boolean NOT NULL DEFAULT FALSE,
+    -- Last accepted time step, so a code can't be replayed
+    last_step bigint,
+    created_at timestamp with time zone NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS users_recovery_codes (
+    username text NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    hash text NOT NULL,
+    used_at timestamp with time zone,
+    PRIMARY KEY (username, hash)
+);
+
+-- Sessions that passed the password but still need a second factor
+ALTER TABLE sessions
+    ADD COLUMN IF NOT EXISTS pending boolean NOT NULL DEFAULT FALSE;
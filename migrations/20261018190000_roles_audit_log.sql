@@ -0,0 +1,47 @@
+CREATE TABLE IF NOT EXISTS users_roles (
+    username text NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    role text NOT NULL,
+    PRIMARY KEY (username, role)
+);
+
+INSERT INTO users_roles (username, role)
+SELECT
+    username,
+    'superadmin'
+FROM
+    admins
+ON CONFLICT
+    DO NOTHING;
+
+DROP TABLE IF EXISTS admins;
+
+-- No foreign key so entries outlive renamed or deleted users
+CREATE TABLE IF NOT EXISTS audit_log (
+    id bigserial PRIMARY KEY NOT NULL,
+    username text NOT NULL,
+    action text NOT NULL,
+    target text NOT NULL,
+    before jsonb,
+    after jsonb,
+    created_at timestamp with time zone NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_username_idx ON audit_log (username);
+
+CREATE INDEX IF NOT EXISTS audit_log_target_idx ON audit_log (target);
+
+CREATE OR REPLACE FUNCTION audit_log_append_only ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$
+LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW
+    EXECUTE FUNCTION audit_log_append_only ();
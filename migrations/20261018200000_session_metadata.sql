@@ -0,0 +1,12 @@
+-- Public id so the session key itself is never exposed
+ALTER TABLE sessions
+    ADD COLUMN IF NOT EXISTS id uuid NOT NULL DEFAULT gen_random_uuid (),
+    ADD COLUMN IF NOT EXISTS created_at timestamp with time zone NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS last_seen timestamp with time zone NOT NULL DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS user_agent text,
+    ADD COLUMN IF NOT EXISTS ip_hash text,
+    ADD COLUMN IF NOT EXISTS label text;
+
+CREATE UNIQUE INDEX IF NOT EXISTS sessions_id_idx ON sessions (id);
+
+CREATE INDEX IF NOT EXISTS sessions_username_idx ON sessions (username);
@@ -0,0 +1,8 @@
+-- Accounts scheduled for deletion after a grace period
+CREATE TABLE IF NOT EXISTS users_deletions (
+    username text PRIMARY KEY NOT NULL REFERENCES users ON UPDATE CASCADE ON DELETE CASCADE,
+    purge_uids boolean NOT NULL,
+    scheduled_at timestamp with time zone NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS users_deletions_scheduled_at_idx ON users_deletions (scheduled_at);
@@ -0,0 +1,20 @@
+-- One changelog per datamine update that changed something
+CREATE TABLE IF NOT EXISTS changelogs (
+    id bigserial PRIMARY KEY NOT NULL,
+    game text NOT NULL,
+    commit text NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS changelogs_game_idx ON changelogs (game, id);
+
+CREATE TABLE IF NOT EXISTS changelog_entries (
+    changelog bigint NOT NULL REFERENCES changelogs ON DELETE CASCADE,
+    kind text NOT NULL,
+    id integer NOT NULL,
+    language text,
+    before text,
+    after text
+);
+
+CREATE INDEX IF NOT EXISTS changelog_entries_changelog_idx ON changelog_entries (changelog);
@@ -0,0 +1,111 @@
+-- Release dates used to map first-seen timestamps to game versions
+CREATE TABLE IF NOT EXISTS versions (
+    game text NOT NULL,
+    version text NOT NULL,
+    released_at timestamp with time zone NOT NULL,
+    PRIMARY KEY (game, version)
+);
+
+-- When a datamine update first contained an achievement, character, light cone, weapon or W-engine
+CREATE TABLE IF NOT EXISTS first_seen (
+    game text NOT NULL,
+    kind text NOT NULL,
+    id integer NOT NULL,
+    -- NULL for everything that existed before tracking started
+    seen_at timestamp with time zone,
+    version text,
+    PRIMARY KEY (game, kind, id)
+);
+
+INSERT INTO first_seen (game, kind, id, version)
+SELECT
+    'hsr',
+    'achievement',
+    id,
+    version
+FROM
+    achievements
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id, version)
+SELECT
+    'gi',
+    'achievement',
+    id,
+    version
+FROM
+    gi_achievements
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id, version)
+SELECT
+    'zzz',
+    'achievement',
+    id,
+    version
+FROM
+    zzz_achievements
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'hsr',
+    'character',
+    id
+FROM
+    characters
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'hsr',
+    'light_cone',
+    id
+FROM
+    light_cones
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'gi',
+    'character',
+    id
+FROM
+    gi_characters
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'gi',
+    'weapon',
+    id
+FROM
+    gi_weapons
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'zzz',
+    'character',
+    id
+FROM
+    zzz_characters
+ON CONFLICT
+    DO NOTHING;
+
+INSERT INTO first_seen (game, kind, id)
+SELECT
+    'zzz',
+    'w_engine',
+    id
+FROM
+    zzz_w_engines
+ON CONFLICT
+    DO NOTHING;
@@ -0,0 +1,5 @@
+ALTER TABLE gi_wishes_stats_character
+    ADD COLUMN count integer NOT NULL DEFAULT -1;
+
+ALTER TABLE gi_wishes_stats_weapon
+    ADD COLUMN count integer NOT NULL DEFAULT -1;
@@ -0,0 +1,21 @@
+-- Deleted accounts are replaced by a pseudonym, anything else stays as it was written
+CREATE OR REPLACE FUNCTION audit_log_append_only ()
+    RETURNS TRIGGER
+    AS $$
+BEGIN
+    IF TG_OP = 'UPDATE'
+        AND NEW.id = OLD.id
+        AND NEW.action = OLD.action
+        AND NEW.before IS NOT DISTINCT FROM OLD.before
+        AND NEW.after IS NOT DISTINCT FROM OLD.after
+        AND NEW.created_at = OLD.created_at
+        AND (NEW.username = OLD.username
+            OR NEW.username LIKE 'deleted:%')
+        AND (NEW.target = OLD.target
+            OR NEW.target LIKE 'users/deleted:%') THEN
+        RETURN NEW;
+    END IF;
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$
+LANGUAGE plpgsql;
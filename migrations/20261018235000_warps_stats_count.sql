@@ -0,0 +1,9 @@
+ALTER TABLE warps_stats_standard
+    ADD COLUMN count integer NOT NULL DEFAULT -1;
+
+ALTER TABLE warps_stats_special
+    ADD COLUMN count integer NOT NULL DEFAULT -1;
+
+ALTER TABLE warps_stats_lc
+    ADD COLUMN count integer NOT NULL DEFAULT -1;
+
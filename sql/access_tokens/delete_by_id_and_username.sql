@@ -0,0 +1,4 @@
+DELETE FROM access_tokens
+WHERE id = $1
+    AND username = $2;
+
@@ -0,0 +1,3 @@
+DELETE FROM access_tokens
+WHERE username = $1;
+
@@ -0,0 +1,12 @@
+SELECT
+    id,
+    username,
+    name,
+    scopes,
+    created_at,
+    last_used_at
+FROM
+    access_tokens
+WHERE
+    hash = $1;
+
@@ -0,0 +1,14 @@
+SELECT
+    id,
+    username,
+    name,
+    scopes,
+    created_at,
+    last_used_at
+FROM
+    access_tokens
+WHERE
+    username = $1
+ORDER BY
+    created_at;
+
@@ -0,0 +1,3 @@
+INSERT INTO access_tokens (id, username, name, hash, scopes)
+    VALUES ($1, $2, $3, $4, $5);
+
@@ -0,0 +1,7 @@
+UPDATE
+    access_tokens
+SET
+    last_used_at = NOW()
+WHERE
+    id = $1;
+
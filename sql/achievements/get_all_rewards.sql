@@ -0,0 +1,6 @@
+SELECT
+    id,
+    jades
+FROM
+    achievements;
+
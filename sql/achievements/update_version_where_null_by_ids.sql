@@ -0,0 +1,8 @@
+UPDATE
+    achievements
+SET
+    version = $2
+WHERE
+    id = ANY ($1)
+    AND version IS NULL;
+
@@ -0,0 +1,3 @@
+INSERT INTO audit_log (username, action, target, before, after)
+    VALUES ($1, $2, $3, $4, $5);
+
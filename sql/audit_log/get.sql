@@ -0,0 +1,22 @@
+SELECT
+    id,
+    username,
+    action,
+    target,
+    before,
+    after,
+    created_at
+FROM
+    audit_log
+WHERE ($1::text IS NULL
+    OR username = $1)
+AND ($2::text IS NULL
+    OR action = $2)
+AND ($3::text IS NULL
+    OR target = $3)
+AND ($4::bigint IS NULL
+    OR id < $4)
+ORDER BY
+    id DESC
+LIMIT $5;
+
@@ -0,0 +1,19 @@
+UPDATE
+    audit_log
+SET
+    username = (
+        CASE WHEN username = $1 THEN
+            $2
+        ELSE
+            username
+        END),
+    target = (
+        CASE WHEN target = 'users/' || $1 THEN
+            'users/' || $2
+        ELSE
+            target
+        END)
+WHERE
+    username = $1
+    OR target = 'users/' || $1;
+
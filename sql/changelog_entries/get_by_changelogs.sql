@@ -0,0 +1,17 @@
+SELECT
+    changelog,
+    kind,
+    id,
+    language,
+    before,
+    after
+FROM
+    changelog_entries
+WHERE
+    changelog = ANY ($1)
+ORDER BY
+    changelog DESC,
+    kind,
+    id,
+    language;
+
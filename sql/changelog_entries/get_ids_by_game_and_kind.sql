@@ -0,0 +1,9 @@
+SELECT
+    changelog_entries.id
+FROM
+    changelog_entries
+    JOIN changelogs ON changelogs.id = changelog_entries.changelog
+WHERE
+    changelogs.game = $1
+    AND changelog_entries.kind = $2;
+
@@ -0,0 +1,7 @@
+INSERT INTO changelog_entries (changelog, kind, id, language, before, after)
+SELECT
+    $1,
+    *
+FROM
+    UNNEST($2::text[], $3::integer[], $4::text[], $5::text[], $6::text[]);
+
@@ -0,0 +1,14 @@
+SELECT
+    id,
+    commit,
+    created_at
+FROM
+    changelogs
+WHERE
+    game = $1
+    AND ($2::bigint IS NULL
+        OR id < $2)
+ORDER BY
+    id DESC
+LIMIT $3;
+
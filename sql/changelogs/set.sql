@@ -0,0 +1,5 @@
+INSERT INTO changelogs (game, commit)
+    VALUES ($1, $2)
+RETURNING
+    id;
+
@@ -0,0 +1,14 @@
+INSERT INTO first_seen (game, kind, id, seen_at, version)
+SELECT
+    $1,
+    $2,
+    *,
+    $4,
+    $5
+FROM
+    UNNEST($3::integer[])
+ON CONFLICT
+    DO NOTHING
+RETURNING
+    id;
+
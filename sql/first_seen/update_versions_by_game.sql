@@ -0,0 +1,38 @@
+WITH tagged AS (
+    SELECT
+        kind,
+        id,
+        version old_version,
+        (
+            SELECT
+                versions.version
+            FROM
+                versions
+            WHERE
+                versions.game = first_seen.game
+                AND versions.released_at <= first_seen.seen_at + make_interval(days => $2)
+            ORDER BY
+                versions.released_at DESC
+            LIMIT 1) new_version
+    FROM
+        first_seen
+    WHERE
+        game = $1
+        AND seen_at IS NOT NULL)
+UPDATE
+    first_seen
+SET
+    version = tagged.new_version
+FROM
+    tagged
+WHERE
+    first_seen.game = $1
+    AND first_seen.kind = tagged.kind
+    AND first_seen.id = tagged.id
+    AND first_seen.version IS DISTINCT FROM tagged.new_version
+RETURNING
+    first_seen.kind,
+    first_seen.id,
+    tagged.old_version AS "old_version?",
+    tagged.new_version AS "new_version?";
+
@@ -0,0 +1,6 @@
+SELECT
+    id,
+    primogems
+FROM
+    gi_achievements;
+
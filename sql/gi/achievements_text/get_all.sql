@@ -0,0 +1,8 @@
+SELECT
+    id,
+    language,
+    name,
+    description
+FROM
+    gi_achievements_text;
+
@@ -0,0 +1,17 @@
+SELECT
+    uid
+FROM
+    gi_connections
+WHERE
+    username = $1
+    AND verified
+    AND NOT EXISTS (
+        SELECT
+            *
+        FROM
+            gi_connections other
+        WHERE
+            other.uid = gi_connections.uid
+            AND other.username != $1
+            AND other.verified);
+
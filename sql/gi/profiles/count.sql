@@ -0,0 +1,12 @@
+SELECT
+    COUNT(*)
+FROM
+    gi_profiles
+WHERE
+    updated_at IS NOT NULL
+    AND ($1::text IS NULL
+        OR region = $1)
+    AND ($2::text IS NULL
+        OR LOWER(name)
+        LIKE '%' || LOWER($2) || '%');
+
@@ -0,0 +1,3 @@
+DELETE FROM gi_profiles
+WHERE uid = $1;
+
@@ -1,5 +1,6 @@
 SELECT
-    *
+    uid,
+    name
 FROM
     gi_profiles
 WHERE
@@ -0,0 +1,25 @@
+SELECT
+    global_rank,
+    regional_rank,
+    uid,
+    region,
+    name,
+    level,
+    signature,
+    name_card,
+    profile_picture,
+    achievement_count,
+    timestamp,
+    updated_at AS "updated_at!"
+FROM (
+    SELECT
+        RANK() OVER (ORDER BY achievement_count DESC, timestamp) global_rank,
+        RANK() OVER (PARTITION BY region ORDER BY achievement_count DESC, timestamp) regional_rank,
+        *
+    FROM
+        gi_profiles
+    WHERE
+        updated_at IS NOT NULL) ranked
+WHERE
+    uid = $1;
+
@@ -0,0 +1,7 @@
+SELECT
+    uid
+FROM
+    gi_profiles
+ORDER BY
+    updated_at NULLS FIRST;
+
@@ -0,0 +1,6 @@
+INSERT INTO gi_profiles (uid, name, region, level, signature, name_card, profile_picture, achievement_count, timestamp, updated_at)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+ON CONFLICT (uid)
+    DO UPDATE SET
+        name = EXCLUDED.name, region = EXCLUDED.region, level = EXCLUDED.level, signature = EXCLUDED.signature, name_card = EXCLUDED.name_card, profile_picture = EXCLUDED.profile_picture, achievement_count = EXCLUDED.achievement_count, timestamp = EXCLUDED.timestamp, updated_at = EXCLUDED.updated_at;
+
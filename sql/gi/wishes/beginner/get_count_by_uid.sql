@@ -0,0 +1,7 @@
+SELECT
+    count(*)
+FROM
+    gi_wishes_beginner
+WHERE
+    uid = $1;
+
@@ -0,0 +1,47 @@
+WITH beginner AS (
+    DELETE FROM gi_wishes_beginner
+    WHERE uid = $1),
+standard AS (
+    DELETE FROM gi_wishes_standard
+    WHERE uid = $1),
+character AS (
+    DELETE FROM gi_wishes_character
+    WHERE uid = $1),
+weapon AS (
+    DELETE FROM gi_wishes_weapon
+    WHERE uid = $1),
+chronicled AS (
+    DELETE FROM gi_wishes_chronicled
+    WHERE uid = $1),
+stats_standard AS (
+    DELETE FROM gi_wishes_stats_standard
+    WHERE uid = $1),
+stats_character AS (
+    DELETE FROM gi_wishes_stats_character
+    WHERE uid = $1),
+stats_weapon AS (
+    DELETE FROM gi_wishes_stats_weapon
+    WHERE uid = $1),
+stats_chronicled AS (
+    DELETE FROM gi_wishes_stats_chronicled
+    WHERE uid = $1),
+stats_global_standard AS (
+    DELETE FROM gi_wishes_stats_global_standard
+    WHERE uid = $1),
+stats_global_character AS (
+    DELETE FROM gi_wishes_stats_global_character
+    WHERE uid = $1),
+stats_global_weapon AS (
+    DELETE FROM gi_wishes_stats_global_weapon
+    WHERE uid = $1),
+stats_global_chronicled AS (
+    DELETE FROM gi_wishes_stats_global_chronicled
+    WHERE uid = $1),
+import_jobs_cursors AS (
+    DELETE FROM import_jobs_cursors
+    WHERE game = 'gi'
+        AND uid = $1)
+DELETE FROM import_jobs
+WHERE game = 'gi'
+    AND uid = $1;
+
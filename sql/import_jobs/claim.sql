@@ -0,0 +1,31 @@
+UPDATE
+    import_jobs
+SET
+    status = 'running',
+    attempts = attempts + 1,
+    updated_at = NOW()
+WHERE (game, uid) = (
+    SELECT
+        game,
+        uid
+    FROM
+        import_jobs
+    WHERE (status = 'pending'
+        AND updated_at <= NOW() - make_interval(secs => attempts * 30))
+        OR (status IN ('running', 'calculating')
+            AND updated_at < NOW() - INTERVAL '5 minutes')
+    ORDER BY
+        updated_at
+    LIMIT 1
+    FOR UPDATE
+        SKIP LOCKED)
+RETURNING
+    game,
+    uid,
+    url,
+    ignore_timestamps,
+    status,
+    gacha_type,
+    attempts,
+    error;
+
@@ -0,0 +1,15 @@
+SELECT
+    game,
+    uid,
+    url,
+    ignore_timestamps,
+    status,
+    gacha_type,
+    attempts,
+    error
+FROM
+    import_jobs
+WHERE
+    game = $1
+    AND uid = $2;
+
@@ -0,0 +1,9 @@
+UPDATE
+    import_jobs
+SET
+    updated_at = NOW()
+WHERE
+    game = $1
+    AND uid = $2
+    AND status IN ('running', 'calculating');
+
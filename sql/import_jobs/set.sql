@@ -0,0 +1,6 @@
+INSERT INTO import_jobs (game, uid, url, ignore_timestamps, status, gacha_type, attempts, error, updated_at)
+    VALUES ($1, $2, $3, $4, $5, NULL, 0, $6, NOW())
+ON CONFLICT (game, uid)
+    DO UPDATE SET
+        url = EXCLUDED.url, ignore_timestamps = EXCLUDED.ignore_timestamps, status = EXCLUDED.status, gacha_type = NULL, attempts = 0, error = EXCLUDED.error, updated_at = NOW();
+
@@ -0,0 +1,10 @@
+UPDATE
+    import_jobs
+SET
+    status = $3,
+    error = $4,
+    updated_at = NOW()
+WHERE
+    game = $1
+    AND uid = $2;
+
@@ -0,0 +1,4 @@
+DELETE FROM import_jobs_cursors
+WHERE game = $1
+    AND uid = $2;
+
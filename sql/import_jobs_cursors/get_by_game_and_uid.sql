@@ -0,0 +1,8 @@
+SELECT
+    *
+FROM
+    import_jobs_cursors
+WHERE
+    game = $1
+    AND uid = $2;
+
@@ -0,0 +1,6 @@
+INSERT INTO import_jobs_cursors (game, uid, gacha_type, end_id, latest_timestamp, count, finished)
+    VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (game, uid, gacha_type)
+    DO UPDATE SET
+        end_id = EXCLUDED.end_id, latest_timestamp = EXCLUDED.latest_timestamp, count = EXCLUDED.count, finished = EXCLUDED.finished;
+
@@ -0,0 +1,3 @@
+DELETE FROM mihomo
+WHERE uid = $1;
+
@@ -0,0 +1,3 @@
+DELETE FROM rate_limits
+WHERE updated_at < NOW() - INTERVAL '1 day';
+
@@ -0,0 +1,15 @@
+INSERT INTO rate_limits (key, tokens, allowed)
+    VALUES ($1, $2::double precision - 1, TRUE)
+ON CONFLICT (key)
+    DO UPDATE SET
+        tokens = CASE WHEN LEAST($2, rate_limits.tokens + EXTRACT(EPOCH FROM NOW() - rate_limits.updated_at)::double precision * $3) >= 1 THEN
+            LEAST($2, rate_limits.tokens + EXTRACT(EPOCH FROM NOW() - rate_limits.updated_at)::double precision * $3) - 1
+        ELSE
+            LEAST($2, rate_limits.tokens + EXTRACT(EPOCH FROM NOW() - rate_limits.updated_at)::double precision * $3)
+        END,
+        allowed = LEAST($2, rate_limits.tokens + EXTRACT(EPOCH FROM NOW() - rate_limits.updated_at)::double precision * $3) >= 1,
+        updated_at = NOW()
+    RETURNING
+        tokens,
+        allowed;
+
@@ -0,0 +1,7 @@
+DELETE FROM sessions
+WHERE id = $1
+    AND username = $2
+    AND NOT pending
+RETURNING
+    uuid;
+
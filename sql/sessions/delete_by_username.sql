@@ -0,0 +1,3 @@
+DELETE FROM sessions
+WHERE username = $1;
+
@@ -0,0 +1,4 @@
+DELETE FROM sessions
+WHERE username = $1
+    AND uuid != $2;
+
@@ -6,6 +6,7 @@ WHERE uuid IN (
             sessions
         WHERE
             username = $1
+            AND pending = $2
         ORDER BY
-            expiry DESC OFFSET 9);
+            last_seen DESC OFFSET 9);
 
@@ -0,0 +1,18 @@
+SELECT
+    id,
+    uuid,
+    label,
+    user_agent,
+    ip_hash,
+    created_at,
+    last_seen,
+    expiry
+FROM
+    sessions
+WHERE
+    username = $1
+    AND NOT pending
+    AND expiry > NOW()
+ORDER BY
+    last_seen DESC;
+
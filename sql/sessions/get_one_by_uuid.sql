@@ -1,5 +1,11 @@
 SELECT
-    *
+    uuid,
+    username,
+    expiry,
+    pending,
+    user_agent,
+    ip_hash,
+    last_seen
 FROM
     sessions
 WHERE
@@ -1,6 +1,6 @@
-INSERT INTO sessions (uuid, username, expiry)
-    VALUES ($1, $2, $3)
+INSERT INTO sessions (uuid, username, expiry, pending, user_agent, ip_hash)
+    VALUES ($1, $2, $3, $4, $5, $6)
 ON CONFLICT (uuid)
     DO UPDATE SET
-        username = EXCLUDED.username, expiry = EXCLUDED.expiry;
+        username = EXCLUDED.username, expiry = EXCLUDED.expiry, pending = EXCLUDED.pending, user_agent = COALESCE(EXCLUDED.user_agent, sessions.user_agent), ip_hash = COALESCE(EXCLUDED.ip_hash, sessions.ip_hash), last_seen = NOW();
 
@@ -0,0 +1,12 @@
+UPDATE
+    sessions
+SET
+    username = $2,
+    expiry = $3,
+    pending = $4,
+    user_agent = COALESCE($5, user_agent),
+    ip_hash = COALESCE($6, ip_hash),
+    last_seen = NOW()
+WHERE
+    uuid = $1;
+
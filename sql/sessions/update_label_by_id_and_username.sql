@@ -0,0 +1,9 @@
+UPDATE
+    sessions
+SET
+    label = $3
+WHERE
+    id = $1
+    AND username = $2
+    AND NOT pending;
+
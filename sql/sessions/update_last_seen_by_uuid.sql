@@ -0,0 +1,7 @@
+UPDATE
+    sessions
+SET
+    last_seen = NOW()
+WHERE
+    uuid = $1;
+
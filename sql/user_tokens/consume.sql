@@ -0,0 +1,13 @@
+UPDATE
+    user_tokens
+SET
+    used_at = NOW()
+WHERE
+    hash = $1
+    AND kind = $2
+    AND used_at IS NULL
+    AND expires_at > NOW()
+RETURNING
+    username,
+    email;
+
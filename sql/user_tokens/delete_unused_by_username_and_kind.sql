@@ -0,0 +1,5 @@
+DELETE FROM user_tokens
+WHERE username = $1
+    AND kind = $2
+    AND used_at IS NULL;
+
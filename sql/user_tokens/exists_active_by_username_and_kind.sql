@@ -0,0 +1,12 @@
+SELECT
+    EXISTS (
+        SELECT
+            *
+        FROM
+            user_tokens
+        WHERE
+            username = $1
+            AND kind = $2
+            AND used_at IS NULL
+            AND expires_at > NOW()) AS "exists!";
+
@@ -0,0 +1,3 @@
+INSERT INTO user_tokens (hash, username, kind, email, expires_at)
+    VALUES ($1, $2, $3, $4, $5);
+
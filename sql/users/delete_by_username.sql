@@ -0,0 +1,3 @@
+DELETE FROM users
+WHERE username = $1;
+
@@ -1,7 +1,8 @@
 UPDATE
     users
 SET
-    email = NULL
+    email = NULL,
+    email_verified = FALSE
 WHERE
     username = $1;
 
@@ -0,0 +1,9 @@
+SELECT
+    EXISTS (
+        SELECT
+            *
+        FROM
+            users
+        WHERE
+            username = $1) AS "exists!";
+
@@ -1,7 +1,8 @@
 UPDATE
     users
 SET
-    email = $2
+    email = $2,
+    email_verified = FALSE
 WHERE
     username = $1;
 
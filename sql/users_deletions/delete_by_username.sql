@@ -0,0 +1,3 @@
+DELETE FROM users_deletions
+WHERE username = $1;
+
@@ -0,0 +1,7 @@
+SELECT
+    *
+FROM
+    users_deletions
+WHERE
+    username = $1;
+
@@ -0,0 +1,6 @@
+INSERT INTO users_deletions (username, purge_uids, scheduled_at)
+    VALUES ($1, $2, $3)
+ON CONFLICT (username)
+    DO UPDATE SET
+        purge_uids = EXCLUDED.purge_uids, scheduled_at = EXCLUDED.scheduled_at;
+
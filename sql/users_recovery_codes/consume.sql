@@ -0,0 +1,9 @@
+UPDATE
+    users_recovery_codes
+SET
+    used_at = NOW()
+WHERE
+    username = $1
+    AND hash = $2
+    AND used_at IS NULL;
+
@@ -0,0 +1,8 @@
+SELECT
+    COUNT(*) AS "count!"
+FROM
+    users_recovery_codes
+WHERE
+    username = $1
+    AND used_at IS NULL;
+
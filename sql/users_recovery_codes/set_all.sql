@@ -0,0 +1,7 @@
+INSERT INTO users_recovery_codes (username, hash)
+SELECT
+    $1,
+    *
+FROM
+    UNNEST($2::text[]);
+
@@ -0,0 +1,5 @@
+INSERT INTO users_roles (username, role)
+    VALUES ($1, $2)
+ON CONFLICT
+    DO NOTHING;
+
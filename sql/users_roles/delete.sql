@@ -0,0 +1,4 @@
+DELETE FROM users_roles
+WHERE username = $1
+    AND role = $2;
+
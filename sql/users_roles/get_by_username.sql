@@ -1,7 +1,7 @@
 SELECT
-    username
+    role
 FROM
-    admins
+    users_roles
 WHERE
     username = $1;
 
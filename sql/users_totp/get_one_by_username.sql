@@ -0,0 +1,8 @@
+SELECT
+    secret,
+    enabled
+FROM
+    users_totp
+WHERE
+    username = $1;
+
@@ -0,0 +1,6 @@
+INSERT INTO users_totp (username, secret)
+    VALUES ($1, $2)
+ON CONFLICT (username)
+    DO UPDATE SET
+        secret = EXCLUDED.secret, enabled = FALSE, last_step = NULL, created_at = NOW();
+
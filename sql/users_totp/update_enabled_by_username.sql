@@ -0,0 +1,8 @@
+UPDATE
+    users_totp
+SET
+    enabled = TRUE,
+    last_step = $2
+WHERE
+    username = $1;
+
@@ -0,0 +1,9 @@
+UPDATE
+    users_totp
+SET
+    last_step = $2
+WHERE
+    username = $1
+    AND (last_step IS NULL
+        OR last_step < $2);
+
@@ -0,0 +1,4 @@
+DELETE FROM versions
+WHERE game = $1
+    AND version = $2;
+
@@ -0,0 +1,10 @@
+SELECT
+    game,
+    version,
+    released_at
+FROM
+    versions
+ORDER BY
+    game,
+    released_at;
+
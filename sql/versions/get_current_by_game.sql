@@ -0,0 +1,11 @@
+SELECT
+    version
+FROM
+    versions
+WHERE
+    game = $1
+    AND released_at <= $2
+ORDER BY
+    released_at DESC
+LIMIT 1;
+
@@ -0,0 +1,6 @@
+INSERT INTO versions (game, version, released_at)
+    VALUES ($1, $2, $3)
+ON CONFLICT (game, version)
+    DO UPDATE SET
+        released_at = EXCLUDED.released_at;
+
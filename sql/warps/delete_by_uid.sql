@@ -0,0 +1,38 @@
+WITH departure AS (
+    DELETE FROM warps_departure
+    WHERE uid = $1),
+standard AS (
+    DELETE FROM warps_standard
+    WHERE uid = $1),
+special AS (
+    DELETE FROM warps_special
+    WHERE uid = $1),
+lc AS (
+    DELETE FROM warps_lc
+    WHERE uid = $1),
+stats_standard AS (
+    DELETE FROM warps_stats_standard
+    WHERE uid = $1),
+stats_special AS (
+    DELETE FROM warps_stats_special
+    WHERE uid = $1),
+stats_lc AS (
+    DELETE FROM warps_stats_lc
+    WHERE uid = $1),
+stats_global_standard AS (
+    DELETE FROM warps_stats_global_standard
+    WHERE uid = $1),
+stats_global_special AS (
+    DELETE FROM warps_stats_global_special
+    WHERE uid = $1),
+stats_global_lc AS (
+    DELETE FROM warps_stats_global_lc
+    WHERE uid = $1),
+import_jobs_cursors AS (
+    DELETE FROM import_jobs_cursors
+    WHERE game = 'hsr'
+        AND uid = $1)
+DELETE FROM import_jobs
+WHERE game = 'hsr'
+    AND uid = $1;
+
@@ -1,6 +1,6 @@
-INSERT INTO warps_stats_special (uid, luck_4, luck_5, win_rate, win_streak, loss_streak)
-    VALUES ($1, $2, $3, $4, $5, $6)
+INSERT INTO warps_stats_special (uid, luck_4, luck_5, win_rate, win_streak, loss_streak, likelihood_4, likelihood_5, likelihood_win, expected_5, actual_5, count)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
 ON CONFLICT (uid)
     DO UPDATE SET
-        luck_4 = EXCLUDED.luck_4, luck_5 = EXCLUDED.luck_5, win_rate = EXCLUDED.win_rate, win_streak = EXCLUDED.win_streak, loss_streak = EXCLUDED.loss_streak;
+        luck_4 = EXCLUDED.luck_4, luck_5 = EXCLUDED.luck_5, win_rate = EXCLUDED.win_rate, win_streak = EXCLUDED.win_streak, loss_streak = EXCLUDED.loss_streak, likelihood_4 = EXCLUDED.likelihood_4, likelihood_5 = EXCLUDED.likelihood_5, likelihood_win = EXCLUDED.likelihood_win, expected_5 = EXCLUDED.expected_5, actual_5 = EXCLUDED.actual_5, count = EXCLUDED.count;
 
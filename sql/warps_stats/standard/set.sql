@@ -1,6 +1,6 @@
-INSERT INTO warps_stats_standard (uid, luck_4, luck_5)
-    VALUES ($1, $2, $3)
+INSERT INTO warps_stats_standard (uid, luck_4, luck_5, likelihood_4, likelihood_5, expected_5, actual_5, count)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
 ON CONFLICT (uid)
     DO UPDATE SET
-        luck_4 = EXCLUDED.luck_4, luck_5 = EXCLUDED.luck_5;
+        luck_4 = EXCLUDED.luck_4, luck_5 = EXCLUDED.luck_5, likelihood_4 = EXCLUDED.likelihood_4, likelihood_5 = EXCLUDED.likelihood_5, expected_5 = EXCLUDED.expected_5, actual_5 = EXCLUDED.actual_5, count = EXCLUDED.count;
 
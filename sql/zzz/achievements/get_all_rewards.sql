@@ -0,0 +1,6 @@
+SELECT
+    id,
+    polychromes
+FROM
+    zzz_achievements;
+
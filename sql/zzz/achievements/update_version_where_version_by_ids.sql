@@ -0,0 +1,8 @@
+UPDATE
+    zzz_achievements
+SET
+    version = $3
+WHERE
+    id = ANY ($1)
+    AND version IS NOT DISTINCT FROM $2;
+
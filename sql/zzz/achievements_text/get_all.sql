@@ -0,0 +1,8 @@
+SELECT
+    id,
+    language,
+    name,
+    description
+FROM
+    zzz_achievements_text;
+
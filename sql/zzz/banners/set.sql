@@ -0,0 +1,6 @@
+INSERT INTO zzz_banners (id, name, start, "end", character, w_engine)
+    VALUES ($1, $2, $3, $4, $5, $6)
+ON CONFLICT (id)
+    DO UPDATE SET
+        name = EXCLUDED.name, start = EXCLUDED.start, "end" = EXCLUDED."end", character = EXCLUDED.character, w_engine = EXCLUDED.w_engine;
+
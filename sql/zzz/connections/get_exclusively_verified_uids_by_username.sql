@@ -0,0 +1,17 @@
+SELECT
+    uid
+FROM
+    zzz_connections
+WHERE
+    username = $1
+    AND verified
+    AND NOT EXISTS (
+        SELECT
+            *
+        FROM
+            zzz_connections other
+        WHERE
+            other.uid = zzz_connections.uid
+            AND other.username != $1
+            AND other.verified);
+
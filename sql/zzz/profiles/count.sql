@@ -0,0 +1,10 @@
+SELECT
+    COUNT(*)
+FROM
+    zzz_profiles
+WHERE ($1::text IS NULL
+    OR region = $1)
+AND ($2::text IS NULL
+    OR LOWER(name)
+    LIKE '%' || LOWER($2) || '%');
+
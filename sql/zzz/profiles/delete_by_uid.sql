@@ -0,0 +1,3 @@
+DELETE FROM zzz_profiles
+WHERE uid = $1;
+
@@ -0,0 +1,14 @@
+SELECT
+    COUNT(*)
+FROM
+    zzz_connections
+    JOIN zzz_users_achievements_completed ON zzz_users_achievements_completed.username = zzz_connections.username
+WHERE
+    zzz_connections.uid = $1
+    AND zzz_connections.verified
+GROUP BY
+    zzz_connections.username
+ORDER BY
+    COUNT(*) DESC
+LIMIT 1;
+
@@ -0,0 +1,23 @@
+SELECT
+    *
+FROM (
+    SELECT
+        RANK() OVER (ORDER BY level DESC, uid) global_rank,
+        RANK() OVER (PARTITION BY region ORDER BY level DESC, uid) regional_rank,
+        *
+    FROM
+        zzz_profiles) ranked
+WHERE ($1::text IS NULL
+    OR region = $1)
+AND ($2::text IS NULL
+    OR LOWER(name)
+    LIKE '%' || LOWER($2) || '%')
+ORDER BY
+    (
+        CASE WHEN $2 IS NOT NULL THEN
+            LEVENSHTEIN (name, $2)
+        ELSE
+            global_rank
+        END)
+LIMIT $3 OFFSET $4;
+
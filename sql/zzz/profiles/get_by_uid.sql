@@ -0,0 +1,12 @@
+SELECT
+    *
+FROM (
+    SELECT
+        RANK() OVER (ORDER BY achievement_count DESC, timestamp) global_rank,
+        RANK() OVER (PARTITION BY region ORDER BY achievement_count DESC, timestamp) regional_rank,
+        *
+    FROM
+        zzz_profiles) ranked
+WHERE
+    uid = $1;
+
@@ -0,0 +1,12 @@
+SELECT
+    *
+FROM (
+    SELECT
+        RANK() OVER (ORDER BY level DESC, uid) global_rank,
+        RANK() OVER (PARTITION BY region ORDER BY level DESC, uid) regional_rank,
+        *
+    FROM
+        zzz_profiles) ranked
+WHERE
+    uid = $1;
+
@@ -0,0 +1,6 @@
+INSERT INTO zzz_profiles (uid, region, name, level, signature, avatar_id, achievement_count, timestamp, updated_at)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT (uid)
+    DO UPDATE SET
+        name = EXCLUDED.name, level = EXCLUDED.level, signature = EXCLUDED.signature, avatar_id = EXCLUDED.avatar_id, achievement_count = EXCLUDED.achievement_count, timestamp = EXCLUDED.timestamp, updated_at = EXCLUDED.updated_at;
+
@@ -0,0 +1,44 @@
+WITH standard AS (
+    DELETE FROM zzz_signals_standard
+    WHERE uid = $1),
+special AS (
+    DELETE FROM zzz_signals_special
+    WHERE uid = $1),
+w_engine AS (
+    DELETE FROM zzz_signals_w_engine
+    WHERE uid = $1),
+bangboo AS (
+    DELETE FROM zzz_signals_bangboo
+    WHERE uid = $1),
+stats_standard AS (
+    DELETE FROM zzz_signals_stats_standard
+    WHERE uid = $1),
+stats_special AS (
+    DELETE FROM zzz_signals_stats_special
+    WHERE uid = $1),
+stats_w_engine AS (
+    DELETE FROM zzz_signals_stats_w_engine
+    WHERE uid = $1),
+stats_bangboo AS (
+    DELETE FROM zzz_signals_stats_bangboo
+    WHERE uid = $1),
+stats_global_standard AS (
+    DELETE FROM zzz_signals_stats_global_standard
+    WHERE uid = $1),
+stats_global_special AS (
+    DELETE FROM zzz_signals_stats_global_special
+    WHERE uid = $1),
+stats_global_w_engine AS (
+    DELETE FROM zzz_signals_stats_global_w_engine
+    WHERE uid = $1),
+stats_global_bangboo AS (
+    DELETE FROM zzz_signals_stats_global_bangboo
+    WHERE uid = $1),
+import_jobs_cursors AS (
+    DELETE FROM import_jobs_cursors
+    WHERE game = 'zzz'
+        AND uid = $1)
+DELETE FROM import_jobs
+WHERE game = 'zzz'
+    AND uid = $1;
+
@@ -1,7 +1,8 @@
 SELECT
     zzz_signals_special.character,
     zzz_signals_special.w_engine,
-    COALESCE(zzz_characters.rarity, zzz_w_engines.rarity) AS rarity
+    COALESCE(zzz_characters.rarity, zzz_w_engines.rarity) AS rarity,
+    zzz_signals_special.timestamp
 FROM
     zzz_signals_special
     LEFT JOIN zzz_characters ON zzz_characters.id = character
@@ -0,0 +1,7 @@
+SELECT DISTINCT
+    uid
+FROM
+    zzz_signals_special
+WHERE
+    timestamp >= $1;
+
@@ -0,0 +1,7 @@
+SELECT
+    min(timestamp)
+FROM
+    zzz_signals_standard
+WHERE
+    uid = $1;
+
@@ -0,0 +1,7 @@
+SELECT
+    min(timestamp)
+FROM
+    zzz_signals_w_engine
+WHERE
+    uid = $1;
+
@@ -0,0 +1,7 @@
+SELECT DISTINCT
+    uid
+FROM
+    zzz_signals_w_engine
+WHERE
+    timestamp >= $1;
+
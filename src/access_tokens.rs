@@ -0,0 +1,169 @@
+use actix_session::SessionExt;
+use actix_web::{
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    error::ErrorInternalServerError,
+    http::header,
+    middleware::Next,
+    web, Error, HttpResponse,
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use crate::database;
+
+const PREFIX: &str = "sdb_";
+
+#[derive(
+    Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, Serialize, Deserialize, ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    ReadAchievements,
+    WriteAchievements,
+    ImportGacha,
+}
+
+// A trailing * matches everything below the path
+const ROUTES: &[(Scope, &[(&str, &str)])] = &[
+    (
+        Scope::ReadAchievements,
+        &[
+            ("GET", "/api/users/me"),
+            ("GET", "/api/users/me/achievements/*"),
+            ("GET", "/api/users/me/gi/achievements/*"),
+            ("GET", "/api/users/me/zzz/achievements/*"),
+            ("GET", "/api/pages/achievement-tracker"),
+            ("GET", "/api/pages/gi/achievement-tracker"),
+            ("GET", "/api/pages/zzz/achievement-tracker"),
+        ],
+    ),
+    (
+        Scope::WriteAchievements,
+        &[
+            ("GET", "/api/users/me"),
+            ("PUT", "/api/users/me/achievements/*"),
+            ("PUT", "/api/users/me/gi/achievements/*"),
+            ("PUT", "/api/users/me/zzz/achievements/*"),
+            ("DELETE", "/api/users/me/achievements/*"),
+            ("DELETE", "/api/users/me/gi/achievements/*"),
+            ("DELETE", "/api/users/me/zzz/achievements/*"),
+            ("PUT", "/api/users/me/import"),
+        ],
+    ),
+    (
+        Scope::ImportGacha,
+        &[
+            ("GET", "/api/users/me"),
+            ("GET", "/api/users/me/uids"),
+            ("GET", "/api/users/me/gi/uids"),
+            ("GET", "/api/users/me/zzz/uids"),
+            ("POST", "/api/warps-import"),
+            ("GET", "/api/warps-import/*"),
+            ("POST", "/api/gi/wishes-import"),
+            ("GET", "/api/gi/wishes-import/*"),
+            ("POST", "/api/zzz/signals-import"),
+            ("GET", "/api/zzz/signals-import/*"),
+            ("POST", "/api/uigf-import"),
+            ("POST", "/api/srgf-warps-import"),
+            ("POST", "/api/pom-warps-import/*"),
+            ("POST", "/api/srs-warps-import/*"),
+            ("POST", "/api/gi/paimon-wishes-import"),
+            ("POST", "/api/zzz/rng-signals-import"),
+        ],
+    ),
+];
+
+pub fn generate() -> String {
+    let mut bytes = [0; 32];
+    rand::rng().fill_bytes(&mut bytes);
+
+    format!("{PREFIX}{}", BASE64_URL_SAFE_NO_PAD.encode(bytes))
+}
+
+pub fn hash(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn scopes(method: &str, path: &str) -> Vec<Scope> {
+    ROUTES
+        .iter()
+        .filter(|(_, routes)| {
+            routes.iter().any(|(m, p)| {
+                *m == method
+                    && match p.strip_suffix('*') {
+                        Some(prefix) => path.starts_with(prefix),
+                        None => path == *p,
+                    }
+            })
+        })
+        .map(|&(scope, _)| scope)
+        .collect()
+}
+
+/// Lets a bearer token stand in for the session username on the routes its scopes cover
+pub async fn authenticate(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|token| token.starts_with(PREFIX))
+        .map(|token| token.trim().to_string());
+
+    let session = req.get_session();
+
+    let (Some(token), Ok(None), Some(pool)) = (
+        token,
+        session.get::<String>("username"),
+        req.app_data::<web::Data<PgPool>>().cloned(),
+    ) else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+
+    let Some(access_token) = database::access_tokens::get_by_hash(&hash(&token), &pool)
+        .await
+        .map_err(ErrorInternalServerError)?
+    else {
+        let response = HttpResponse::Unauthorized().finish();
+
+        return Ok(req.into_response(response).map_into_right_body());
+    };
+
+    let scopes = scopes(req.method().as_str(), req.path());
+
+    if scopes.is_empty() {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+
+    if !access_token
+        .scopes
+        .iter()
+        .any(|scope| scopes.iter().any(|s| s.to_string() == *scope))
+    {
+        let response = HttpResponse::Forbidden().finish();
+
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    database::access_tokens::update_last_used_at_by_id(access_token.id, &pool)
+        .await
+        .map_err(ErrorInternalServerError)?;
+
+    session.insert("username", access_token.username)?;
+
+    let response = next.call(req).await;
+
+    // Without a cookie an empty session isn't stored, so the token never turns into a session
+    session.clear();
+
+    Ok(response?.map_into_left_body())
+}
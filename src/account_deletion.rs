@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database, enka, mihomo,
+    scheduler::{self, Schedule},
+};
+
+/// Days between requesting the deletion and it happening, unless asked for immediately
+pub const GRACE_DAYS: i64 = 14;
+
+pub async fn spawn(pool: PgPool) {
+    scheduler::register(
+        "account_deletion",
+        Schedule::Every(Duration::from_secs(60 * 60)),
+        move || {
+            let pool = pool.clone();
+
+            async move { delete_due(&pool).await }
+        },
+    );
+}
+
+async fn delete_due(pool: &PgPool) -> Result<()> {
+    for user_deletion in database::users_deletions::get_due(pool).await? {
+        let username = user_deletion.username;
+
+        if let Err(e) = delete(&username, user_deletion.purge_uids, pool).await {
+            error!("Deleting account {username} failed with {e}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes the user and everything that references it and pseudonymises it in the audit log.
+/// With `purge_uids` the pulls, profiles and cached showcases of uids that only this user
+/// verified are deleted too
+pub async fn delete(username: &str, purge_uids: bool, pool: &PgPool) -> Result<()> {
+    if purge_uids {
+        for uid in
+            database::connections::get_exclusively_verified_uids_by_username(username, pool).await?
+        {
+            database::warps::delete_by_uid(uid, pool).await?;
+            database::mihomo::delete_by_uid(uid, pool).await?;
+            mihomo::delete(uid)?;
+        }
+
+        for uid in
+            database::gi::connections::get_exclusively_verified_uids_by_username(username, pool)
+                .await?
+        {
+            database::gi::wishes::delete_by_uid(uid, pool).await?;
+            database::gi::profiles::delete_by_uid(uid, pool).await?;
+            enka::gi::delete(uid)?;
+        }
+
+        for uid in
+            database::zzz::connections::get_exclusively_verified_uids_by_username(username, pool)
+                .await?
+        {
+            database::zzz::signals::delete_by_uid(uid, pool).await?;
+            database::zzz::profiles::delete_by_uid(uid, pool).await?;
+            enka::zzz::delete(uid)?;
+        }
+    }
+
+    // Random, so it can't be traced back to the username and a later account with the same name
+    // gets a different one. Entries of the same deletion still share it
+    let pseudonym = format!("deleted:{}", Uuid::new_v4().simple());
+
+    database::audit_log::pseudonymise_by_username(username, &pseudonym, pool).await?;
+
+    // Connections, achievements, sessions, tokens etc. cascade
+    database::users::delete_by_username(username, pool).await?;
+
+    info!("Deleted account {username}");
+
+    Ok(())
+}
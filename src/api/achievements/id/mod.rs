@@ -43,8 +43,10 @@ async fn get_achievement(
     let mut achievement = Achievement::from(db_achievement);
 
     if let Some(set) = achievement.set {
-        achievement.related =
-            Some(database::achievements::get_all_related_ids(achievement.id, set, &pool).await?);
+        achievement.related = Some(
+            database::achievements::get_all_related_ids(achievement.id, set, pool.get_ref())
+                .await?,
+        );
     }
 
     Ok(HttpResponse::Ok().json(achievement))
@@ -8,7 +8,9 @@ use utoipa::{OpenApi, ToSchema};
 
 use crate::{
     api::{ApiResult, LanguageParams},
-    database, Difficulty,
+    database,
+    permissions::{self, Permission},
+    Difficulty,
 };
 
 use crate::Language;
@@ -79,9 +81,11 @@ impl From<database::achievements::DbAchievement> for Achievement {
             impossible: db_achievement.impossible,
             set: db_achievement.set,
             related: None,
-            percent: (!db_achievement.impossible)
-                .then_some(db_achievement.percent.unwrap_or_default())
-                .unwrap_or_default(),
+            percent: if db_achievement.impossible {
+                0.0
+            } else {
+                db_achievement.percent.unwrap_or_default()
+            },
         }
     }
 }
@@ -122,7 +126,8 @@ async fn get_achievements(
     for achievement in &mut achievements {
         if let Some(set) = achievement.set {
             achievement.related = Some(
-                database::achievements::get_all_related_ids(achievement.id, set, &pool).await?,
+                database::achievements::get_all_related_ids(achievement.id, set, pool.get_ref())
+                    .await?,
             );
         }
     }
@@ -163,17 +168,20 @@ async fn put_achievements(
     achievements: web::Json<Vec<UpdateAchievement>>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
-    let admin = if let Ok(Some(username)) = session.get::<String>("username") {
-        database::admins::exists(&username, &pool).await?
-    } else {
-        false
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::Forbidden().finish());
     };
 
-    if !admin {
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
     for achievement in achievements.iter() {
+        let before = database::achievements::get_one_by_id(achievement.id, Language::En, &pool)
+            .await
+            .ok()
+            .map(Achievement::from);
+
         let update_achievement = database::achievements::DbUpdateAchievement {
             id: achievement.id,
             version: achievement.version.clone(),
@@ -188,6 +196,21 @@ async fn put_achievements(
         };
 
         database::achievements::update_achievement_by_id(&update_achievement, &pool).await?;
+
+        let after = database::achievements::get_one_by_id(achievement.id, Language::En, &pool)
+            .await
+            .ok()
+            .map(Achievement::from);
+
+        database::audit_log::add(
+            &username,
+            "achievement.update",
+            &format!("achievements/{}", achievement.id),
+            before.map(serde_json::to_value).transpose()?,
+            after.map(serde_json::to_value).transpose()?,
+            &pool,
+        )
+        .await?;
     }
 
     Ok(HttpResponse::Ok().finish())
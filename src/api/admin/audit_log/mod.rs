@@ -0,0 +1,112 @@
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::{
+    api::ApiResult,
+    database::{self, audit_log::AuditLogFilter},
+    permissions::{self, Permission},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/audit-log")),
+    paths(get_audit_log),
+    components(schemas(AuditLogEntry))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_audit_log);
+}
+
+#[derive(Deserialize, IntoParams)]
+struct AuditLogParams {
+    username: Option<String>,
+    action: Option<String>,
+    /// e.g. `banners/1001` or `gi/achievements/81000`
+    target: Option<String>,
+    /// Only entries older than this id, for paging
+    before: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct AuditLogEntry {
+    id: i64,
+    username: String,
+    action: String,
+    target: String,
+    #[schema(value_type = Option<Object>)]
+    before: Option<Value>,
+    #[schema(value_type = Option<Object>)]
+    after: Option<Value>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<database::audit_log::DbAuditLog> for AuditLogEntry {
+    fn from(audit_log: database::audit_log::DbAuditLog) -> Self {
+        AuditLogEntry {
+            id: audit_log.id,
+            username: audit_log.username,
+            action: audit_log.action,
+            target: audit_log.target,
+            before: audit_log.before,
+            after: audit_log.after,
+            created_at: audit_log.created_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "admin/audit-log",
+    get,
+    path = "/api/admin/audit-log",
+    params(AuditLogParams),
+    responses(
+        (status = 200, description = "Newest entries first", body = Vec<AuditLogEntry>),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to view the audit log"),
+    ),
+    security(("admin" = []))
+)]
+#[get("/api/admin/audit-log")]
+async fn get_audit_log(
+    session: Session,
+    audit_log_params: web::Query<AuditLogParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ViewAuditLog, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let audit_log_params = audit_log_params.into_inner();
+
+    let filter = AuditLogFilter {
+        username: audit_log_params.username,
+        action: audit_log_params.action,
+        target: audit_log_params.target,
+        before_id: audit_log_params.before,
+        limit: audit_log_params.limit.unwrap_or(100).clamp(1, 1000),
+    };
+
+    let entries: Vec<AuditLogEntry> = database::audit_log::get(&filter, &pool)
+        .await?
+        .into_iter()
+        .map(AuditLogEntry::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(entries))
+}
@@ -3,7 +3,11 @@ use actix_web::{post, web, HttpResponse, Responder};
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database};
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -40,7 +44,7 @@ async fn post_delete_unofficial_warps(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::DeleteUnofficialGacha, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
@@ -53,5 +57,15 @@ async fn post_delete_unofficial_warps(
     database::warps::special::delete_unofficial(uid, &pool).await?;
     database::warps::lc::delete_unofficial(uid, &pool).await?;
 
+    database::audit_log::add(
+        &username,
+        "warps.delete_unofficial",
+        &format!("warps/{uid}"),
+        None,
+        None,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
@@ -3,7 +3,11 @@ use actix_web::{post, web, HttpResponse, Responder};
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database};
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -40,7 +44,7 @@ async fn post_delete_unofficial_wishes(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::DeleteUnofficialGacha, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
@@ -54,5 +58,15 @@ async fn post_delete_unofficial_wishes(
     database::gi::wishes::weapon::delete_unofficial(uid, &pool).await?;
     database::gi::wishes::chronicled::delete_unofficial(uid, &pool).await?;
 
+    database::audit_log::add(
+        &username,
+        "wishes.delete_unofficial",
+        &format!("gi/wishes/{uid}"),
+        None,
+        None,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
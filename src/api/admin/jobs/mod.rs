@@ -0,0 +1,103 @@
+mod name;
+
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::ApiResult,
+    permissions::{self, Permission},
+    scheduler::{self, Schedule},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/jobs")),
+    paths(get_jobs),
+    components(schemas(Job))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(name::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_jobs).configure(name::configure);
+}
+
+#[derive(Serialize, ToSchema)]
+struct Job {
+    name: String,
+    /// Seconds between the starts of two runs
+    every: Option<u64>,
+    /// Seconds between the end of one run and the start of the next
+    after: Option<u64>,
+    running: bool,
+    last_started_at: Option<DateTime<Utc>>,
+    /// Seconds
+    last_duration: Option<f64>,
+    last_succeeded_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    /// Failures since the last success. Failed runs are retried with backoff
+    failures: u32,
+    next_run_at: Option<DateTime<Utc>>,
+}
+
+impl From<&scheduler::Job> for Job {
+    fn from(job: &scheduler::Job) -> Self {
+        let (every, after) = match job.schedule {
+            Schedule::Every(interval) => (Some(interval.as_secs()), None),
+            Schedule::After(delay) => (None, Some(delay.as_secs())),
+        };
+
+        let status = job.status();
+
+        Job {
+            name: job.name.to_string(),
+            every,
+            after,
+            running: status.running,
+            last_started_at: status.last_started_at,
+            last_duration: status.last_duration,
+            last_succeeded_at: status.last_succeeded_at,
+            last_error: status.last_error,
+            failures: status.failures,
+            next_run_at: status.next_run_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "admin/jobs",
+    get,
+    path = "/api/admin/jobs",
+    responses(
+        (status = 200, description = "Background jobs", body = Vec<Job>),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to manage jobs"),
+    ),
+    security(("admin" = []))
+)]
+#[get("/api/admin/jobs")]
+async fn get_jobs(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ManageJobs, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let jobs: Vec<Job> = scheduler::jobs()
+        .iter()
+        .map(|job| Job::from(job.as_ref()))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(jobs))
+}
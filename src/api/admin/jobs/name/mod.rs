@@ -0,0 +1,74 @@
+use actix_session::Session;
+use actix_web::{post, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+    scheduler,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/jobs/{name}")),
+    paths(post_job)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(post_job);
+}
+
+#[utoipa::path(
+    tag = "admin/jobs/{name}",
+    post,
+    path = "/api/admin/jobs/{name}",
+    responses(
+        (status = 202, description = "Job will run now"),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to manage jobs"),
+        (status = 404, description = "Unknown job"),
+        (status = 409, description = "Job is already running"),
+    ),
+    security(("admin" = []))
+)]
+#[post("/api/admin/jobs/{name}")]
+async fn post_job(
+    session: Session,
+    name: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ManageJobs, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let Some(job) = scheduler::get(&name) else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    if !job.trigger() {
+        return Ok(HttpResponse::Conflict().finish());
+    }
+
+    database::audit_log::add(
+        &username,
+        "job.trigger",
+        &format!("jobs/{name}"),
+        None,
+        None,
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().finish())
+}
@@ -1,18 +1,30 @@
+mod audit_log;
 mod delete_unofficial_signals;
 mod delete_unofficial_warps;
 mod delete_unofficial_wishes;
+mod jobs;
+mod roles;
+mod versions;
 
 use actix_web::web;
 
 pub fn openapi() -> utoipa::openapi::OpenApi {
-    let mut openapi = delete_unofficial_signals::openapi();
+    let mut openapi = audit_log::openapi();
+    openapi.merge(delete_unofficial_signals::openapi());
     openapi.merge(delete_unofficial_warps::openapi());
     openapi.merge(delete_unofficial_wishes::openapi());
+    openapi.merge(jobs::openapi());
+    openapi.merge(roles::openapi());
+    openapi.merge(versions::openapi());
     openapi
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.configure(delete_unofficial_signals::configure)
+    cfg.configure(audit_log::configure)
+        .configure(delete_unofficial_signals::configure)
         .configure(delete_unofficial_warps::configure)
-        .configure(delete_unofficial_wishes::configure);
+        .configure(delete_unofficial_wishes::configure)
+        .configure(jobs::configure)
+        .configure(roles::configure)
+        .configure(versions::configure);
 }
@@ -0,0 +1,11 @@
+mod username;
+
+use actix_web::web;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    username::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.configure(username::configure);
+}
@@ -0,0 +1,59 @@
+mod role;
+
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::ApiResult,
+    permissions::{self, Permission, Role},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/roles/{username}")),
+    paths(get_roles),
+    components(schemas(Role))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(role::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_roles).configure(role::configure);
+}
+
+#[utoipa::path(
+    tag = "admin/roles/{username}",
+    get,
+    path = "/api/admin/roles/{username}",
+    responses(
+        (status = 200, description = "Roles of the user", body = Vec<Role>),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to manage roles"),
+    ),
+    security(("admin" = []))
+)]
+#[get("/api/admin/roles/{username}")]
+async fn get_roles(
+    session: Session,
+    target: web::Path<String>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ManageRoles, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let roles = permissions::roles(&target.to_lowercase(), &pool).await?;
+
+    Ok(HttpResponse::Ok().json(roles))
+}
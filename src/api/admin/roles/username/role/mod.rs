@@ -0,0 +1,129 @@
+use actix_session::Session;
+use actix_web::{delete, put, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission, Role},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/roles/{username}/{role}")),
+    paths(put_role, delete_role)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(put_role).service(delete_role);
+}
+
+#[utoipa::path(
+    tag = "admin/roles/{username}/{role}",
+    put,
+    path = "/api/admin/roles/{username}/{role}",
+    responses(
+        (status = 200, description = "Granted role"),
+        (status = 400, description = "Not logged in or unknown user"),
+        (status = 403, description = "Not allowed to manage roles"),
+    ),
+    security(("admin" = []))
+)]
+#[put("/api/admin/roles/{username}/{role}")]
+async fn put_role(
+    session: Session,
+    path: web::Path<(String, Role)>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ManageRoles, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let (target, role) = path.into_inner();
+    let target = target.to_lowercase();
+
+    if database::users::get_one_by_username(&target, &pool)
+        .await
+        .is_err()
+    {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let before = permissions::roles(&target, &pool).await?;
+
+    database::users_roles::add(&target, &role.to_string(), &pool).await?;
+
+    let after = permissions::roles(&target, &pool).await?;
+
+    database::audit_log::add(
+        &username,
+        "role.add",
+        &format!("users/{target}"),
+        Some(serde_json::to_value(before)?),
+        Some(serde_json::to_value(after)?),
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "admin/roles/{username}/{role}",
+    delete,
+    path = "/api/admin/roles/{username}/{role}",
+    responses(
+        (status = 200, description = "Revoked role"),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to manage roles"),
+        (status = 404, description = "User doesn't have the role"),
+    ),
+    security(("admin" = []))
+)]
+#[delete("/api/admin/roles/{username}/{role}")]
+async fn delete_role(
+    session: Session,
+    path: web::Path<(String, Role)>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::ManageRoles, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let (target, role) = path.into_inner();
+    let target = target.to_lowercase();
+
+    let before = permissions::roles(&target, &pool).await?;
+
+    if !database::users_roles::delete(&target, &role.to_string(), &pool).await? {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    let after = permissions::roles(&target, &pool).await?;
+
+    database::audit_log::add(
+        &username,
+        "role.remove",
+        &format!("users/{target}"),
+        Some(serde_json::to_value(before)?),
+        Some(serde_json::to_value(after)?),
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
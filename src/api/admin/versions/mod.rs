@@ -0,0 +1,79 @@
+mod version;
+
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/versions")),
+    paths(get_versions),
+    components(schemas(Version))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(version::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_versions).configure(version::configure);
+}
+
+#[derive(Serialize, ToSchema)]
+struct Version {
+    game: String,
+    version: String,
+    released_at: DateTime<Utc>,
+}
+
+impl From<database::versions::DbVersion> for Version {
+    fn from(version: database::versions::DbVersion) -> Self {
+        Version {
+            game: version.game,
+            version: version.version,
+            released_at: version.released_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "admin/versions",
+    get,
+    path = "/api/admin/versions",
+    responses(
+        (status = 200, description = "Release dates used to tag new achievements", body = Vec<Version>),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to edit achievements"),
+    ),
+    security(("admin" = []))
+)]
+#[get("/api/admin/versions")]
+async fn get_versions(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let versions: Vec<Version> = database::versions::get_all(&pool)
+        .await?
+        .into_iter()
+        .map(Version::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(versions))
+}
@@ -0,0 +1,129 @@
+use actix_session::Session;
+use actix_web::{delete, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{ApiResult, Game},
+    database,
+    permissions::{self, Permission},
+    update::dimbreath::first_seen,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "admin/versions/{game}/{version}")),
+    paths(put_version, delete_version),
+    components(schemas(VersionUpdate))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(put_version).service(delete_version);
+}
+
+#[derive(Deserialize, ToSchema)]
+struct VersionUpdate {
+    released_at: DateTime<Utc>,
+}
+
+#[utoipa::path(
+    tag = "admin/versions/{game}/{version}",
+    put,
+    path = "/api/admin/versions/{game}/{version}",
+    request_body = VersionUpdate,
+    responses(
+        (status = 200, description = "Set release date"),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to edit achievements"),
+    ),
+    security(("admin" = []))
+)]
+#[put("/api/admin/versions/{game}/{version}")]
+async fn put_version(
+    session: Session,
+    path: web::Path<(Game, String)>,
+    version_update: web::Json<VersionUpdate>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let (game, version) = path.into_inner();
+    let game = game.to_string();
+
+    database::versions::set(&game, &version, version_update.released_at, &pool).await?;
+
+    first_seen::retag(&game, &pool).await?;
+
+    database::audit_log::add(
+        &username,
+        "version.set",
+        &format!("versions/{game}/{version}"),
+        None,
+        Some(serde_json::json!({ "released_at": version_update.released_at })),
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "admin/versions/{game}/{version}",
+    delete,
+    path = "/api/admin/versions/{game}/{version}",
+    responses(
+        (status = 200, description = "Deleted release date"),
+        (status = 400, description = "Not logged in"),
+        (status = 403, description = "Not allowed to edit achievements"),
+        (status = 404, description = "Unknown version"),
+    ),
+    security(("admin" = []))
+)]
+#[delete("/api/admin/versions/{game}/{version}")]
+async fn delete_version(
+    session: Session,
+    path: web::Path<(Game, String)>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let (game, version) = path.into_inner();
+    let game = game.to_string();
+
+    if !database::versions::delete(&game, &version, &pool).await? {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    first_seen::retag(&game, &pool).await?;
+
+    database::audit_log::add(
+        &username,
+        "version.delete",
+        &format!("versions/{game}/{version}"),
+        None,
+        None,
+        &pool,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -8,6 +8,7 @@ use utoipa::{OpenApi, ToSchema};
 use crate::{
     api::{banners::Banner, ApiResult},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -71,7 +72,7 @@ async fn put_banner(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
@@ -86,8 +87,28 @@ async fn put_banner(
         light_cone: banner.light_cone,
     };
 
+    let before = database::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(Banner::from);
+
     database::banners::set(&db_banner, &pool).await?;
 
+    let after = database::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(Banner::from);
+
+    database::audit_log::add(
+        &username,
+        "banner.update",
+        &format!("banners/{id}"),
+        before.map(serde_json::to_value).transpose()?,
+        after.map(serde_json::to_value).transpose()?,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
 
@@ -110,13 +131,28 @@ async fn delete_banner(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
+    let before = database::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(Banner::from);
+
     database::banners::delete_by_id(*id, &pool).await?;
 
+    database::audit_log::add(
+        &username,
+        "banner.delete",
+        &format!("banners/{id}"),
+        before.map(serde_json::to_value).transpose()?,
+        None,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::{
+    api::{ApiResult, Game},
+    database::{self, changelog_entries::ChangelogEntryKind},
+    Language,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "changelog")),
+    paths(get_changelog),
+    components(schemas(Changelog, ChangelogEntry, ChangelogEntryKind))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_changelog);
+}
+
+#[derive(Deserialize, IntoParams)]
+struct ChangelogParams {
+    /// Only text changes in this language
+    lang: Option<Language>,
+    /// Only changelogs older than this id, for paging
+    before: Option<i64>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Changelog {
+    id: i64,
+    /// Upstream datamine commit
+    commit: String,
+    created_at: DateTime<Utc>,
+    entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct ChangelogEntry {
+    kind: ChangelogEntryKind,
+    /// Achievement, character, light cone, weapon or W-engine id
+    id: i32,
+    language: Option<Language>,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+#[utoipa::path(
+    tag = "changelog",
+    get,
+    path = "/api/{game}/changelog",
+    params(ChangelogParams),
+    responses(
+        (status = 200, description = "Datamine changes per update, newest first", body = Vec<Changelog>),
+    )
+)]
+#[get("/api/{game}/changelog")]
+async fn get_changelog(
+    game: web::Path<Game>,
+    changelog_params: web::Query<ChangelogParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let limit = changelog_params.limit.unwrap_or(10).clamp(1, 100);
+
+    let db_changelogs =
+        database::changelogs::get_by_game(&game.to_string(), changelog_params.before, limit, &pool)
+            .await?;
+
+    let ids: Vec<_> = db_changelogs.iter().map(|changelog| changelog.id).collect();
+
+    let db_entries = database::changelog_entries::get_by_changelogs(&ids, &pool).await?;
+
+    let mut changelogs = Vec::new();
+
+    for db_changelog in db_changelogs {
+        let entries = db_entries
+            .iter()
+            .filter(|entry| entry.changelog == db_changelog.id)
+            .filter_map(|entry| {
+                let language = match &entry.language {
+                    Some(language) => Some(Language::from_str(language).ok()?),
+                    None => None,
+                };
+
+                if let (Some(language), Some(lang)) = (language, changelog_params.lang) {
+                    if language != lang {
+                        return None;
+                    }
+                }
+
+                Some(ChangelogEntry {
+                    kind: ChangelogEntryKind::from_str(&entry.kind).ok()?,
+                    id: entry.id,
+                    language,
+                    before: entry.before.clone(),
+                    after: entry.after.clone(),
+                })
+            })
+            .collect();
+
+        changelogs.push(Changelog {
+            id: db_changelog.id,
+            commit: db_changelog.commit,
+            created_at: db_changelog.created_at,
+            entries,
+        });
+    }
+
+    Ok(HttpResponse::Ok().json(changelogs))
+}
@@ -0,0 +1,21 @@
+mod public_key;
+mod verify;
+
+use actix_web::web;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi()]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(public_key::openapi());
+    openapi.merge(verify::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.configure(public_key::configure)
+        .configure(verify::configure);
+}
@@ -0,0 +1,111 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::{
+    api::ApiResult,
+    signing::{self, SigningKeys},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "export/public-key")),
+    paths(get_public_key),
+    components(schemas(
+        KeyFormat,
+        Jwks,
+        Jwk
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_public_key);
+}
+
+#[derive(Default, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+enum KeyFormat {
+    #[default]
+    Jwk,
+    Pem,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct PublicKeyParams {
+    #[serde(default)]
+    format: KeyFormat,
+    kid: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Jwk {
+    kid: String,
+    kty: String,
+    crv: String,
+    x: String,
+    alg: String,
+    #[serde(rename = "use")]
+    r#use: String,
+}
+
+#[utoipa::path(
+    tag = "export/public-key",
+    get,
+    path = "/api/export/public-key",
+    params(PublicKeyParams),
+    responses(
+        (status = 200, description = "JWK set of all export keys, current key first", body = Jwks),
+        (status = 200, description = "SPKI PEM of a single key, the current one by default", body = String, content_type = "application/x-pem-file"),
+        (status = 404, description = "Unknown kid"),
+    )
+)]
+#[get("/api/export/public-key")]
+async fn get_public_key(
+    params: web::Query<PublicKeyParams>,
+    signing_keys: web::Data<SigningKeys>,
+) -> ApiResult<impl Responder> {
+    match params.format {
+        KeyFormat::Jwk => {
+            let keys: Vec<_> = signing_keys
+                .verifying_keys()
+                .iter()
+                .filter(|(kid, _)| params.kid.as_ref().is_none_or(|k| k == kid))
+                .map(|(kid, verifying_key)| Jwk {
+                    kid: kid.clone(),
+                    kty: "OKP".to_string(),
+                    crv: "Ed25519".to_string(),
+                    x: signing::x(verifying_key),
+                    alg: "EdDSA".to_string(),
+                    r#use: "sig".to_string(),
+                })
+                .collect();
+
+            if keys.is_empty() {
+                return Ok(HttpResponse::NotFound().finish());
+            }
+
+            Ok(HttpResponse::Ok().json(Jwks { keys }))
+        }
+        KeyFormat::Pem => {
+            let kid = params.kid.as_deref().unwrap_or(signing_keys.kid());
+
+            let Some(verifying_key) = signing_keys.get(kid) else {
+                return Ok(HttpResponse::NotFound().finish());
+            };
+
+            Ok(HttpResponse::Ok()
+                .content_type("application/x-pem-file")
+                .body(signing::pem(verifying_key)?))
+        }
+    }
+}
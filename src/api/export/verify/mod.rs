@@ -0,0 +1,85 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{api::users::me::export, signing::SigningKeys};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "export/verify")),
+    paths(post_verify),
+    components(schemas(
+        VerifyData,
+        Verification
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(post_verify);
+}
+
+/// Either a full export file in `data`, or only its `user` object byte for byte as exported together with a detached `signature` and optional `kid`.
+#[derive(Deserialize, ToSchema)]
+struct VerifyData {
+    data: String,
+    signature: Option<String>,
+    kid: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Verification {
+    valid: bool,
+    kid: Option<String>,
+    username: Option<String>,
+}
+
+#[utoipa::path(
+    tag = "export/verify",
+    post,
+    path = "/api/export/verify",
+    request_body = VerifyData,
+    responses(
+        (status = 200, description = "Verification result", body = Verification),
+        (status = 400, description = "Not an export"),
+    )
+)]
+#[post("/api/export/verify")]
+async fn post_verify(
+    verify_data: web::Json<VerifyData>,
+    signing_keys: web::Data<SigningKeys>,
+) -> impl Responder {
+    let verified = if let Some(signature) = &verify_data.signature {
+        export::verify_detached(
+            &verify_data.data,
+            signature,
+            verify_data.kid.as_deref(),
+            &signing_keys,
+        )
+    } else {
+        export::verify(&verify_data.data, &signing_keys)
+    };
+
+    let Ok(verified) = verified else {
+        return HttpResponse::BadRequest().finish();
+    };
+
+    let verification = match verified {
+        Some((user, kid)) => Verification {
+            valid: true,
+            kid: Some(kid),
+            username: Some(user.username),
+        },
+        None => Verification {
+            valid: false,
+            kid: None,
+            username: None,
+        },
+    };
+
+    HttpResponse::Ok().json(verification)
+}
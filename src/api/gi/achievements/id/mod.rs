@@ -6,6 +6,7 @@ use utoipa::OpenApi;
 use crate::{
     api::{gi::achievements::Achievement, ApiResult, LanguageParams},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -37,7 +38,7 @@ async fn get_gi_achievement(
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let admin = if let Ok(Some(username)) = session.get::<String>("username") {
-        database::admins::exists(&username, &pool).await?
+        permissions::has(&username, Permission::EditAchievements, &pool).await?
     } else {
         false
     };
@@ -53,7 +54,8 @@ async fn get_gi_achievement(
 
     if let Some(set) = achievement.set {
         achievement.related = Some(
-            database::gi::achievements::get_all_related_ids(achievement.id, set, &pool).await?,
+            database::gi::achievements::get_all_related_ids(achievement.id, set, pool.get_ref())
+                .await?,
         );
     }
 
@@ -8,6 +8,7 @@ use utoipa::{OpenApi, ToSchema};
 use crate::{
     api::{gi::banners::GiBanner, ApiResult},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -68,7 +69,7 @@ async fn put_gi_banner(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
@@ -83,8 +84,28 @@ async fn put_gi_banner(
         weapon: banner.weapon,
     };
 
+    let before = database::gi::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(GiBanner::from);
+
     database::gi::banners::set(&db_banner, &pool).await?;
 
+    let after = database::gi::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(GiBanner::from);
+
+    database::audit_log::add(
+        &username,
+        "banner.update",
+        &format!("gi/banners/{id}"),
+        before.map(serde_json::to_value).transpose()?,
+        after.map(serde_json::to_value).transpose()?,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
 
@@ -104,13 +125,28 @@ async fn delete_gi_banner(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
 
     if !admin {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
+    let before = database::gi::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(GiBanner::from);
+
     database::gi::banners::delete_by_id(*id, &pool).await?;
 
+    database::audit_log::add(
+        &username,
+        "banner.delete",
+        &format!("gi/banners/{id}"),
+        before.map(serde_json::to_value).transpose()?,
+        None,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
@@ -1,7 +1,7 @@
 mod achievements;
 mod banners;
 mod paimon_wishes_import;
-mod uigf_wishes_import;
+mod scores;
 mod wishes;
 mod wishes_import;
 
@@ -17,7 +17,7 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     openapi.merge(achievements::openapi());
     openapi.merge(banners::openapi());
     openapi.merge(paimon_wishes_import::openapi());
-    openapi.merge(uigf_wishes_import::openapi());
+    openapi.merge(scores::openapi());
     openapi.merge(wishes::openapi());
     openapi.merge(wishes_import::openapi());
     openapi
@@ -27,7 +27,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.configure(achievements::configure)
         .configure(banners::configure)
         .configure(paimon_wishes_import::configure)
-        .configure(uigf_wishes_import::configure)
+        .configure(scores::configure)
         .configure(wishes::configure)
         .configure(wishes_import::configure);
 }
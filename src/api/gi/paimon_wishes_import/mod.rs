@@ -5,7 +5,12 @@ use rand::seq::IndexedRandom as _;
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database, GiGachaType};
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+    GiGachaType,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -75,7 +80,7 @@ async fn post_paimon_warps_import(
         wish_uid.as_str().unwrap().parse()?
     };
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::AccessAllUids, &pool).await?;
 
     if !admin
         && database::gi::profiles::get_by_uid(uid, &pool)
@@ -86,7 +91,7 @@ async fn post_paimon_warps_import(
     }
 
     let allowed = admin
-        || database::gi::connections::get_by_username(&username, &pool)
+        || database::gi::connections::get_by_username(&username, pool.get_ref())
             .await?
             .iter()
             .find(|c| c.uid == uid)
@@ -263,11 +268,11 @@ async fn post_paimon_warps_import(
         }
     }
 
-    database::gi::wishes::beginner::set_all(&set_all_beginner, &pool).await?;
-    database::gi::wishes::standard::set_all(&set_all_standard, &pool).await?;
-    database::gi::wishes::character::set_all(&set_all_character, &pool).await?;
-    database::gi::wishes::weapon::set_all(&set_all_weapon, &pool).await?;
-    database::gi::wishes::chronicled::set_all(&set_all_chronicled, &pool).await?;
+    database::gi::wishes::beginner::set_all(&set_all_beginner, pool.get_ref()).await?;
+    database::gi::wishes::standard::set_all(&set_all_standard, pool.get_ref()).await?;
+    database::gi::wishes::character::set_all(&set_all_character, pool.get_ref()).await?;
+    database::gi::wishes::weapon::set_all(&set_all_weapon, pool.get_ref()).await?;
+    database::gi::wishes::chronicled::set_all(&set_all_chronicled, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -0,0 +1,99 @@
+mod uid;
+
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{scores::ScoresParams, ApiResult, Region},
+    database,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "gi/scores/achievements")),
+    paths(get_gi_scores_achievements),
+    components(schemas(
+        GiScoreAchievement
+    ))
+)]
+struct ApiDoc;
+
+#[derive(Serialize, ToSchema)]
+struct GiScoreAchievement {
+    global_rank: i64,
+    regional_rank: i64,
+    uid: i32,
+    region: Region,
+    name: String,
+    level: i32,
+    signature: String,
+    name_card: i32,
+    profile_picture: i32,
+    achievement_count: i32,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<database::gi::profiles::DbProfileScore> for GiScoreAchievement {
+    fn from(db_profile: database::gi::profiles::DbProfileScore) -> Self {
+        GiScoreAchievement {
+            global_rank: db_profile.global_rank.unwrap(),
+            regional_rank: db_profile.regional_rank.unwrap(),
+            uid: db_profile.uid,
+            region: db_profile.region.parse().unwrap(),
+            name: db_profile.name,
+            level: db_profile.level,
+            signature: db_profile.signature,
+            name_card: db_profile.name_card,
+            profile_picture: db_profile.profile_picture,
+            achievement_count: db_profile.achievement_count,
+            updated_at: db_profile.updated_at,
+        }
+    }
+}
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(uid::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_gi_scores_achievements)
+        .configure(uid::configure);
+}
+
+#[utoipa::path(
+    tag = "gi/scores/achievements",
+    get,
+    path = "/api/gi/scores/achievements",
+    params(
+        ScoresParams
+    ),
+    responses(
+        (status = 200, description = "[GiScoreAchievement]", body = Vec<GiScoreAchievement>),
+    )
+)]
+#[get("/api/gi/scores/achievements")]
+async fn get_gi_scores_achievements(
+    scores_params: web::Query<ScoresParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let db_profiles = database::gi::profiles::get_scores(
+        scores_params.region.map(|r| r.to_string()).as_deref(),
+        scores_params.query.as_deref(),
+        scores_params.limit,
+        scores_params.offset,
+        &pool,
+    )
+    .await?;
+
+    let scores: Vec<_> = db_profiles
+        .into_iter()
+        .map(GiScoreAchievement::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(scores))
+}
@@ -0,0 +1,66 @@
+use actix_web::{get, put, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::{gi::scores::achievements::GiScoreAchievement, ApiResult},
+    database, enka,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "gi/scores/achievements/{uid}")),
+    paths(get_gi_score_achievement, put_gi_score_achievement)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_gi_score_achievement)
+        .service(put_gi_score_achievement);
+}
+
+#[utoipa::path(
+    tag = "gi/scores/achievements/{uid}",
+    get,
+    path = "/api/gi/scores/achievements/{uid}",
+    responses(
+        (status = 200, description = "GiScoreAchievement", body = GiScoreAchievement),
+    )
+)]
+#[get("/api/gi/scores/achievements/{uid}")]
+async fn get_gi_score_achievement(
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let score: GiScoreAchievement = database::gi::profiles::get_score_by_uid(*uid, &pool)
+        .await?
+        .into();
+
+    Ok(HttpResponse::Ok().json(score))
+}
+
+#[utoipa::path(
+    tag = "gi/scores/achievements/{uid}",
+    put,
+    path = "/api/gi/scores/achievements/{uid}",
+    responses(
+        (status = 200, description = "GiScoreAchievement", body = GiScoreAchievement),
+    )
+)]
+#[put("/api/gi/scores/achievements/{uid}")]
+async fn put_gi_score_achievement(
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    enka::gi::update_and_get(*uid, &pool).await?;
+
+    let score: GiScoreAchievement = database::gi::profiles::get_score_by_uid(*uid, &pool)
+        .await?
+        .into();
+
+    Ok(HttpResponse::Ok().json(score))
+}
@@ -1,9 +1,11 @@
 use actix_web::{get, web, HttpResponse, Responder};
+use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::api::{
-    gi::wishes_import::{WishesImportInfo, WishesImportInfos},
-    ApiResult,
+use crate::{
+    api::ApiResult,
+    gacha::{self, gi::Wishes, ImportInfo},
+    GiGachaType,
 };
 
 #[derive(OpenApi)]
@@ -26,19 +28,17 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     get,
     path = "/api/gi/wishes-import/{uid}",
     responses(
-        (status = 200, description = "WishesImportInfo", body = WishesImportInfo)
+        (status = 200, description = "WishesImportInfo", body = ImportInfo<GiGachaType>)
     )
 )]
 #[get("/api/gi/wishes-import/{uid}")]
 async fn get_gi_wishes_import(
     uid: web::Path<i32>,
-    wishes_import_infos: web::Data<WishesImportInfos>,
+    pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
-    let Some(info) = wishes_import_infos.lock().await.get(&*uid).cloned() else {
+    let Some(info) = gacha::get_import_info::<Wishes>(*uid, &pool).await? else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let info = info.lock().await.clone();
-
     Ok(HttpResponse::Ok().json(info))
 }
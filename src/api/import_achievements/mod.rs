@@ -10,6 +10,7 @@ use utoipa::OpenApi;
 use crate::{
     api::{ApiResult, File},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -72,7 +73,7 @@ async fn import_achievements(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    if !database::admins::exists(&username, &pool).await? {
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
@@ -83,10 +84,14 @@ async fn import_achievements(
         .collect::<Vec<_>>()
         .join("\n");
 
+    let mut ids = Vec::new();
+
     let mut reader = csv::Reader::from_reader(lines.as_bytes());
     for achievement in reader.deserialize() {
         let achievement: Achievement = achievement?;
 
+        ids.push(achievement.key);
+
         database::achievements::update_version_by_id(
             achievement.key,
             achievement.version.as_deref(),
@@ -151,5 +156,15 @@ async fn import_achievements(
         .await?;
     }
 
+    database::audit_log::add(
+        &username,
+        "achievements.import",
+        "import-achievements",
+        None,
+        Some(serde_json::json!({ "ids": ids })),
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
@@ -10,6 +10,7 @@ use utoipa::OpenApi;
 use crate::{
     api::{ApiResult, File},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -64,7 +65,7 @@ async fn import_gi_achievements(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    if !database::admins::exists(&username, &pool).await? {
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
@@ -74,10 +75,14 @@ async fn import_gi_achievements(
         .collect::<Vec<_>>()
         .join("\n");
 
+    let mut ids = Vec::new();
+
     let mut reader = csv::Reader::from_reader(lines.as_bytes());
     for achievement in reader.deserialize() {
         let achievement: Achievement = achievement?;
 
+        ids.push(achievement.key);
+
         database::gi::achievements::update_version_by_id(
             achievement.key,
             achievement.version.as_deref(),
@@ -114,5 +119,15 @@ async fn import_gi_achievements(
         .await?;
     }
 
+    database::audit_log::add(
+        &username,
+        "achievements.import",
+        "import-gi-achievements",
+        None,
+        Some(serde_json::json!({ "ids": ids })),
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
@@ -2,7 +2,9 @@ mod achievement_series;
 mod achievements;
 mod admin;
 mod banners;
+mod changelog;
 mod characters;
+mod export;
 mod gi;
 mod import_achievements;
 mod import_gi_achievements;
@@ -14,10 +16,12 @@ mod pages;
 mod pom_warps_import;
 mod scores;
 mod select_all;
+mod simulate;
 mod sitemap;
 mod srgf_warps_import;
 mod srs_warps_import;
-mod users;
+mod uigf_import;
+pub mod users;
 mod warps;
 mod warps_import;
 mod zzz;
@@ -39,7 +43,7 @@ use crate::{Difficulty, GachaType, GiGachaType, Language, ZzzGachaType};
 type ApiResult<T> = Result<T, Box<dyn std::error::Error>>;
 
 #[derive(OpenApi)]
-#[openapi(tags((name = "pinned")), components(schemas(Language, GachaType, ZzzGachaType, GiGachaType, File, Difficulty)), modifiers(&PrivateAddon))]
+#[openapi(tags((name = "pinned")), components(schemas(Language, Game, GachaType, ZzzGachaType, GiGachaType, File, Difficulty)), modifiers(&PrivateAddon))]
 struct ApiDoc;
 
 struct PrivateAddon;
@@ -70,6 +74,15 @@ pub enum Region {
     Cn,
 }
 
+#[derive(Display, EnumString, Serialize, Deserialize, ToSchema, Clone, Copy)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Game {
+    Hsr,
+    Gi,
+    Zzz,
+}
+
 #[derive(MultipartForm, ToSchema)]
 struct File {
     #[schema(value_type = String, format = Binary)]
@@ -87,7 +100,9 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     openapi.merge(achievement_series::openapi());
     openapi.merge(achievements::openapi());
     openapi.merge(banners::openapi());
+    openapi.merge(changelog::openapi());
     openapi.merge(characters::openapi());
+    openapi.merge(export::openapi());
     openapi.merge(gi::openapi());
     openapi.merge(import_achievements::openapi());
     openapi.merge(import_gi_achievements::openapi());
@@ -99,9 +114,11 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     openapi.merge(pom_warps_import::openapi());
     openapi.merge(scores::openapi());
     openapi.merge(select_all::openapi());
+    openapi.merge(simulate::openapi());
     openapi.merge(sitemap::openapi());
     openapi.merge(srgf_warps_import::openapi());
     openapi.merge(srs_warps_import::openapi());
+    openapi.merge(uigf_import::openapi());
     openapi.merge(users::openapi());
     openapi.merge(warps::openapi());
     openapi.merge(warps_import::openapi());
@@ -114,7 +131,9 @@ pub fn configure(cfg: &mut web::ServiceConfig, pool: PgPool) {
         .configure(achievement_series::configure)
         .configure(achievements::configure)
         .configure(banners::configure)
+        .configure(changelog::configure)
         .configure(characters::configure)
+        .configure(export::configure)
         .configure(gi::configure)
         .configure(import_achievements::configure)
         .configure(import_gi_achievements::configure)
@@ -126,9 +145,11 @@ pub fn configure(cfg: &mut web::ServiceConfig, pool: PgPool) {
         .configure(pom_warps_import::configure)
         .configure(scores::configure)
         .configure(select_all::configure)
+        .configure(simulate::configure)
         .configure(|sc| sitemap::configure(sc, pool.clone()))
         .configure(srgf_warps_import::configure)
         .configure(srs_warps_import::configure)
+        .configure(uigf_import::configure)
         .configure(users::configure)
         .configure(warps::configure)
         .configure(warps_import::configure)
@@ -3,15 +3,11 @@ use std::{
     fs::File,
     io::BufReader,
     sync::Mutex,
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use actix_session::Session;
-use actix_web::{
-    get,
-    rt::{self, Runtime},
-    web, HttpResponse, Responder,
-};
+use actix_web::{get, web, HttpResponse, Responder};
 use async_rwlock::RwLock;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
@@ -20,7 +16,9 @@ use utoipa::OpenApi;
 
 use crate::{
     api::{private, ApiResult, Language, LanguageParams},
-    database, Difficulty,
+    database,
+    scheduler::{self, Schedule},
+    Difficulty,
 };
 
 lazy_static::lazy_static! {
@@ -118,9 +116,11 @@ impl From<database::gi::achievements::DbAchievement> for Achievement {
             video: db_achievement.video.clone(),
             gacha: db_achievement.gacha,
             impossible: db_achievement.impossible,
-            percent: (!db_achievement.impossible)
-                .then_some(db_achievement.percent.unwrap_or_default())
-                .unwrap_or_default(),
+            percent: if db_achievement.impossible {
+                0.0
+            } else {
+                db_achievement.percent.unwrap_or_default()
+            },
         }
     }
 }
@@ -144,44 +144,11 @@ pub fn cache(pool: PgPool) -> web::Data<GiAchievementTrackerCache> {
     {
         let achievement_tracker_cache = achievement_tracker_cache.clone();
 
-        std::thread::spawn(move || {
-            let rt = Runtime::new().unwrap();
-
-            let handle = rt.spawn(async move {
-                let mut success = true;
-
-                let mut interval = rt::time::interval(Duration::from_secs(60));
-
-                loop {
-                    if success {
-                        interval.tick().await;
-                    }
-
-                    let start = Instant::now();
-
-                    if let Err(e) =
-                        update_achievement_tracker(achievement_tracker_cache.clone(), pool.clone())
-                            .await
-                    {
-                        success = false;
-
-                        error!(
-                            "Gi Achievement Tracker update failed with {e} in {}s",
-                            start.elapsed().as_secs_f64()
-                        );
-                    } else {
-                        success = true;
-
-                        info!(
-                            "Gi Achievement Tracker update succeeded in {}s",
-                            start.elapsed().as_secs_f64()
-                        );
-                    }
-                }
-            });
-
-            rt.block_on(handle).unwrap();
-        });
+        scheduler::register(
+            "gi_achievement_tracker",
+            Schedule::Every(Duration::from_secs(60)),
+            move || update_achievement_tracker(achievement_tracker_cache.clone(), pool.clone()),
+        );
     }
 
     achievement_tracker_cache
@@ -0,0 +1,158 @@
+use actix_session::Session;
+use actix_web::{get, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::{private, ApiResult, Region},
+    database, enka,
+    gacha::{gi::Wishes, Gacha},
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(get_gi_profile, update_gi_profile))]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_gi_profile).service(update_gi_profile);
+}
+
+#[derive(Serialize)]
+struct Profile {
+    rank_global: i64,
+    rank_regional: i64,
+    top_global: f64,
+    top_regional: f64,
+    region: Region,
+    updated_at: DateTime<Utc>,
+    enka: Value,
+    collection: Collection,
+}
+
+#[derive(Serialize)]
+struct Collection {
+    total: i64,
+    beginner: i64,
+    standard: i64,
+    character: i64,
+    weapon: i64,
+    chronicled: i64,
+}
+
+#[utoipa::path(
+    tag = "gi/pages",
+    get,
+    path = "/api/pages/gi/profiles/{uid}",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Profile"),
+        (status = 403, description = "Private"),
+    )
+)]
+#[get("/api/pages/gi/profiles/{uid}", guard = "private")]
+async fn get_gi_profile(
+    session: Session,
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let uid = *uid;
+
+    let username = session.get::<String>("username").ok().flatten();
+
+    if Wishes::forbidden(uid, username, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let profile = get_profile_json(false, uid, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+#[utoipa::path(
+    tag = "gi/pages",
+    put,
+    path = "/api/pages/gi/profiles/{uid}",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Profile"),
+        (status = 403, description = "Private"),
+    )
+)]
+#[put("/api/pages/gi/profiles/{uid}", guard = "private")]
+async fn update_gi_profile(
+    session: Session,
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let uid = *uid;
+
+    let username = session.get::<String>("username").ok().flatten();
+
+    if Wishes::forbidden(uid, username, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let profile = get_profile_json(true, uid, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+async fn get_profile_json(update: bool, uid: i32, pool: &PgPool) -> ApiResult<Profile> {
+    let enka = if update {
+        enka::gi::update_and_get(uid, pool).await?
+    } else {
+        enka::gi::get(uid, pool).await?
+    };
+
+    let db_profile = database::gi::profiles::get_score_by_uid(uid, pool).await?;
+
+    let rank_global = db_profile.global_rank.unwrap_or_default();
+    let rank_regional = db_profile.regional_rank.unwrap_or_default();
+
+    let count_global = database::gi::profiles::count(None, None, pool).await?;
+    let count_regional =
+        database::gi::profiles::count(Some(&db_profile.region), None, pool).await?;
+
+    let top_global = rank_global as f64 / count_global as f64;
+    let top_regional = rank_regional as f64 / count_regional as f64;
+
+    let region = db_profile.region.parse()?;
+
+    let updated_at = db_profile.updated_at;
+
+    let beginner = database::gi::wishes::beginner::get_count_by_uid(uid, pool).await?;
+    let standard = database::gi::wishes::standard::get_count_by_uid(uid, pool).await?;
+    let character = database::gi::wishes::character::get_count_by_uid(uid, pool).await?;
+    let weapon = database::gi::wishes::weapon::get_count_by_uid(uid, pool).await?;
+    let chronicled = database::gi::wishes::chronicled::get_count_by_uid(uid, pool).await?;
+    let total = beginner + standard + character + weapon + chronicled;
+
+    let collection = Collection {
+        total,
+        beginner,
+        standard,
+        character,
+        weapon,
+        chronicled,
+    };
+
+    let profile = Profile {
+        rank_global,
+        rank_regional,
+        top_global,
+        top_regional,
+        region,
+        updated_at,
+        enka,
+        collection,
+    };
+
+    Ok(profile)
+}
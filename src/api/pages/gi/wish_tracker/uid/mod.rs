@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use actix_session::Session;
 use actix_web::{get, web, HttpResponse, Responder};
 use chrono::{DateTime, Utc};
@@ -10,6 +8,9 @@ use utoipa::OpenApi;
 use crate::{
     api::{private, ApiResult, LanguageParams},
     database,
+    gacha::{self, Gacha, Outcome, Pull},
+    permissions::{self, Permission},
+    GiGachaType,
 };
 
 #[derive(OpenApi)]
@@ -35,7 +36,7 @@ struct Wish {
     pull_4: usize,
     pull_5: usize,
     timestamp: DateTime<Utc>,
-    win: Option<WinType>,
+    win: Option<Outcome>,
 }
 
 #[derive(Serialize)]
@@ -45,14 +46,6 @@ enum WishType {
     Weapon,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "snake_case")]
-enum WinType {
-    Win,
-    Loss,
-    Guarantee,
-}
-
 impl From<database::gi::wishes::DbWish> for Wish {
     fn from(wish: database::gi::wishes::DbWish) -> Self {
         let r#type = if wish.character.is_some() {
@@ -96,6 +89,8 @@ struct Wishes {
     max_pull_4: usize,
     max_pull_5: usize,
     count: usize,
+    radiance: Option<i32>,
+    fate_points: Option<i32>,
     stats: Option<Stats>,
 }
 
@@ -146,7 +141,7 @@ async fn get_wish_tracker(
 
     if forbidden {
         if let Ok(Some(username)) = session.get::<String>("username") {
-            if database::admins::exists(&username, &pool).await? {
+            if permissions::has(&username, Permission::AccessAllUids, &pool).await? {
                 forbidden = false;
             } else if let Ok(connection) =
                 database::gi::connections::get_by_uid_and_username(uid, &username, &pool).await
@@ -164,23 +159,8 @@ async fn get_wish_tracker(
 
     let name = database::gi::profiles::get_by_uid(uid, &pool).await?.name;
 
-    let mut banners: HashMap<_, Vec<_>> = HashMap::new();
-
-    for banner in database::gi::banners::get_all(&pool).await? {
-        if let Some(character) = banner.character {
-            banners
-                .entry(character)
-                .or_default()
-                .push(banner.start..banner.end);
-        }
-
-        if let Some(weapon) = banner.weapon {
-            banners
-                .entry(weapon)
-                .or_default()
-                .push(banner.start..banner.end);
-        }
-    }
+    let featured_character = gacha::gi::Wishes::get_featured(GiGachaType::Character, &pool).await?;
+    let featured_weapon = gacha::gi::Wishes::get_featured(GiGachaType::Weapon, &pool).await?;
 
     // Beginner
     let mut beginner = Wishes::default();
@@ -273,9 +253,16 @@ async fn get_wish_tracker(
     let mut character_pull = 0;
     let mut character_pull_4 = 0;
     let mut character_pull_5 = 0;
-    let mut guarantee = false;
 
-    for wish in database::gi::wishes::character::get_by_uid(uid, language, &pool).await? {
+    let wishes = database::gi::wishes::character::get_by_uid(uid, language, &pool).await?;
+
+    let (outcomes, state) = gacha::gi::state(
+        GiGachaType::Character,
+        &pulls(&wishes),
+        featured_character.as_ref(),
+    );
+
+    for (wish, outcome) in wishes.into_iter().zip(outcomes) {
         let mut wish: Wish = wish.into();
 
         character_pull += 1;
@@ -291,21 +278,7 @@ async fn get_wish_tracker(
             5 => {
                 character_pull_5 = 0;
 
-                wish.win = if guarantee {
-                    guarantee = false;
-
-                    Some(WinType::Guarantee)
-                } else if banners
-                    .get(&wish.item_id)
-                    .map(|v| v.iter().any(|r| r.contains(&wish.timestamp)))
-                    .unwrap_or_default()
-                {
-                    Some(WinType::Win)
-                } else {
-                    guarantee = true;
-
-                    Some(WinType::Loss)
-                };
+                wish.win = outcome;
             }
             _ => {}
         }
@@ -326,6 +299,7 @@ async fn get_wish_tracker(
     };
 
     character.count = character.wishes.len();
+    character.radiance = Some(state.radiance);
     // Character
 
     // Weapon
@@ -333,9 +307,16 @@ async fn get_wish_tracker(
     let mut weapon_pull = 0;
     let mut weapon_pull_4 = 0;
     let mut weapon_pull_5 = 0;
-    let mut guarantee = false;
 
-    for wish in database::gi::wishes::weapon::get_by_uid(uid, language, &pool).await? {
+    let wishes = database::gi::wishes::weapon::get_by_uid(uid, language, &pool).await?;
+
+    let (outcomes, state) = gacha::gi::state(
+        GiGachaType::Weapon,
+        &pulls(&wishes),
+        featured_weapon.as_ref(),
+    );
+
+    for (wish, outcome) in wishes.into_iter().zip(outcomes) {
         let mut wish: Wish = wish.into();
 
         weapon_pull += 1;
@@ -351,21 +332,7 @@ async fn get_wish_tracker(
             5 => {
                 weapon_pull_5 = 0;
 
-                wish.win = if guarantee {
-                    guarantee = false;
-
-                    Some(WinType::Guarantee)
-                } else if banners
-                    .get(&wish.item_id)
-                    .map(|v| v.iter().any(|r| r.contains(&wish.timestamp)))
-                    .unwrap_or_default()
-                {
-                    Some(WinType::Win)
-                } else {
-                    guarantee = true;
-
-                    Some(WinType::Loss)
-                };
+                wish.win = outcome;
             }
             _ => {}
         }
@@ -386,6 +353,7 @@ async fn get_wish_tracker(
     };
 
     weapon.count = weapon.wishes.len();
+    weapon.fate_points = Some(state.fate_points);
     // Weapon
 
     // Chronicled
@@ -394,7 +362,11 @@ async fn get_wish_tracker(
     let mut chronicled_pull_4 = 0;
     let mut chronicled_pull_5 = 0;
 
-    for wish in database::gi::wishes::chronicled::get_by_uid(uid, language, &pool).await? {
+    let wishes = database::gi::wishes::chronicled::get_by_uid(uid, language, &pool).await?;
+
+    let (outcomes, state) = gacha::gi::state(GiGachaType::Chronicled, &pulls(&wishes), None);
+
+    for (wish, outcome) in wishes.into_iter().zip(outcomes) {
         let mut wish: Wish = wish.into();
 
         chronicled_pull += 1;
@@ -409,6 +381,8 @@ async fn get_wish_tracker(
             4 => chronicled_pull_4 = 0,
             5 => {
                 chronicled_pull_5 = 0;
+
+                wish.win = outcome;
             }
             _ => {}
         }
@@ -429,6 +403,7 @@ async fn get_wish_tracker(
     };
 
     chronicled.count = chronicled.wishes.len();
+    chronicled.fate_points = Some(state.fate_points);
     // Chronicled
 
     if let Some(stats) = database::gi::wishes_stats::standard::get_by_uid(uid, &pool).await? {
@@ -522,3 +497,14 @@ async fn get_wish_tracker(
 
     Ok(HttpResponse::Ok().json(wish_tracker))
 }
+
+fn pulls(wishes: &[database::gi::wishes::DbWish]) -> Vec<Pull> {
+    wishes
+        .iter()
+        .map(|wish| Pull {
+            item: wish.character.or(wish.weapon),
+            rarity: wish.rarity.unwrap(),
+            timestamp: wish.timestamp,
+        })
+        .collect()
+}
@@ -10,6 +10,7 @@ use utoipa::OpenApi;
 use crate::{
     api::{private, ApiResult, LanguageParams},
     database,
+    permissions::{self, Permission},
 };
 
 #[derive(OpenApi)]
@@ -102,6 +103,10 @@ struct Warps {
 struct Stats {
     luck_4: f64,
     luck_5: f64,
+    likelihood_4: f64,
+    likelihood_5: f64,
+    expected_5: f64,
+    actual_5: i32,
     win_stats: Option<WinStats>,
     global_stats: Option<GlobalStats>,
 }
@@ -111,6 +116,7 @@ struct WinStats {
     win_rate: f64,
     win_streak: i32,
     loss_streak: i32,
+    likelihood_win: f64,
 }
 
 #[derive(Serialize)]
@@ -145,7 +151,7 @@ async fn get_warp_tracker(
 
     if forbidden {
         if let Ok(Some(username)) = session.get::<String>("username") {
-            if database::admins::exists(&username, &pool).await? {
+            if permissions::has(&username, Permission::AccessAllUids, &pool).await? {
                 forbidden = false;
             } else if let Ok(connection) =
                 database::connections::get_by_uid_and_username(uid, &username, &pool).await
@@ -383,6 +389,10 @@ async fn get_warp_tracker(
         standard.stats = Some(Stats {
             luck_4: stats.luck_4,
             luck_5: stats.luck_5,
+            likelihood_4: stats.likelihood_4,
+            likelihood_5: stats.likelihood_5,
+            expected_5: stats.expected_5,
+            actual_5: stats.actual_5,
             win_stats: None,
             global_stats,
         });
@@ -393,6 +403,7 @@ async fn get_warp_tracker(
             win_rate: stats.win_rate,
             win_streak: stats.win_streak,
             loss_streak: stats.loss_streak,
+            likelihood_win: stats.likelihood_win,
         });
 
         let global_stats = database::warps_stats_global::special::get_by_uid(uid, &pool)
@@ -406,6 +417,10 @@ async fn get_warp_tracker(
         special.stats = Some(Stats {
             luck_4: stats.luck_4,
             luck_5: stats.luck_5,
+            likelihood_4: stats.likelihood_4,
+            likelihood_5: stats.likelihood_5,
+            expected_5: stats.expected_5,
+            actual_5: stats.actual_5,
             win_stats,
             global_stats,
         });
@@ -416,6 +431,7 @@ async fn get_warp_tracker(
             win_rate: stats.win_rate,
             win_streak: stats.win_streak,
             loss_streak: stats.loss_streak,
+            likelihood_win: stats.likelihood_win,
         });
 
         let global_stats = database::warps_stats_global::lc::get_by_uid(uid, &pool)
@@ -429,6 +445,10 @@ async fn get_warp_tracker(
         lc.stats = Some(Stats {
             luck_4: stats.luck_4,
             luck_5: stats.luck_5,
+            likelihood_4: stats.likelihood_4,
+            likelihood_5: stats.likelihood_5,
+            expected_5: stats.expected_5,
+            actual_5: stats.actual_5,
             win_stats,
             global_stats,
         });
@@ -0,0 +1,128 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{IntoParams, OpenApi};
+
+use crate::{
+    api::{private, ApiResult, Region},
+    database,
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(get_zzz_leaderboard))]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_leaderboard);
+}
+
+#[derive(Serialize)]
+struct Leaderboard {
+    count: i64,
+    count_na: i64,
+    count_eu: i64,
+    count_asia: i64,
+    count_cn: i64,
+    count_query: i64,
+    scores: Vec<Score>,
+}
+
+#[derive(Serialize)]
+struct Score {
+    global_rank: i64,
+    regional_rank: i64,
+    uid: i32,
+    region: Region,
+    name: String,
+    level: i32,
+    signature: String,
+    avatar_id: i32,
+    achievement_count: i32,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize, IntoParams)]
+struct LeaderboardParams {
+    region: Option<Region>,
+    query: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl From<database::zzz::profiles::DbProfile> for Score {
+    fn from(db_profile: database::zzz::profiles::DbProfile) -> Self {
+        Score {
+            global_rank: db_profile.global_rank.unwrap(),
+            regional_rank: db_profile.regional_rank.unwrap(),
+            uid: db_profile.uid,
+            region: db_profile.region.parse().unwrap(),
+            name: db_profile.name,
+            level: db_profile.level,
+            signature: db_profile.signature,
+            avatar_id: db_profile.avatar_id,
+            achievement_count: db_profile.achievement_count,
+            updated_at: db_profile.updated_at,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "zzz/pages",
+    get,
+    path = "/api/pages/zzz/leaderboard",
+    params(LeaderboardParams),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Leaderboard"),
+    )
+)]
+#[get("/api/pages/zzz/leaderboard", guard = "private")]
+async fn get_zzz_leaderboard(
+    leaderboard_params: web::Query<LeaderboardParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let count_na =
+        database::zzz::profiles::count(Some(&Region::Na.to_string()), None, &pool).await?;
+    let count_eu =
+        database::zzz::profiles::count(Some(&Region::Eu.to_string()), None, &pool).await?;
+    let count_asia =
+        database::zzz::profiles::count(Some(&Region::Asia.to_string()), None, &pool).await?;
+    let count_cn =
+        database::zzz::profiles::count(Some(&Region::Cn.to_string()), None, &pool).await?;
+    let count_query = database::zzz::profiles::count(
+        leaderboard_params.region.map(|r| r.to_string()).as_deref(),
+        leaderboard_params.query.as_deref(),
+        &pool,
+    )
+    .await?;
+
+    let count = count_na + count_eu + count_asia + count_cn;
+
+    let db_profiles = database::zzz::profiles::get(
+        leaderboard_params.region.map(|r| r.to_string()).as_deref(),
+        leaderboard_params.query.as_deref(),
+        leaderboard_params.limit,
+        leaderboard_params.offset,
+        &pool,
+    )
+    .await?;
+
+    let scores = db_profiles.into_iter().map(Score::from).collect();
+
+    let leaderboard = Leaderboard {
+        count,
+        count_na,
+        count_eu,
+        count_asia,
+        count_cn,
+        count_query,
+        scores,
+    };
+
+    Ok(HttpResponse::Ok().json(leaderboard))
+}
@@ -1,4 +1,6 @@
 mod achievement_tracker;
+mod leaderboard;
+mod profiles;
 mod signal_tracker;
 
 use actix_web::web;
@@ -12,11 +14,15 @@ struct ApiDoc;
 pub fn openapi() -> utoipa::openapi::OpenApi {
     let mut openapi = ApiDoc::openapi();
     openapi.merge(achievement_tracker::openapi());
+    openapi.merge(leaderboard::openapi());
+    openapi.merge(profiles::openapi());
     openapi.merge(signal_tracker::openapi());
     openapi
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig, pool: PgPool) {
     cfg.configure(|sc| achievement_tracker::configure(sc, pool))
+        .configure(leaderboard::configure)
+        .configure(profiles::configure)
         .configure(signal_tracker::configure);
 }
@@ -0,0 +1,155 @@
+use actix_session::Session;
+use actix_web::{get, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::{private, ApiResult, Region},
+    database, enka,
+    gacha::{zzz::Signals, Gacha},
+};
+
+#[derive(OpenApi)]
+#[openapi(paths(get_zzz_profile, update_zzz_profile))]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_profile).service(update_zzz_profile);
+}
+
+#[derive(Serialize)]
+struct Profile {
+    rank_global: i64,
+    rank_regional: i64,
+    top_global: f64,
+    top_regional: f64,
+    region: Region,
+    updated_at: DateTime<Utc>,
+    enka: Value,
+    collection: Collection,
+}
+
+#[derive(Serialize)]
+struct Collection {
+    total: i64,
+    standard: i64,
+    special: i64,
+    w_engine: i64,
+    bangboo: i64,
+}
+
+#[utoipa::path(
+    tag = "zzz/pages",
+    get,
+    path = "/api/pages/zzz/profiles/{uid}",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Profile"),
+        (status = 403, description = "Private"),
+    )
+)]
+#[get("/api/pages/zzz/profiles/{uid}", guard = "private")]
+async fn get_zzz_profile(
+    session: Session,
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let uid = *uid;
+
+    let username = session.get::<String>("username").ok().flatten();
+
+    if Signals::forbidden(uid, username, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let profile = get_profile_json(false, uid, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+#[utoipa::path(
+    tag = "zzz/pages",
+    put,
+    path = "/api/pages/zzz/profiles/{uid}",
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Profile"),
+        (status = 403, description = "Private"),
+    )
+)]
+#[put("/api/pages/zzz/profiles/{uid}", guard = "private")]
+async fn update_zzz_profile(
+    session: Session,
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let uid = *uid;
+
+    let username = session.get::<String>("username").ok().flatten();
+
+    if Signals::forbidden(uid, username, &pool).await? {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let profile = get_profile_json(true, uid, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+async fn get_profile_json(update: bool, uid: i32, pool: &PgPool) -> ApiResult<Profile> {
+    let enka = if update {
+        enka::zzz::update_and_get(uid, pool).await?
+    } else {
+        enka::zzz::get(uid, pool).await?
+    };
+
+    let db_profile = database::zzz::profiles::get_by_uid(uid, pool).await?;
+
+    let rank_global = db_profile.global_rank.unwrap_or_default();
+    let rank_regional = db_profile.regional_rank.unwrap_or_default();
+
+    let count_global = database::zzz::profiles::count(None, None, pool).await?;
+    let count_regional =
+        database::zzz::profiles::count(Some(&db_profile.region), None, pool).await?;
+
+    let top_global = rank_global as f64 / count_global as f64;
+    let top_regional = rank_regional as f64 / count_regional as f64;
+
+    let region = db_profile.region.parse()?;
+
+    let updated_at = db_profile.updated_at;
+
+    let standard = database::zzz::signals::standard::get_count_by_uid(uid, pool).await?;
+    let special = database::zzz::signals::special::get_count_by_uid(uid, pool).await?;
+    let w_engine = database::zzz::signals::w_engine::get_count_by_uid(uid, pool).await?;
+    let bangboo = database::zzz::signals::bangboo::get_count_by_uid(uid, pool).await?;
+    let total = standard + special + w_engine + bangboo;
+
+    let collection = Collection {
+        total,
+        standard,
+        special,
+        w_engine,
+        bangboo,
+    };
+
+    let profile = Profile {
+        rank_global,
+        rank_regional,
+        top_global,
+        top_regional,
+        region,
+        updated_at,
+        enka,
+        collection,
+    };
+
+    Ok(profile)
+}
@@ -8,6 +8,9 @@ use utoipa::OpenApi;
 use crate::{
     api::{private, ApiResult, LanguageParams},
     database,
+    gacha::{self, Featured, Gacha, Pull},
+    permissions::{self, Permission},
+    ZzzGachaType,
 };
 
 #[derive(OpenApi)]
@@ -149,7 +152,7 @@ async fn get_signal_tracker(
 
     if forbidden {
         if let Ok(Some(username)) = session.get::<String>("username") {
-            if database::admins::exists(&username, &pool).await? {
+            if permissions::has(&username, Permission::AccessAllUids, &pool).await? {
                 forbidden = false;
             } else if let Ok(connection) =
                 database::zzz::connections::get_by_uid_and_username(uid, &username, &pool).await
@@ -165,6 +168,9 @@ async fn get_signal_tracker(
 
     let language = language_params.lang;
 
+    let featured_special = gacha::zzz::Signals::get_featured(ZzzGachaType::Special, &pool).await?;
+    let featured_w_engine = gacha::zzz::Signals::get_featured(ZzzGachaType::WEngine, &pool).await?;
+
     // Standard
     let mut standard = Signals::default();
     let mut standard_pull = 0;
@@ -237,7 +243,7 @@ async fn get_signal_tracker(
                     guarantee = false;
 
                     Some(WinType::Guarantee)
-                } else if [1021, 1041, 1101, 1141, 1181, 1211].contains(&signal.item_id) {
+                } else if !featured(featured_special.as_ref(), &signal) {
                     guarantee = true;
 
                     Some(WinType::Loss)
@@ -294,7 +300,7 @@ async fn get_signal_tracker(
                     guarantee = false;
 
                     Some(WinType::Guarantee)
-                } else if [14102, 14104, 14110, 14114, 14118, 14121].contains(&signal.item_id) {
+                } else if !featured(featured_w_engine.as_ref(), &signal) {
                     guarantee = true;
 
                     Some(WinType::Loss)
@@ -456,3 +462,13 @@ async fn get_signal_tracker(
 
     Ok(HttpResponse::Ok().json(signal_tracker))
 }
+
+fn featured(featured: Option<&Featured>, signal: &Signal) -> bool {
+    let pull = Pull {
+        item: Some(signal.item_id),
+        rarity: signal.rarity,
+        timestamp: signal.timestamp,
+    };
+
+    featured.is_none_or(|featured| featured.contains(&pull))
+}
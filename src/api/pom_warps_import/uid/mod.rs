@@ -4,7 +4,12 @@ use chrono::NaiveDateTime;
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database, mihomo, GachaType, Language};
+use crate::{
+    api::ApiResult,
+    database, mihomo,
+    permissions::{self, Permission},
+    GachaType, Language,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -76,10 +81,10 @@ async fn post_pom_warps_import(
 
     let uid = *uid;
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::AccessAllUids, &pool).await?;
 
     let allowed = admin
-        || database::connections::get_by_username(&username, &pool)
+        || database::connections::get_by_username(&username, pool.get_ref())
             .await?
             .iter()
             .find(|c| c.uid == uid)
@@ -193,10 +198,10 @@ async fn post_pom_warps_import(
         }
     }
 
-    database::warps::departure::set_all(&set_all_departure, &pool).await?;
-    database::warps::standard::set_all(&set_all_standard, &pool).await?;
-    database::warps::special::set_all(&set_all_special, &pool).await?;
-    database::warps::lc::set_all(&set_all_lc, &pool).await?;
+    database::warps::departure::set_all(&set_all_departure, pool.get_ref()).await?;
+    database::warps::standard::set_all(&set_all_standard, pool.get_ref()).await?;
+    database::warps::special::set_all(&set_all_special, pool.get_ref()).await?;
+    database::warps::lc::set_all(&set_all_lc, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -4,7 +4,11 @@ use serde::Deserialize;
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
 
-use crate::{api::ApiResult, database};
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -50,11 +54,21 @@ async fn select_all(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    if !database::admins::exists(&username, &pool).await? {
+    if !permissions::has(&username, Permission::SelectAll, &pool).await? {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
     database::achievements::select_all(&username_json.username, &pool).await?;
 
+    database::audit_log::add(
+        &username,
+        "achievements.select_all",
+        &format!("users/{}", username_json.username),
+        None,
+        None,
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
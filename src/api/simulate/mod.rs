@@ -0,0 +1,120 @@
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use rand::Rng;
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{IntoParams, OpenApi};
+
+use crate::{
+    api::{ApiResult, Game},
+    gacha::{
+        self,
+        gi::Wishes,
+        hsr::Warps,
+        simulate::{Banner, Point, Simulation, State, Target},
+        zzz::Signals,
+        Gacha,
+    },
+};
+
+// Bounds the work of one request, budget * iterations RNG steps
+const MAX_BUDGET: usize = 1500;
+const MAX_ITERATIONS: usize = 20000;
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "simulate")),
+    paths(get_simulation),
+    components(schemas(
+        Simulation,
+        State,
+        Point
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_simulation);
+}
+
+#[derive(Deserialize, IntoParams)]
+struct SimulateParams {
+    #[serde(default)]
+    character: usize,
+    #[serde(default)]
+    weapon: usize,
+    budget: usize,
+    iterations: Option<usize>,
+    seed: Option<u64>,
+}
+
+#[utoipa::path(
+    tag = "simulate",
+    get,
+    path = "/api/simulate/{game}/{uid}",
+    params(
+        SimulateParams
+    ),
+    responses(
+        (status = 200, description = "Simulation", body = Simulation),
+        (status = 400, description = "Invalid target"),
+        (status = 403, description = "Private"),
+    )
+)]
+#[get("/api/simulate/{game}/{uid}")]
+async fn get_simulation(
+    session: Session,
+    path: web::Path<(Game, i32)>,
+    params: web::Query<SimulateParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let (game, uid) = *path;
+
+    if params.budget > MAX_BUDGET || params.character + params.weapon == 0 {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let target = Target {
+        character: params.character,
+        weapon: params.weapon,
+        budget: params.budget,
+        iterations: params.iterations.unwrap_or(10000).min(MAX_ITERATIONS),
+        seed: params.seed.unwrap_or_else(|| rand::rng().random()),
+    };
+
+    let username = session.get::<String>("username").ok().flatten();
+
+    let banners = match game {
+        Game::Hsr => load::<Warps>(uid, username, &pool).await?,
+        Game::Gi => load::<Wishes>(uid, username, &pool).await?,
+        Game::Zzz => load::<Signals>(uid, username, &pool).await?,
+    };
+
+    let Some((character, weapon)) = banners else {
+        return Ok(HttpResponse::Forbidden().finish());
+    };
+
+    let simulation =
+        web::block(move || gacha::simulate::simulate(&target, &character, &weapon)).await?;
+
+    Ok(HttpResponse::Ok().json(simulation))
+}
+
+async fn load<G: Gacha>(
+    uid: i32,
+    username: Option<String>,
+    pool: &PgPool,
+) -> anyhow::Result<Option<(Banner, Banner)>> {
+    if G::forbidden(uid, username, pool).await? {
+        return Ok(None);
+    }
+
+    Ok(Some((
+        gacha::simulate::load::<G>(uid, G::CHARACTER, pool).await?,
+        gacha::simulate::load::<G>(uid, G::WEAPON, pool).await?,
+    )))
+}
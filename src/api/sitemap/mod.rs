@@ -1,18 +1,16 @@
-use std::{
-    sync::Mutex,
-    time::{Duration, Instant},
-};
+use std::{sync::Mutex, time::Duration};
 
-use actix_web::{
-    get,
-    rt::{self, Runtime},
-    web, Responder,
-};
+use actix_web::{get, web, Responder};
 use sqlx::PgPool;
 use strum::IntoEnumIterator;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database, Language};
+use crate::{
+    api::ApiResult,
+    database,
+    scheduler::{self, Schedule},
+    Language,
+};
 
 lazy_static::lazy_static! {
     static ref CACHE: Mutex<Option<()>> = Mutex::new(None);
@@ -100,33 +98,11 @@ struct Sitemap {
 }
 
 pub fn cache(pool: PgPool) {
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            let mut interval = rt::time::interval(Duration::from_secs(60 * 60 * 24));
-
-            loop {
-                interval.tick().await;
-
-                let start = Instant::now();
-
-                if let Err(e) = update(pool.clone()).await {
-                    error!(
-                        "Sitemap update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "Sitemap update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
-
-        rt.block_on(handle).unwrap();
-    });
+    scheduler::register(
+        "sitemap",
+        Schedule::Every(Duration::from_secs(60 * 60 * 24)),
+        move || update(pool.clone()),
+    );
 }
 
 fn write_urls(count: usize, urls: Vec<Url>) -> anyhow::Result<()> {
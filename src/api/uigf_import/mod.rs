@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use actix_session::Session;
+use actix_web::{post, web, HttpResponse, Responder};
+use anyhow::Result;
+use chrono::{FixedOffset, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use strum::IntoEnumIterator;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::ApiResult,
+    gacha::{self, gi::Wishes, hsr::Warps, zzz::Signals, Gacha},
+    permissions::{self, Permission},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "uigf-import")),
+    paths(post_uigf_import),
+    components(schemas(UigfImportParams, UigfImport, SkippedPulls)),
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(post_uigf_import);
+}
+
+#[derive(Deserialize, ToSchema)]
+struct UigfImportParams {
+    data: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct UigfImport {
+    /// Pulls of gacha types that aren't supported, which were left out
+    skipped: Vec<SkippedPulls>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct SkippedPulls {
+    game: String,
+    uid: i32,
+    gacha_type: String,
+    count: usize,
+}
+
+#[derive(Deserialize)]
+struct Uigf {
+    #[serde(default)]
+    hk4e: Vec<Section>,
+    #[serde(default)]
+    hkrpg: Vec<Section>,
+    #[serde(default)]
+    nap: Vec<Section>,
+}
+
+#[derive(Deserialize)]
+struct Section {
+    uid: Uid,
+    timezone: i32,
+    list: Vec<Pull>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Uid {
+    String(String),
+    Number(i32),
+}
+
+#[derive(Deserialize)]
+struct Pull {
+    id: String,
+    gacha_type: String,
+    uigf_gacha_type: Option<String>,
+    #[serde(default)]
+    item_id: String,
+    #[serde(default)]
+    item_type: String,
+    #[serde(default)]
+    name: String,
+    time: String,
+}
+
+impl Uid {
+    fn parse(&self) -> Result<i32> {
+        Ok(match self {
+            Uid::String(uid) => uid.parse()?,
+            Uid::Number(uid) => *uid,
+        })
+    }
+}
+
+#[utoipa::path(
+    tag = "uigf-import",
+    post,
+    path = "/api/uigf-import",
+    request_body = UigfImportParams,
+    responses(
+        (status = 200, description = "Pulls imported", body = UigfImport),
+        (status = 400, description = "Not logged in or invalid data"),
+        (status = 403, description = "Not verified"),
+    )
+)]
+#[post("/api/uigf-import")]
+async fn post_uigf_import(
+    session: Session,
+    params: web::Json<UigfImportParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let uigf: Uigf = serde_json::from_str(&params.data)?;
+
+    let admin = permissions::has(&username, Permission::AccessAllUids, &pool).await?;
+
+    for section in &uigf.hkrpg {
+        if !allowed::<Warps>(section, &username, admin, &pool).await? {
+            return Ok(HttpResponse::Forbidden().finish());
+        }
+    }
+
+    for section in &uigf.hk4e {
+        if !allowed::<Wishes>(section, &username, admin, &pool).await? {
+            return Ok(HttpResponse::Forbidden().finish());
+        }
+    }
+
+    for section in &uigf.nap {
+        if !allowed::<Signals>(section, &username, admin, &pool).await? {
+            return Ok(HttpResponse::Forbidden().finish());
+        }
+    }
+
+    // Convert everything first so an invalid section doesn't leave the others half imported
+    let mut warps = Vec::new();
+    for section in uigf.hkrpg {
+        let Some(prepared) = prepare::<Warps>(section, admin, &pool).await? else {
+            return Ok(HttpResponse::BadRequest().finish());
+        };
+
+        warps.push(prepared);
+    }
+
+    let mut wishes = Vec::new();
+    for section in uigf.hk4e {
+        let Some(prepared) = prepare::<Wishes>(section, admin, &pool).await? else {
+            return Ok(HttpResponse::BadRequest().finish());
+        };
+
+        wishes.push(prepared);
+    }
+
+    let mut signals = Vec::new();
+    for section in uigf.nap {
+        let Some(prepared) = prepare::<Signals>(section, admin, &pool).await? else {
+            return Ok(HttpResponse::BadRequest().finish());
+        };
+
+        signals.push(prepared);
+    }
+
+    let mut skipped = Vec::new();
+
+    for prepared in warps {
+        skipped.extend(write::<Warps>(prepared, admin, &pool).await?);
+    }
+
+    for prepared in wishes {
+        skipped.extend(write::<Wishes>(prepared, admin, &pool).await?);
+    }
+
+    for prepared in signals {
+        skipped.extend(write::<Signals>(prepared, admin, &pool).await?);
+    }
+
+    Ok(HttpResponse::Ok().json(UigfImport { skipped }))
+}
+
+async fn allowed<G: Gacha>(
+    section: &Section,
+    username: &str,
+    admin: bool,
+    pool: &PgPool,
+) -> Result<bool> {
+    let uid = section.uid.parse()?;
+
+    if admin {
+        return Ok(true);
+    }
+
+    G::verified(uid, username, pool).await
+}
+
+struct Prepared<G: Gacha> {
+    uid: i32,
+    set_alls: Vec<(G::GachaType, G::SetAll)>,
+    /// Unknown gacha types and how many pulls they had
+    skipped: HashMap<String, usize>,
+}
+
+/// UIGF timezones are whole hours from UTC
+fn timezone(hours: i32) -> Option<FixedOffset> {
+    if !(-12..=14).contains(&hours) {
+        return None;
+    }
+
+    FixedOffset::east_opt(hours.checked_mul(3600)?)
+}
+
+/// Returns None if the section has an invalid timezone
+async fn prepare<G: Gacha>(
+    section: Section,
+    admin: bool,
+    pool: &PgPool,
+) -> Result<Option<Prepared<G>>> {
+    let uid = section.uid.parse()?;
+    let Some(tz) = timezone(section.timezone) else {
+        return Ok(None);
+    };
+
+    let mut set_alls = Vec::new();
+    let mut skipped = HashMap::new();
+
+    let mut pulls_map: HashMap<_, Vec<Pull>> = HashMap::new();
+
+    for pull in section.list {
+        let uigf_gacha_type = pull.uigf_gacha_type.as_ref().unwrap_or(&pull.gacha_type);
+
+        // Newer banner types (e.g. collabs) aren't tracked, the rest of the file still is
+        let Some(gacha_type) = G::GachaType::iter()
+            .find(|&gacha_type| G::uigf_gacha_type(gacha_type).to_string() == *uigf_gacha_type)
+        else {
+            *skipped.entry(uigf_gacha_type.clone()).or_default() += 1;
+            continue;
+        };
+
+        pulls_map.entry(gacha_type).or_default().push(pull);
+    }
+
+    for gacha_type in G::GachaType::iter() {
+        let Some(pulls) = pulls_map.get_mut(&gacha_type) else {
+            continue;
+        };
+
+        pulls.sort_by_key(|pull| pull.id.parse::<i64>().unwrap_or_default());
+
+        let earliest_timestamp = G::get_earliest_timestamp(uid, gacha_type, pool).await?;
+
+        let mut set_all = G::SetAll::default();
+
+        for pull in pulls.iter() {
+            let timestamp = NaiveDateTime::parse_from_str(&pull.time, "%Y-%m-%d %H:%M:%S")?
+                .and_local_timezone(tz)
+                .unwrap()
+                .to_utc();
+
+            if !admin {
+                if let Some(earliest_timestamp) = earliest_timestamp {
+                    if timestamp >= earliest_timestamp {
+                        break;
+                    }
+                }
+            }
+
+            let entry = gacha::Entry {
+                id: pull.id.clone(),
+                uid: uid.to_string(),
+                item_type: pull.item_type.clone(),
+                item_id: pull.item_id.clone(),
+                name: pull.name.clone(),
+                time: pull.time.clone(),
+            };
+
+            G::push(uid, &entry, timestamp, false, &mut set_all, pool).await?;
+        }
+
+        set_alls.push((gacha_type, set_all));
+    }
+
+    Ok(Some(Prepared {
+        uid,
+        set_alls,
+        skipped,
+    }))
+}
+
+async fn write<G: Gacha>(
+    prepared: Prepared<G>,
+    admin: bool,
+    pool: &PgPool,
+) -> Result<Vec<SkippedPulls>> {
+    if admin {
+        G::register(prepared.uid, None, pool).await?;
+    }
+
+    for (gacha_type, set_all) in &prepared.set_alls {
+        G::set_all(*gacha_type, set_all, &mut *pool.acquire().await?).await?;
+    }
+
+    Ok(prepared
+        .skipped
+        .into_iter()
+        .map(|(gacha_type, count)| SkippedPulls {
+            game: G::GAME.to_string(),
+            uid: prepared.uid,
+            gacha_type,
+            count,
+        })
+        .collect())
+}
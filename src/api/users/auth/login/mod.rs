@@ -1,20 +1,22 @@
-use std::collections::HashMap;
-
 use actix_session::Session;
 use actix_web::{post, web, HttpResponse, Responder};
-use futures::lock::Mutex;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
-use uuid::Uuid;
 
-use crate::{api::ApiResult, database};
+use crate::{
+    access_tokens,
+    api::ApiResult,
+    database::{self, user_tokens::UserTokenKind},
+    pg_session_store::PENDING_USERNAME,
+    two_factor,
+};
 
 #[derive(OpenApi)]
 #[openapi(
     tags((name = "users/auth/login")),
-    paths(login),
-    components(schemas(UserLogin))
+    paths(login, login_two_factor),
+    components(schemas(UserLogin, TwoFactorLogin))
 )]
 struct ApiDoc;
 
@@ -23,7 +25,7 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(login);
+    cfg.service(login).service(login_two_factor);
 }
 
 #[derive(Serialize, Deserialize, ToSchema)]
@@ -44,7 +46,8 @@ pub enum UserLogin {
         )
     ),
     responses(
-        (status = 200, description = "Successfull login. The session id is returned in a cookie named `id`. You need to include this cookie in subsequent requests"),
+        (status = 200, description = "Successfull login. The session id is renewed and returned in a cookie named `id`. You need to include this cookie in subsequent requests"),
+        (status = 202, description = "Correct credentials, but the account has 2FA. Finish the login with `/api/users/auth/login/2fa` using the returned cookie"),
         (status = 400, description = "Don't have an account")
     )
 )]
@@ -52,7 +55,6 @@ pub enum UserLogin {
 async fn login(
     session: Session,
     user_login: web::Json<UserLogin>,
-    tokens: web::Data<Mutex<HashMap<Uuid, String>>>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let username = match &*user_login {
@@ -70,15 +72,70 @@ async fn login(
             username.clone()
         }
         UserLogin::Token { token } => {
-            let Some(username) = tokens.lock().await.remove(&token.parse()?) else {
+            let Some(user_token) = database::user_tokens::consume(
+                &access_tokens::hash(token.trim()),
+                UserTokenKind::Login,
+                &pool,
+            )
+            .await?
+            else {
                 return Ok(HttpResponse::BadRequest().finish());
             };
 
-            username.clone()
+            user_token.username
         }
     };
 
-    session.insert("username", username.to_lowercase())?;
+    let username = username.to_lowercase();
+
+    session.remove("username");
+
+    if two_factor::enabled(&username, &pool).await? {
+        session.insert(PENDING_USERNAME, username)?;
+
+        return Ok(HttpResponse::Accepted().finish());
+    }
+
+    session.remove(PENDING_USERNAME);
+    session.insert("username", username)?;
+    session.renew();
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct TwoFactorLogin {
+    /// TOTP or recovery code
+    code: String,
+}
+
+#[utoipa::path(
+    tag = "users/auth/login",
+    post,
+    path = "/api/users/auth/login/2fa",
+    request_body = TwoFactorLogin,
+    responses(
+        (status = 200, description = "Successfull login. The session id is renewed"),
+        (status = 400, description = "No pending login or wrong code")
+    )
+)]
+#[post("/api/users/auth/login/2fa")]
+async fn login_two_factor(
+    session: Session,
+    two_factor_login: web::Json<TwoFactorLogin>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>(PENDING_USERNAME) else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !two_factor::verify(&username, &two_factor_login.code, &pool).await? {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    session.remove(PENDING_USERNAME);
+    session.insert("username", username)?;
+    session.renew();
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -2,7 +2,10 @@ mod login;
 mod logout;
 mod register;
 mod renew;
+mod request_password_reset;
 mod request_token;
+mod reset_password;
+mod verify_email;
 
 use actix_web::web;
 use utoipa::OpenApi;
@@ -18,6 +21,9 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     openapi.merge(renew::openapi());
     openapi.merge(register::openapi());
     openapi.merge(request_token::openapi());
+    openapi.merge(request_password_reset::openapi());
+    openapi.merge(reset_password::openapi());
+    openapi.merge(verify_email::openapi());
     openapi
 }
 
@@ -26,5 +32,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .configure(logout::configure)
         .configure(register::configure)
         .configure(renew::configure)
-        .configure(request_token::configure);
+        .configure(request_token::configure)
+        .configure(request_password_reset::configure)
+        .configure(reset_password::configure)
+        .configure(verify_email::configure);
 }
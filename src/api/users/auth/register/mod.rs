@@ -6,8 +6,10 @@ use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
 
 use crate::{
-    api::{private, ApiResult},
+    api::{private, users::me::email, ApiResult},
     database,
+    mailer::Mailer,
+    Language,
 };
 
 #[derive(OpenApi)]
@@ -31,6 +33,9 @@ pub struct UserRegister {
     username: String,
     password: String,
     email: Option<String>,
+    /// Language of the verification email
+    #[serde(default)]
+    language: Language,
 }
 
 #[utoipa::path(
@@ -47,8 +52,8 @@ pub struct UserRegister {
         ("api_key" = [])
     ),
     responses(
-        (status = 200, description = "Successfull register. The session id is returned in a cookie named `id`. You need to include this cookie in subsequent requests"),
-        (status = 400, description = "Credentials too long"),
+        (status = 200, description = "Successfull register. A verification link is sent to the email if there is one. The session id is returned in a cookie named `id`. You need to include this cookie in subsequent requests"),
+        (status = 400, description = "Credentials too long or invalid email"),
         (status = 409, description = "Account already exists")
     )
 )]
@@ -56,15 +61,23 @@ pub struct UserRegister {
 async fn register(
     session: Session,
     user_register: web::Json<UserRegister>,
+    mailer: web::Data<dyn Mailer>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let username = user_register.username.trim().to_lowercase();
     let password = user_register.password.clone();
-    let email = user_register.email.clone();
+    let email = user_register
+        .email
+        .as_ref()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
 
     if username.len() > 32
         || password.len() > 64
         || email.as_ref().map(|s| s.len()).unwrap_or_default() > 64
+        || email
+            .as_ref()
+            .is_some_and(|s| s.parse::<lettre::Address>().is_err())
     {
         return Ok(HttpResponse::BadRequest().finish());
     }
@@ -86,14 +99,30 @@ async fn register(
 
     {
         let username = username.clone();
+        let email = email.clone();
         let user = database::users::DbUser {
             username,
             password,
             email,
+            email_verified: false,
         };
         database::users::set(&user, &pool).await?;
     }
 
+    if let Some(email) = &email {
+        if let Err(e) = email::send_verification(
+            &username,
+            email,
+            user_register.language,
+            mailer.get_ref(),
+            &pool,
+        )
+        .await
+        {
+            error!("Sending verification to {username} failed with {e}");
+        }
+    }
+
     session.insert("username", username)?;
 
     Ok(HttpResponse::Ok().finish())
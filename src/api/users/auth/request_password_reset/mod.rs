@@ -0,0 +1,107 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    access_tokens,
+    api::ApiResult,
+    database::{
+        self,
+        user_tokens::{DbUserToken, UserTokenKind},
+    },
+    mailer::{self, templates, Mailer},
+    Language,
+};
+
+const EXPIRY_MINUTES: i64 = 30;
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/auth/request-password-reset")),
+    paths(request_password_reset),
+    components(schemas(RequestPasswordReset))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(request_password_reset);
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RequestPasswordReset {
+    username: String,
+    /// Language of the email
+    #[serde(default)]
+    language: Language,
+}
+
+#[utoipa::path(
+    tag = "users/auth/request-password-reset",
+    post,
+    path = "/api/users/auth/request-password-reset",
+    request_body = RequestPasswordReset,
+    responses(
+        (status = 200, description = "Send mail with a password reset link"),
+        (status = 400, description = "No verified email connected or a reset is still pending"),
+    )
+)]
+#[post("/api/users/auth/request-password-reset")]
+async fn request_password_reset(
+    request_password_reset: web::Json<RequestPasswordReset>,
+    mailer: web::Data<dyn Mailer>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let username = request_password_reset.username.trim().to_lowercase();
+
+    database::user_tokens::delete_expired(&pool).await?;
+
+    if database::user_tokens::exists_active_by_username_and_kind(
+        &username,
+        UserTokenKind::ResetPassword,
+        &pool,
+    )
+    .await?
+    {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let Ok(user) = database::users::get_one_by_username(&username, &pool).await else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let (Some(email), true) = (user.email, user.email_verified) else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let to = mailer::mailbox(&username, &email)?;
+
+    let token = Uuid::new_v4().to_string();
+
+    let mail = templates::reset_password(
+        to,
+        request_password_reset.language,
+        &username,
+        &token,
+        EXPIRY_MINUTES,
+    );
+
+    mailer.send(&mail).await?;
+
+    let user_token = DbUserToken {
+        username,
+        kind: UserTokenKind::ResetPassword,
+        email: None,
+        expires_at: Utc::now() + Duration::minutes(EXPIRY_MINUTES),
+    };
+
+    database::user_tokens::set(&user_token, &access_tokens::hash(&token), &pool).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
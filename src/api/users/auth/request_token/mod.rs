@@ -1,20 +1,22 @@
-use std::{collections::HashMap, env};
-
-use actix_web::{post, rt, web, HttpResponse, Responder};
-use futures::lock::Mutex;
-use lettre::{transport::smtp::authentication::Credentials, Message, SmtpTransport, Transport};
+use actix_web::{post, web, HttpResponse, Responder};
+use chrono::{Duration, Utc};
 use serde::Deserialize;
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
 use uuid::Uuid;
 
-use crate::{api::ApiResult, database};
-
-type TokenMap = Mutex<HashMap<Uuid, String>>;
+use crate::{
+    access_tokens,
+    api::ApiResult,
+    database::{
+        self,
+        user_tokens::{DbUserToken, UserTokenKind},
+    },
+    mailer::{self, templates, Mailer},
+    Language,
+};
 
-lazy_static::lazy_static! {
-    static ref CACHE: std::sync::Mutex<Option<web::Data<TokenMap>>> = std::sync::Mutex::new(None);
-}
+const EXPIRY_MINUTES: i64 = 5;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -29,18 +31,15 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    let data = CACHE
-        .lock()
-        .unwrap()
-        .get_or_insert_with(web::Data::default)
-        .clone();
-
-    cfg.service(request_token).app_data(data);
+    cfg.service(request_token);
 }
 
 #[derive(Deserialize, ToSchema)]
 pub struct RequestToken {
     username: String,
+    /// Language of the email
+    #[serde(default)]
+    language: Language,
 }
 
 #[utoipa::path(
@@ -50,18 +49,26 @@ pub struct RequestToken {
     request_body = RequestToken,
     responses(
         (status = 200, description = "Send mail with emergency login"),
-        (status = 400, description = "No email connected"),
+        (status = 400, description = "No verified email connected or a token is still active"),
     )
 )]
 #[post("/api/users/auth/request-token")]
 async fn request_token(
     request_token: web::Json<RequestToken>,
-    tokens: web::Data<Mutex<HashMap<Uuid, String>>>,
+    mailer: web::Data<dyn Mailer>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let username = request_token.username.trim().to_lowercase();
 
-    if tokens.lock().await.values().any(|s| s == &username) {
+    database::user_tokens::delete_expired(&pool).await?;
+
+    if database::user_tokens::exists_active_by_username_and_kind(
+        &username,
+        UserTokenKind::Login,
+        &pool,
+    )
+    .await?
+    {
         return Ok(HttpResponse::BadRequest().finish());
     }
 
@@ -69,36 +76,26 @@ async fn request_token(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let Some(email) = user.email else {
+    let (Some(email), true) = (user.email, user.email_verified) else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let to = format!("{username} <{email}>").parse()?;
-
-    let token = Uuid::new_v4();
-    let email = Message::builder()
-        .from("stardb <noreply@stardb.gg>".parse()?)
-        .to(to)
-        .subject("StarDB.GG Emergency Login")
-        .body(format!(
-            "Use the following link to login:\nhttps://stardb.gg/login?token={token}"
-        ))?;
+    let to = mailer::mailbox(&username, &email)?;
 
-    let credentials = Credentials::new(env::var("SMTP_USERNAME")?, env::var("SMTP_PASSWORD")?);
+    let token = Uuid::new_v4().to_string();
 
-    let mailer = SmtpTransport::relay("smtppro.zoho.eu")?
-        .credentials(credentials)
-        .build();
+    let mail = templates::login(to, request_token.language, &token, EXPIRY_MINUTES);
 
-    mailer.send(&email)?;
+    mailer.send(&mail).await?;
 
-    tokens.lock().await.insert(token, username.clone());
-
-    rt::spawn(async move {
-        rt::time::sleep(std::time::Duration::from_secs(5 * 60)).await;
+    let user_token = DbUserToken {
+        username,
+        kind: UserTokenKind::Login,
+        email: None,
+        expires_at: Utc::now() + Duration::minutes(EXPIRY_MINUTES),
+    };
 
-        tokens.lock().await.remove(&token);
-    });
+    database::user_tokens::set(&user_token, &access_tokens::hash(&token), &pool).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -0,0 +1,104 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use argon2::Config;
+use rand::Rng;
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    access_tokens,
+    api::{users::me::email, ApiResult},
+    database::{self, user_tokens::UserTokenKind},
+    mailer::{templates, Mailer},
+    Language,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/auth/reset-password")),
+    paths(reset_password),
+    components(schemas(ResetPassword))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(reset_password);
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ResetPassword {
+    token: String,
+    password: String,
+    /// Language of the notification email
+    #[serde(default)]
+    language: Language,
+}
+
+#[utoipa::path(
+    tag = "users/auth/reset-password",
+    post,
+    path = "/api/users/auth/reset-password",
+    request_body = ResetPassword,
+    responses(
+        (status = 200, description = "Set the new password, logged out all sessions and revoked all access tokens. The verified email is notified"),
+        (status = 400, description = "Invalid or expired token, or password too long"),
+    )
+)]
+#[post("/api/users/auth/reset-password")]
+async fn reset_password(
+    reset_password: web::Json<ResetPassword>,
+    mailer: web::Data<dyn Mailer>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    if reset_password.password.len() > 64 {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let Some(user_token) = database::user_tokens::consume(
+        &access_tokens::hash(reset_password.token.trim()),
+        UserTokenKind::ResetPassword,
+        &pool,
+    )
+    .await?
+    else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let username = user_token.username;
+
+    let salt = rand::rng().random::<[u8; 32]>();
+
+    let password = argon2::hash_encoded(
+        reset_password.password.as_bytes(),
+        &salt,
+        &Config::rfc9106_low_mem(),
+    )?;
+
+    database::users::update_password_by_username(&username, &password, &pool).await?;
+
+    database::sessions::delete_by_username(&username, &pool).await?;
+
+    database::access_tokens::delete_by_username(&username, &pool).await?;
+
+    database::user_tokens::delete_unused_by_username_and_kind(
+        &username,
+        UserTokenKind::Login,
+        &pool,
+    )
+    .await?;
+
+    let user = database::users::get_one_by_username(&username, &pool).await?;
+
+    email::notify(
+        &user,
+        |to| templates::password_changed(to, reset_password.language, &username),
+        mailer.get_ref(),
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,73 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    access_tokens,
+    api::ApiResult,
+    database::{self, user_tokens::UserTokenKind},
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/auth/verify-email")),
+    paths(verify_email),
+    components(schemas(VerifyEmail))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(verify_email);
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct VerifyEmail {
+    token: String,
+}
+
+#[utoipa::path(
+    tag = "users/auth/verify-email",
+    post,
+    path = "/api/users/auth/verify-email",
+    request_body = VerifyEmail,
+    responses(
+        (status = 200, description = "Verified email"),
+        (status = 400, description = "Invalid or expired token, or the email changed since"),
+    )
+)]
+#[post("/api/users/auth/verify-email")]
+async fn verify_email(
+    verify_email: web::Json<VerifyEmail>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Some(user_token) = database::user_tokens::consume(
+        &access_tokens::hash(verify_email.token.trim()),
+        UserTokenKind::VerifyEmail,
+        &pool,
+    )
+    .await?
+    else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let Some(email) = user_token.email else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !database::users::update_email_verified_by_username_and_email(
+        &user_token.username,
+        &email,
+        &pool,
+    )
+    .await?
+    {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
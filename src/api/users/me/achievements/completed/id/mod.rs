@@ -43,7 +43,7 @@ async fn put_user_achievement_completed(
     let id = *id;
     let db_complete =
         database::users_achievements_completed::DbUserAchievementCompleted { username, id };
-    database::users_achievements_completed::add(&db_complete, &pool).await?;
+    database::users_achievements_completed::add(&db_complete, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
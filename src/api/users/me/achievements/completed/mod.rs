@@ -81,7 +81,7 @@ async fn put_user_achievements_completed(
     for id in ids.0 {
         complete.id = id;
 
-        database::users_achievements_completed::add(&complete, &pool).await?;
+        database::users_achievements_completed::add(&complete, pool.get_ref()).await?;
     }
 
     Ok(HttpResponse::Ok().finish())
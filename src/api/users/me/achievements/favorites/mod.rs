@@ -80,7 +80,7 @@ async fn put_user_achievements_favorites(
     for id in ids.0 {
         favorite.id = id;
 
-        database::users_achievements_favorites::add(&favorite, &pool).await?;
+        database::users_achievements_favorites::add(&favorite, pool.get_ref()).await?;
     }
 
     Ok(HttpResponse::Ok().finish())
@@ -0,0 +1,44 @@
+use actix_session::Session;
+use actix_web::{delete, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{api::ApiResult, database};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/deletion")),
+    paths(delete_deletion)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(delete_deletion);
+}
+
+#[utoipa::path(
+    tag = "users/me/deletion",
+    delete,
+    path = "/api/users/me/deletion",
+    responses(
+        (status = 200, description = "Cancelled the scheduled account deletion"),
+        (status = 400, description = "Not logged in"),
+        (status = 404, description = "No deletion scheduled"),
+    )
+)]
+#[delete("/api/users/me/deletion")]
+async fn delete_deletion(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !database::users_deletions::delete_by_username(&username, &pool).await? {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -1,15 +1,31 @@
 use actix_session::Session;
-use actix_web::{delete, get, put, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use lettre::{message::Mailbox, Address};
 use serde::Deserialize;
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
 
-use crate::{api::ApiResult, database};
+use crate::{
+    access_tokens,
+    api::{ApiResult, LanguageParams},
+    database::{
+        self,
+        user_tokens::{DbUserToken, UserTokenKind},
+        users::DbUser,
+    },
+    mailer::{self, templates, Mail, Mailer},
+    Language,
+};
+
+const VERIFY_EXPIRY_MINUTES: i64 = 24 * 60;
 
 #[derive(OpenApi)]
 #[openapi(
     tags((name = "users/me/email")),
-    paths(get_email, put_email, delete_email),
+    paths(get_email, put_email, delete_email, post_email_verify),
     components(schemas(
         EmailUpdate
     ))
@@ -23,7 +39,63 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(get_email)
         .service(put_email)
-        .service(delete_email);
+        .service(delete_email)
+        .service(post_email_verify);
+}
+
+/// Replaces any pending verification for the user and mails a new link to `email`
+pub async fn send_verification(
+    username: &str,
+    email: &str,
+    language: Language,
+    mailer: &dyn Mailer,
+    pool: &PgPool,
+) -> Result<()> {
+    database::user_tokens::delete_unused_by_username_and_kind(
+        username,
+        UserTokenKind::VerifyEmail,
+        pool,
+    )
+    .await?;
+
+    let token = Uuid::new_v4().to_string();
+
+    let user_token = DbUserToken {
+        username: username.to_string(),
+        kind: UserTokenKind::VerifyEmail,
+        email: Some(email.to_string()),
+        expires_at: Utc::now() + Duration::minutes(VERIFY_EXPIRY_MINUTES),
+    };
+
+    database::user_tokens::set(&user_token, &access_tokens::hash(&token), pool).await?;
+
+    let to = mailer::mailbox(username, email)?;
+
+    mailer
+        .send(&templates::verify_email(
+            to,
+            language,
+            username,
+            &token,
+            VERIFY_EXPIRY_MINUTES,
+        ))
+        .await
+}
+
+/// Mails the verified address of the user. Failures are only logged so they don't undo the change
+pub async fn notify(user: &DbUser, mail: impl FnOnce(Mailbox) -> Mail, mailer: &dyn Mailer) {
+    let (Some(email), true) = (&user.email, user.email_verified) else {
+        return;
+    };
+
+    let result = match mailer::mailbox(&user.username, email) {
+        Ok(to) => mailer.send(&mail(to)).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        error!("Notifying {} failed with {e}", user.username);
+    }
 }
 
 #[utoipa::path(
@@ -49,6 +121,9 @@ async fn get_email(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl
 #[derive(Deserialize, ToSchema)]
 pub struct EmailUpdate {
     email: String,
+    /// Language of the verification email
+    #[serde(default)]
+    language: Language,
 }
 
 #[utoipa::path(
@@ -57,21 +132,54 @@ pub struct EmailUpdate {
     path = "/api/users/me/email",
     request_body = EmailUpdate,
     responses(
-        (status = 200, description = "Updated email"),
-        (status = 400, description = "Not logged in"),
+        (status = 200, description = "Updated email and sent a verification link to it. The previous address is notified"),
+        (status = 400, description = "Not logged in or invalid email"),
     )
 )]
 #[put("/api/users/me/email")]
 async fn put_email(
     session: Session,
     email_update: web::Json<EmailUpdate>,
+    mailer: web::Data<dyn Mailer>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let Ok(Some(username)) = session.get::<String>("username") else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    database::users::update_email_by_username(&username, &email_update.email, &pool).await?;
+    let email = email_update.email.trim().to_string();
+
+    if email.len() > 64 || email.parse::<Address>().is_err() {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let user = database::users::get_one_by_username(&username, &pool).await?;
+
+    let changed = user.email.as_deref() != Some(email.as_str());
+
+    if !changed && user.email_verified {
+        return Ok(HttpResponse::Ok().finish());
+    }
+
+    database::users::update_email_by_username(&username, &email, &pool).await?;
+
+    send_verification(
+        &username,
+        &email,
+        email_update.language,
+        mailer.get_ref(),
+        &pool,
+    )
+    .await?;
+
+    if changed {
+        notify(
+            &user,
+            |to| templates::email_changed(to, email_update.language, &username),
+            mailer.get_ref(),
+        )
+        .await;
+    }
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -80,18 +188,79 @@ async fn put_email(
     tag = "users/me/email",
     delete,
     path = "/api/users/me/email",
+    params(LanguageParams),
     responses(
-        (status = 200, description = "Deleted email"),
+        (status = 200, description = "Deleted email. The previous address is notified"),
         (status = 400, description = "Not logged in"),
     )
 )]
 #[delete("/api/users/me/email")]
-async fn delete_email(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+async fn delete_email(
+    session: Session,
+    language_params: web::Query<LanguageParams>,
+    mailer: web::Data<dyn Mailer>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
     let Ok(Some(username)) = session.get::<String>("username") else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
+    let user = database::users::get_one_by_username(&username, &pool).await?;
+
     database::users::delete_email_by_username(&username, &pool).await?;
 
+    database::user_tokens::delete_unused_by_username_and_kind(
+        &username,
+        UserTokenKind::VerifyEmail,
+        &pool,
+    )
+    .await?;
+
+    notify(
+        &user,
+        |to| templates::email_changed(to, language_params.lang, &username),
+        mailer.get_ref(),
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "users/me/email",
+    post,
+    path = "/api/users/me/email/verify",
+    params(LanguageParams),
+    responses(
+        (status = 200, description = "Sent a new verification link"),
+        (status = 400, description = "Not logged in, no email or already verified"),
+    )
+)]
+#[post("/api/users/me/email/verify")]
+async fn post_email_verify(
+    session: Session,
+    language_params: web::Query<LanguageParams>,
+    mailer: web::Data<dyn Mailer>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let user = database::users::get_one_by_username(&username, &pool).await?;
+
+    let (Some(email), false) = (user.email, user.email_verified) else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    send_verification(
+        &username,
+        &email,
+        language_params.lang,
+        mailer.get_ref(),
+        &pool,
+    )
+    .await?;
+
     Ok(HttpResponse::Ok().finish())
 }
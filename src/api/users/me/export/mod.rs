@@ -1,12 +1,14 @@
+mod uigf;
+
 use actix_session::Session;
 use actix_web::{get, web, HttpResponse, Responder};
 use chrono::{DateTime, Utc};
-use ed25519_dalek::{ed25519::signature::SignerMut, SigningKey};
-use futures::lock::Mutex;
+use ed25519_dalek::Signature;
+use serde_json::value::RawValue;
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database, Language};
+use crate::{api::ApiResult, database, signing::SigningKeys, Language};
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
@@ -35,139 +37,159 @@ use crate::{api::ApiResult, database, Language};
 struct ApiDoc;
 
 pub fn openapi() -> utoipa::openapi::OpenApi {
-    ApiDoc::openapi()
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(uigf::openapi());
+    openapi
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(get_export);
+    cfg.service(get_export).configure(uigf::configure);
 }
 
 #[derive(serde::Serialize, utoipa::ToSchema)]
 struct Export {
     user: UserExport,
     signature: String,
+    kid: String,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct UserExport {
-    username: String,
-    hsr: Hsr,
-    zzz: Zzz,
-    gi: Gi,
+#[derive(serde::Deserialize)]
+struct SignedExport<'a> {
+    #[serde(borrow)]
+    user: &'a RawValue,
+    signature: String,
+    kid: Option<String>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Hsr {
-    achievements: Vec<i32>,
-    uids: Vec<HsrUid>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct UserExport {
+    pub username: String,
+    /// Missing in exports from before it was added
+    #[serde(default)]
+    pub issued_at: Option<DateTime<Utc>>,
+    pub hsr: Hsr,
+    pub zzz: Zzz,
+    pub gi: Gi,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct HsrUid {
-    uid: i32,
-    verified: bool,
-    private: bool,
-    warps: Warps,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Hsr {
+    pub achievements: Vec<i32>,
+    #[serde(default)]
+    pub favorites: Vec<i32>,
+    pub uids: Vec<HsrUid>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Warps {
-    departure: Vec<Warp>,
-    standard: Vec<Warp>,
-    character: Vec<Warp>,
-    light_cone: Vec<Warp>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct HsrUid {
+    pub uid: i32,
+    pub verified: bool,
+    pub private: bool,
+    pub warps: Warps,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Warp {
-    id: String,
-    item_id: i32,
-    r#type: WarpType,
-    timestamp: DateTime<Utc>,
-    official: bool,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Warps {
+    pub departure: Vec<Warp>,
+    pub standard: Vec<Warp>,
+    pub character: Vec<Warp>,
+    pub light_cone: Vec<Warp>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Warp {
+    pub id: String,
+    pub item_id: i32,
+    pub r#type: WarpType,
+    pub timestamp: DateTime<Utc>,
+    pub official: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
-enum WarpType {
+pub enum WarpType {
     Character,
     LightCone,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Zzz {
-    achievements: Vec<i32>,
-    uids: Vec<ZzzUid>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Zzz {
+    pub achievements: Vec<i32>,
+    #[serde(default)]
+    pub favorites: Vec<i32>,
+    pub uids: Vec<ZzzUid>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct ZzzUid {
-    uid: i32,
-    verified: bool,
-    private: bool,
-    signals: Signals,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct ZzzUid {
+    pub uid: i32,
+    pub verified: bool,
+    pub private: bool,
+    pub signals: Signals,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Signals {
-    standard: Vec<Signal>,
-    character: Vec<Signal>,
-    w_engine: Vec<Signal>,
-    bangboo: Vec<Signal>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Signals {
+    pub standard: Vec<Signal>,
+    pub character: Vec<Signal>,
+    pub w_engine: Vec<Signal>,
+    pub bangboo: Vec<Signal>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Signal {
-    id: String,
-    item_id: i32,
-    r#type: SignalType,
-    timestamp: DateTime<Utc>,
-    official: bool,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Signal {
+    pub id: String,
+    pub item_id: i32,
+    pub r#type: SignalType,
+    pub timestamp: DateTime<Utc>,
+    pub official: bool,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
-enum SignalType {
+pub enum SignalType {
     Character,
     WEngine,
     Bangboo,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Gi {
-    achievements: Vec<i32>,
-    uids: Vec<GiUid>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Gi {
+    pub achievements: Vec<i32>,
+    #[serde(default)]
+    pub favorites: Vec<i32>,
+    pub uids: Vec<GiUid>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct GiUid {
-    uid: i32,
-    verified: bool,
-    private: bool,
-    wishes: Wishes,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct GiUid {
+    pub uid: i32,
+    pub verified: bool,
+    pub private: bool,
+    pub wishes: Wishes,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Wishes {
-    beginner: Vec<Wish>,
-    standard: Vec<Wish>,
-    character: Vec<Wish>,
-    weapon: Vec<Wish>,
-    chronicled: Vec<Wish>,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Wishes {
+    pub beginner: Vec<Wish>,
+    pub standard: Vec<Wish>,
+    pub character: Vec<Wish>,
+    pub weapon: Vec<Wish>,
+    pub chronicled: Vec<Wish>,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
-struct Wish {
-    id: String,
-    item_id: i32,
-    r#type: WishType,
-    timestamp: DateTime<Utc>,
-    official: bool,
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct Wish {
+    pub id: String,
+    pub item_id: i32,
+    pub r#type: WishType,
+    pub timestamp: DateTime<Utc>,
+    pub official: bool,
 }
 
-#[derive(serde::Serialize, utoipa::ToSchema)]
+#[derive(serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
-enum WishType {
+pub enum WishType {
     Character,
     Weapon,
 }
@@ -244,7 +266,7 @@ impl From<database::gi::wishes::DbWish> for Wish {
 #[get("/api/users/me/export")]
 async fn get_export(
     session: Session,
-    signing_key: web::Data<Mutex<SigningKey>>,
+    signing_keys: web::Data<SigningKeys>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let Ok(Some(username)) = session.get::<String>("username") else {
@@ -259,9 +281,15 @@ async fn get_export(
                 .map(|a| a.id)
                 .collect();
 
+        let favorites = database::users_achievements_favorites::get_by_username(&username, &pool)
+            .await?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+
         let mut uids = Vec::new();
 
-        for connection in database::connections::get_by_username(&username, &pool).await? {
+        for connection in database::connections::get_by_username(&username, pool.get_ref()).await? {
             let uid = connection.uid;
             let verified = connection.verified;
             let private = connection.private;
@@ -305,7 +333,11 @@ async fn get_export(
             });
         }
 
-        Hsr { achievements, uids }
+        Hsr {
+            achievements,
+            favorites,
+            uids,
+        }
     };
 
     let zzz = {
@@ -316,9 +348,18 @@ async fn get_export(
                 .map(|a| a.id)
                 .collect();
 
+        let favorites =
+            database::zzz::users_achievements_favorites::get_by_username(&username, &pool)
+                .await?
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+
         let mut uids = Vec::new();
 
-        for connection in database::zzz::connections::get_by_username(&username, &pool).await? {
+        for connection in
+            database::zzz::connections::get_by_username(&username, pool.get_ref()).await?
+        {
             let uid = connection.uid;
             let verified = connection.verified;
             let private = connection.private;
@@ -362,7 +403,11 @@ async fn get_export(
             });
         }
 
-        Zzz { achievements, uids }
+        Zzz {
+            achievements,
+            favorites,
+            uids,
+        }
     };
 
     let gi = {
@@ -373,9 +418,18 @@ async fn get_export(
                 .map(|a| a.id)
                 .collect();
 
+        let favorites =
+            database::gi::users_achievements_favorites::get_by_username(&username, &pool)
+                .await?
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+
         let mut uids = Vec::new();
 
-        for connection in database::gi::connections::get_by_username(&username, &pool).await? {
+        for connection in
+            database::gi::connections::get_by_username(&username, pool.get_ref()).await?
+        {
             let uid = connection.uid;
             let verified = connection.verified;
             let private = connection.private;
@@ -426,20 +480,65 @@ async fn get_export(
             });
         }
 
-        Gi { achievements, uids }
+        Gi {
+            achievements,
+            favorites,
+            uids,
+        }
     };
 
     let user = UserExport {
         username,
+        issued_at: Some(Utc::now()),
         hsr,
         zzz,
         gi,
     };
 
     let user_bytes = serde_json::to_vec(&user)?;
-    let signature = signing_key.lock().await.sign(&user_bytes).to_string();
+    let signature = signing_keys.sign(&user_bytes).to_string();
+    let kid = signing_keys.kid().to_string();
 
-    let export = Export { user, signature };
+    let export = Export {
+        user,
+        signature,
+        kid,
+    };
 
     Ok(HttpResponse::Ok().json(export))
 }
+
+/// Checks a full export file and returns its contents with the id of the key that signed it.
+pub fn verify(
+    data: &str,
+    signing_keys: &SigningKeys,
+) -> anyhow::Result<Option<(UserExport, String)>> {
+    let export: SignedExport = serde_json::from_str(data)?;
+
+    verify_detached(
+        export.user.get(),
+        &export.signature,
+        export.kid.as_deref(),
+        signing_keys,
+    )
+}
+
+pub fn verify_detached(
+    user: &str,
+    signature: &str,
+    kid: Option<&str>,
+    signing_keys: &SigningKeys,
+) -> anyhow::Result<Option<(UserExport, String)>> {
+    let Ok(signature) = signature.parse::<Signature>() else {
+        return Ok(None);
+    };
+
+    let user_export: UserExport = serde_json::from_str(user)?;
+
+    // Only the exact bytes that were signed verify, any reformatting counts as a modification
+    let kid = signing_keys
+        .verify(kid, user.as_bytes(), &signature)
+        .map(ToString::to_string);
+
+    Ok(kid.map(|kid| (user_export, kid)))
+}
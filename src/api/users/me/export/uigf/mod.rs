@@ -0,0 +1,380 @@
+use actix_session::Session;
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{ApiResult, LanguageParams},
+    database,
+    gacha::{gi::Wishes, hsr::Warps, zzz::Signals, Gacha},
+    GachaType, GiGachaType, ZzzGachaType,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/export/uigf")),
+    paths(get_export_uigf),
+    components(schemas(
+        Uigf,
+        Info,
+        Game,
+        Entry
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_export_uigf);
+}
+
+#[derive(Serialize, ToSchema)]
+struct Uigf {
+    info: Info,
+    hk4e: Vec<Game>,
+    hkrpg: Vec<Game>,
+    nap: Vec<Game>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Info {
+    export_timestamp: i64,
+    export_app: String,
+    export_app_version: String,
+    version: String,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Game {
+    uid: String,
+    timezone: i32,
+    lang: String,
+    list: Vec<Entry>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct Entry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uigf_gacha_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gacha_id: Option<String>,
+    gacha_type: String,
+    item_id: String,
+    count: String,
+    time: String,
+    name: String,
+    item_type: String,
+    rank_type: String,
+    id: String,
+}
+
+impl Entry {
+    fn new(
+        gacha_type: i32,
+        id: i64,
+        item_id: i32,
+        item_type: &str,
+        name: Option<String>,
+        rarity: Option<i32>,
+        time: String,
+    ) -> Self {
+        Self {
+            uigf_gacha_type: None,
+            gacha_id: None,
+            gacha_type: gacha_type.to_string(),
+            item_id: item_id.to_string(),
+            count: "1".to_string(),
+            time,
+            name: name.unwrap_or_default(),
+            item_type: item_type.to_string(),
+            rank_type: rarity.unwrap_or_default().to_string(),
+            id: id.to_string(),
+        }
+    }
+}
+
+fn time(timestamp: DateTime<Utc>, timezone: i32) -> String {
+    timestamp
+        .with_timezone(&FixedOffset::east_opt(3600 * timezone).unwrap())
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// Banners can run at the same time, so the one featuring the pulled item wins. Anything else (lost
+/// 50/50s, 4* items) can't be told apart and gets the first banner that was running
+fn gacha_id(
+    banners: &[database::banners::DbBanner],
+    featured: impl Fn(&database::banners::DbBanner) -> Option<i32>,
+    item_id: i32,
+    timestamp: DateTime<Utc>,
+) -> Option<i32> {
+    let mut running = banners
+        .iter()
+        .filter(|b| featured(b).is_some() && (b.start..b.end).contains(&timestamp))
+        .peekable();
+
+    let first = running.peek().map(|b| b.id);
+
+    running
+        .find(|b| featured(b) == Some(item_id))
+        .map(|b| b.id)
+        .or(first)
+}
+
+#[utoipa::path(
+    tag = "users/me/export/uigf",
+    get,
+    path = "/api/users/me/export/uigf",
+    params(LanguageParams),
+    responses(
+        (status = 200, description = "UIGF v4", body = Uigf),
+        (status = 400, description = "Not logged in"),
+    )
+)]
+#[get("/api/users/me/export/uigf")]
+async fn get_export_uigf(
+    session: Session,
+    language_params: web::Query<LanguageParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let language = language_params.lang;
+
+    let mut hkrpg = Vec::new();
+
+    let banners = database::banners::get_all(&pool).await?;
+
+    for connection in database::connections::get_by_username(&username, pool.get_ref()).await? {
+        if !connection.verified {
+            continue;
+        }
+
+        let uid = connection.uid;
+        let timezone = Warps::time_zone(uid);
+
+        let mut list = Vec::new();
+
+        for gacha_type in [
+            GachaType::Standard,
+            GachaType::Departure,
+            GachaType::Special,
+            GachaType::Lc,
+        ] {
+            let warps = match gacha_type {
+                GachaType::Standard => {
+                    database::warps::standard::get_by_uid(uid, language, &pool).await?
+                }
+                GachaType::Departure => {
+                    database::warps::departure::get_by_uid(uid, language, &pool).await?
+                }
+                GachaType::Special => {
+                    database::warps::special::get_by_uid(uid, language, &pool).await?
+                }
+                GachaType::Lc => database::warps::lc::get_by_uid(uid, language, &pool).await?,
+            };
+
+            for warp in warps {
+                let (item_id, item_type) = match (warp.character, warp.light_cone) {
+                    (Some(character), _) => (character, "Character"),
+                    (_, Some(light_cone)) => (light_cone, "Light Cone"),
+                    _ => continue,
+                };
+
+                let gacha_id = match gacha_type {
+                    GachaType::Standard => Some(1001),
+                    GachaType::Departure => Some(4001),
+                    GachaType::Special => {
+                        gacha_id(&banners, |b| b.character, item_id, warp.timestamp)
+                    }
+                    GachaType::Lc => gacha_id(&banners, |b| b.light_cone, item_id, warp.timestamp),
+                };
+
+                let mut entry = Entry::new(
+                    gacha_type.id(),
+                    warp.id,
+                    item_id,
+                    item_type,
+                    warp.name,
+                    warp.rarity,
+                    time(warp.timestamp, timezone),
+                );
+
+                entry.gacha_id = Some(gacha_id.map(|id| id.to_string()).unwrap_or_default());
+
+                list.push(entry);
+            }
+        }
+
+        list.sort_unstable_by_key(|e| e.id.parse::<i64>().unwrap_or_default());
+
+        hkrpg.push(Game {
+            uid: uid.to_string(),
+            timezone,
+            lang: language.uigf(),
+            list,
+        });
+    }
+
+    let mut hk4e = Vec::new();
+
+    for connection in database::gi::connections::get_by_username(&username, pool.get_ref()).await? {
+        if !connection.verified {
+            continue;
+        }
+
+        let uid = connection.uid;
+        let timezone = Wishes::time_zone(uid);
+
+        let mut list = Vec::new();
+
+        for gacha_type in [
+            GiGachaType::Beginner,
+            GiGachaType::Standard,
+            GiGachaType::Character,
+            GiGachaType::Weapon,
+            GiGachaType::Chronicled,
+        ] {
+            let wishes = match gacha_type {
+                GiGachaType::Beginner => {
+                    database::gi::wishes::beginner::get_by_uid(uid, language, &pool).await?
+                }
+                GiGachaType::Standard => {
+                    database::gi::wishes::standard::get_by_uid(uid, language, &pool).await?
+                }
+                GiGachaType::Character => {
+                    database::gi::wishes::character::get_by_uid(uid, language, &pool).await?
+                }
+                GiGachaType::Weapon => {
+                    database::gi::wishes::weapon::get_by_uid(uid, language, &pool).await?
+                }
+                GiGachaType::Chronicled => {
+                    database::gi::wishes::chronicled::get_by_uid(uid, language, &pool).await?
+                }
+            };
+
+            for wish in wishes {
+                let (item_id, item_type) = match (wish.character, wish.weapon) {
+                    (Some(character), _) => (character, "Character"),
+                    (_, Some(weapon)) => (weapon, "Weapon"),
+                    _ => continue,
+                };
+
+                let mut entry = Entry::new(
+                    gacha_type.id(),
+                    wish.id,
+                    item_id,
+                    item_type,
+                    wish.name,
+                    wish.rarity,
+                    time(wish.timestamp, timezone),
+                );
+
+                entry.uigf_gacha_type = Some(gacha_type.id().to_string());
+
+                list.push(entry);
+            }
+        }
+
+        list.sort_unstable_by_key(|e| e.id.parse::<i64>().unwrap_or_default());
+
+        hk4e.push(Game {
+            uid: uid.to_string(),
+            timezone,
+            lang: language.uigf(),
+            list,
+        });
+    }
+
+    let mut nap = Vec::new();
+
+    for connection in database::zzz::connections::get_by_username(&username, pool.get_ref()).await?
+    {
+        if !connection.verified {
+            continue;
+        }
+
+        let uid = connection.uid;
+        let timezone = Signals::time_zone(uid);
+
+        let mut list = Vec::new();
+
+        for gacha_type in [
+            ZzzGachaType::Standard,
+            ZzzGachaType::Special,
+            ZzzGachaType::WEngine,
+            ZzzGachaType::Bangboo,
+        ] {
+            let signals = match gacha_type {
+                ZzzGachaType::Standard => {
+                    database::zzz::signals::standard::get_by_uid(uid, language, &pool).await?
+                }
+                ZzzGachaType::Special => {
+                    database::zzz::signals::special::get_by_uid(uid, language, &pool).await?
+                }
+                ZzzGachaType::WEngine => {
+                    database::zzz::signals::w_engine::get_by_uid(uid, language, &pool).await?
+                }
+                ZzzGachaType::Bangboo => {
+                    database::zzz::signals::bangboo::get_by_uid(uid, language, &pool).await?
+                }
+            };
+
+            for signal in signals {
+                let (item_id, item_type) = match (signal.character, signal.w_engine, signal.bangboo)
+                {
+                    (Some(character), _, _) => (character, "Agents"),
+                    (_, Some(w_engine), _) => (w_engine, "W-Engines"),
+                    (_, _, Some(bangboo)) => (bangboo, "Bangboo"),
+                    _ => continue,
+                };
+
+                let mut entry = Entry::new(
+                    Signals::uigf_gacha_type(gacha_type),
+                    signal.id,
+                    item_id,
+                    item_type,
+                    signal.name,
+                    signal.rarity,
+                    time(signal.timestamp, timezone),
+                );
+
+                entry.gacha_id = Some(String::new());
+
+                list.push(entry);
+            }
+        }
+
+        list.sort_unstable_by_key(|e| e.id.parse::<i64>().unwrap_or_default());
+
+        nap.push(Game {
+            uid: uid.to_string(),
+            timezone,
+            lang: language.uigf(),
+            list,
+        });
+    }
+
+    let info = Info {
+        export_timestamp: Utc::now().timestamp(),
+        export_app: "stardb".to_string(),
+        export_app_version: env!("CARGO_PKG_VERSION").to_string(),
+        version: "v4.0".to_string(),
+    };
+
+    let uigf = Uigf {
+        info,
+        hk4e,
+        hkrpg,
+        nap,
+    };
+
+    Ok(HttpResponse::Ok().json(uigf))
+}
@@ -43,7 +43,7 @@ async fn put_gi_user_achievement_completed(
     let id = *id;
     let db_complete =
         database::gi::users_achievements_completed::DbUserAchievementCompleted { username, id };
-    database::gi::users_achievements_completed::add(&db_complete, &pool).await?;
+    database::gi::users_achievements_completed::add(&db_complete, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
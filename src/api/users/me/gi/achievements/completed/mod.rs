@@ -81,7 +81,7 @@ async fn put_gi_user_achievements_completed(
     for id in ids.0 {
         complete.id = id;
 
-        let _ = database::gi::users_achievements_completed::add(&complete, &pool).await;
+        let _ = database::gi::users_achievements_completed::add(&complete, pool.get_ref()).await;
     }
 
     Ok(HttpResponse::Ok().finish())
@@ -43,7 +43,7 @@ async fn put_gi_user_achievement_favorites(
     let id = *id;
     let favorite =
         database::gi::users_achievements_favorites::DbUserAchievementFavorite { username, id };
-    database::gi::users_achievements_favorites::add(&favorite, &pool).await?;
+    database::gi::users_achievements_favorites::add(&favorite, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
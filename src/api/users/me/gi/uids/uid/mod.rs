@@ -59,7 +59,7 @@ async fn put_user_gi_uid(
         private: false,
     };
 
-    database::gi::connections::set(&connection, &pool).await?;
+    database::gi::connections::set(&connection, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -40,7 +40,7 @@ async fn put_user_gi_uid_private(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let allowed = database::gi::connections::get_by_username(&username, &pool)
+    let allowed = database::gi::connections::get_by_username(&username, pool.get_ref())
         .await?
         .iter()
         .find(|c| c.uid == *uid)
@@ -76,7 +76,7 @@ async fn delete_user_gi_uid_private(
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let allowed = database::gi::connections::get_by_username(&username, &pool)
+    let allowed = database::gi::connections::get_by_username(&username, pool.get_ref())
         .await?
         .iter()
         .find(|c| c.uid == *uid)
@@ -1,3 +1,5 @@
+mod signed;
+
 use actix_session::Session;
 use actix_web::{put, web, HttpResponse, Responder};
 use serde::Deserialize;
@@ -17,11 +19,13 @@ use crate::{api::ApiResult, database};
 struct ApiDoc;
 
 pub fn openapi() -> utoipa::openapi::OpenApi {
-    ApiDoc::openapi()
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(signed::openapi());
+    openapi
 }
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(import);
+    cfg.service(import).configure(signed::configure);
 }
 
 #[derive(Deserialize, ToSchema)]
@@ -60,7 +64,8 @@ async fn import(
         for &achievement in achievements {
             achievement_completed.id = achievement;
 
-            database::users_achievements_completed::add(&achievement_completed, &pool).await?;
+            database::users_achievements_completed::add(&achievement_completed, pool.get_ref())
+                .await?;
         }
     }
 
@@ -74,7 +79,8 @@ async fn import(
         for &achievement in achievements {
             achievement_completed.id = achievement;
 
-            database::gi::users_achievements_completed::add(&achievement_completed, &pool).await?;
+            database::gi::users_achievements_completed::add(&achievement_completed, pool.get_ref())
+                .await?;
         }
     }
 
@@ -0,0 +1,473 @@
+use actix_session::Session;
+use actix_web::{put, web, HttpResponse, Responder};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use sqlx::{PgConnection, PgPool};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{
+        users::me::export::{self, Gi, Hsr, SignalType, WarpType, WishType, Zzz},
+        ApiResult,
+    },
+    database,
+    gacha::{gi::Wishes, hsr::Warps, zzz::Signals, Gacha},
+    signing::SigningKeys,
+    GachaType, GiGachaType, ZzzGachaType,
+};
+
+// Verified connections only carry over from exports younger than this
+const MAX_VERIFIED_AGE_DAYS: i64 = 7;
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/import/signed")),
+    paths(put_import_signed),
+    components(schemas(
+        SignedImportData,
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(put_import_signed);
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SignedImportData {
+    data: String,
+}
+
+#[utoipa::path(
+    tag = "users/me/import/signed",
+    put,
+    path = "/api/users/me/import/signed",
+    request_body = SignedImportData,
+    responses(
+        (status = 200, description = "Successfully restored. Uids stay verified only for a recent export of this account, or of an account that no longer exists"),
+        (status = 400, description = "Not logged in or invalid signature"),
+    )
+)]
+#[put("/api/users/me/import/signed")]
+async fn put_import_signed(
+    session: Session,
+    import_data: web::Json<SignedImportData>,
+    signing_keys: web::Data<SigningKeys>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let Some((user, _)) = export::verify(&import_data.data, &signing_keys)? else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    // Exports get shared as proof of pulls, so anyone else's copy only restores unverified uids
+    let recent = user
+        .issued_at
+        .is_some_and(|issued_at| Utc::now() - issued_at <= Duration::days(MAX_VERIFIED_AGE_DAYS));
+
+    let verified = recent
+        && (user.username == username
+            || !database::users::exists_by_username(&user.username, &pool).await?);
+
+    let hsr = Restored::<Warps>::try_from(user.hsr)?;
+    let zzz = Restored::<Signals>::try_from(user.zzz)?;
+    let gi = Restored::<Wishes>::try_from(user.gi)?;
+
+    // Registering can call out to the game apis, so it stays outside the transaction
+    register(&hsr, &pool).await?;
+    register(&zzz, &pool).await?;
+    register(&gi, &pool).await?;
+
+    let mut tx = pool.begin().await?;
+
+    restore(&username, hsr, verified, &mut tx).await?;
+    restore(&username, zzz, verified, &mut tx).await?;
+    restore(&username, gi, verified, &mut tx).await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+struct Restored<G: Gacha> {
+    achievements: Vec<i32>,
+    favorites: Vec<i32>,
+    uids: Vec<RestoredUid<G>>,
+}
+
+struct RestoredUid<G: Gacha> {
+    uid: i32,
+    verified: bool,
+    private: bool,
+    set_alls: Vec<(G::GachaType, G::SetAll)>,
+}
+
+/// The per game tables a restore writes besides the pulls
+trait Restore: Gacha {
+    async fn add_completed(username: &str, id: i32, conn: &mut PgConnection) -> Result<()>;
+
+    async fn add_favorite(username: &str, id: i32, conn: &mut PgConnection) -> Result<()>;
+
+    async fn get_verified_uids(username: &str, conn: &mut PgConnection) -> Result<Vec<i32>>;
+
+    async fn set_connection(
+        uid: i32,
+        username: &str,
+        verified: bool,
+        private: bool,
+        conn: &mut PgConnection,
+    ) -> Result<()>;
+}
+
+async fn register<G: Gacha>(restored: &Restored<G>, pool: &PgPool) -> Result<()> {
+    for restored_uid in &restored.uids {
+        G::register(restored_uid.uid, None, pool).await?;
+    }
+
+    Ok(())
+}
+
+async fn restore<G: Restore>(
+    username: &str,
+    restored: Restored<G>,
+    verified: bool,
+    conn: &mut PgConnection,
+) -> Result<()> {
+    for id in restored.achievements {
+        G::add_completed(username, id, conn).await?;
+    }
+
+    for id in restored.favorites {
+        G::add_favorite(username, id, conn).await?;
+    }
+
+    // Don't unverify uids this user already verified
+    let already_verified = G::get_verified_uids(username, conn).await?;
+
+    for restored_uid in restored.uids {
+        let uid = restored_uid.uid;
+
+        G::set_connection(
+            uid,
+            username,
+            verified && restored_uid.verified || already_verified.contains(&uid),
+            restored_uid.private,
+            conn,
+        )
+        .await?;
+
+        for (gacha_type, set_all) in restored_uid.set_alls {
+            G::set_all(gacha_type, &set_all, &mut *conn).await?;
+        }
+    }
+
+    Ok(())
+}
+
+impl TryFrom<Hsr> for Restored<Warps> {
+    type Error = anyhow::Error;
+
+    fn try_from(hsr: Hsr) -> Result<Self> {
+        let mut uids = Vec::new();
+
+        for hsr_uid in hsr.uids {
+            let uid = hsr_uid.uid;
+            let warps = hsr_uid.warps;
+
+            let mut set_alls = Vec::new();
+
+            for (gacha_type, warps) in [
+                (GachaType::Departure, warps.departure),
+                (GachaType::Standard, warps.standard),
+                (GachaType::Special, warps.character),
+                (GachaType::Lc, warps.light_cone),
+            ] {
+                let mut set_all = database::warps::SetAll::default();
+
+                for warp in warps {
+                    let (character, light_cone) = match warp.r#type {
+                        WarpType::Character => (Some(warp.item_id), None),
+                        WarpType::LightCone => (None, Some(warp.item_id)),
+                    };
+
+                    set_all.id.push(warp.id.parse()?);
+                    set_all.uid.push(uid);
+                    set_all.character.push(character);
+                    set_all.light_cone.push(light_cone);
+                    set_all.timestamp.push(warp.timestamp);
+                    set_all.official.push(warp.official);
+                }
+
+                set_alls.push((gacha_type, set_all));
+            }
+
+            uids.push(RestoredUid {
+                uid,
+                verified: hsr_uid.verified,
+                private: hsr_uid.private,
+                set_alls,
+            });
+        }
+
+        Ok(Self {
+            achievements: hsr.achievements,
+            favorites: hsr.favorites,
+            uids,
+        })
+    }
+}
+
+impl TryFrom<Zzz> for Restored<Signals> {
+    type Error = anyhow::Error;
+
+    fn try_from(zzz: Zzz) -> Result<Self> {
+        let mut uids = Vec::new();
+
+        for zzz_uid in zzz.uids {
+            let uid = zzz_uid.uid;
+            let signals = zzz_uid.signals;
+
+            let mut set_alls = Vec::new();
+
+            for (gacha_type, signals) in [
+                (ZzzGachaType::Standard, signals.standard),
+                (ZzzGachaType::Special, signals.character),
+                (ZzzGachaType::WEngine, signals.w_engine),
+                (ZzzGachaType::Bangboo, signals.bangboo),
+            ] {
+                let mut set_all = database::zzz::signals::SetAll::default();
+
+                for signal in signals {
+                    let (character, w_engine, bangboo) = match signal.r#type {
+                        SignalType::Character => (Some(signal.item_id), None, None),
+                        SignalType::WEngine => (None, Some(signal.item_id), None),
+                        SignalType::Bangboo => (None, None, Some(signal.item_id)),
+                    };
+
+                    set_all.id.push(signal.id.parse()?);
+                    set_all.uid.push(uid);
+                    set_all.character.push(character);
+                    set_all.w_engine.push(w_engine);
+                    set_all.bangboo.push(bangboo);
+                    set_all.timestamp.push(signal.timestamp);
+                    set_all.official.push(signal.official);
+                }
+
+                set_alls.push((gacha_type, set_all));
+            }
+
+            uids.push(RestoredUid {
+                uid,
+                verified: zzz_uid.verified,
+                private: zzz_uid.private,
+                set_alls,
+            });
+        }
+
+        Ok(Self {
+            achievements: zzz.achievements,
+            favorites: zzz.favorites,
+            uids,
+        })
+    }
+}
+
+impl TryFrom<Gi> for Restored<Wishes> {
+    type Error = anyhow::Error;
+
+    fn try_from(gi: Gi) -> Result<Self> {
+        let mut uids = Vec::new();
+
+        for gi_uid in gi.uids {
+            let uid = gi_uid.uid;
+            let wishes = gi_uid.wishes;
+
+            let mut set_alls = Vec::new();
+
+            for (gacha_type, wishes) in [
+                (GiGachaType::Beginner, wishes.beginner),
+                (GiGachaType::Standard, wishes.standard),
+                (GiGachaType::Character, wishes.character),
+                (GiGachaType::Weapon, wishes.weapon),
+                (GiGachaType::Chronicled, wishes.chronicled),
+            ] {
+                let mut set_all = database::gi::wishes::SetAll::default();
+
+                for wish in wishes {
+                    let (character, weapon) = match wish.r#type {
+                        WishType::Character => (Some(wish.item_id), None),
+                        WishType::Weapon => (None, Some(wish.item_id)),
+                    };
+
+                    set_all.id.push(wish.id.parse()?);
+                    set_all.uid.push(uid);
+                    set_all.character.push(character);
+                    set_all.weapon.push(weapon);
+                    set_all.timestamp.push(wish.timestamp);
+                    set_all.official.push(wish.official);
+                }
+
+                set_alls.push((gacha_type, set_all));
+            }
+
+            uids.push(RestoredUid {
+                uid,
+                verified: gi_uid.verified,
+                private: gi_uid.private,
+                set_alls,
+            });
+        }
+
+        Ok(Self {
+            achievements: gi.achievements,
+            favorites: gi.favorites,
+            uids,
+        })
+    }
+}
+
+impl Restore for Warps {
+    async fn add_completed(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let achievement = database::users_achievements_completed::DbUserAchievementCompleted {
+            username: username.to_string(),
+            id,
+        };
+
+        database::users_achievements_completed::add(&achievement, conn).await
+    }
+
+    async fn add_favorite(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let favorite = database::users_achievements_favorites::DbUserAchievementFavorite {
+            username: username.to_string(),
+            id,
+        };
+
+        database::users_achievements_favorites::add(&favorite, conn).await
+    }
+
+    async fn get_verified_uids(username: &str, conn: &mut PgConnection) -> Result<Vec<i32>> {
+        Ok(database::connections::get_by_username(username, conn)
+            .await?
+            .into_iter()
+            .filter(|connection| connection.verified)
+            .map(|connection| connection.uid)
+            .collect())
+    }
+
+    async fn set_connection(
+        uid: i32,
+        username: &str,
+        verified: bool,
+        private: bool,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        let connection = database::connections::DbConnection {
+            uid,
+            username: username.to_string(),
+            verified,
+            private,
+        };
+
+        database::connections::set(&connection, conn).await
+    }
+}
+
+impl Restore for Signals {
+    async fn add_completed(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let achievement = database::zzz::users_achievements_completed::DbUserAchievementCompleted {
+            username: username.to_string(),
+            id,
+        };
+
+        database::zzz::users_achievements_completed::add(&achievement, conn).await
+    }
+
+    async fn add_favorite(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let favorite = database::zzz::users_achievements_favorites::DbUserAchievementFavorite {
+            username: username.to_string(),
+            id,
+        };
+
+        database::zzz::users_achievements_favorites::add(&favorite, conn).await
+    }
+
+    async fn get_verified_uids(username: &str, conn: &mut PgConnection) -> Result<Vec<i32>> {
+        Ok(database::zzz::connections::get_by_username(username, conn)
+            .await?
+            .into_iter()
+            .filter(|connection| connection.verified)
+            .map(|connection| connection.uid)
+            .collect())
+    }
+
+    async fn set_connection(
+        uid: i32,
+        username: &str,
+        verified: bool,
+        private: bool,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        let connection = database::zzz::connections::DbConnection {
+            uid,
+            username: username.to_string(),
+            verified,
+            private,
+        };
+
+        database::zzz::connections::set(&connection, conn).await
+    }
+}
+
+impl Restore for Wishes {
+    async fn add_completed(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let achievement = database::gi::users_achievements_completed::DbUserAchievementCompleted {
+            username: username.to_string(),
+            id,
+        };
+
+        database::gi::users_achievements_completed::add(&achievement, conn).await
+    }
+
+    async fn add_favorite(username: &str, id: i32, conn: &mut PgConnection) -> Result<()> {
+        let favorite = database::gi::users_achievements_favorites::DbUserAchievementFavorite {
+            username: username.to_string(),
+            id,
+        };
+
+        database::gi::users_achievements_favorites::add(&favorite, conn).await
+    }
+
+    async fn get_verified_uids(username: &str, conn: &mut PgConnection) -> Result<Vec<i32>> {
+        Ok(database::gi::connections::get_by_username(username, conn)
+            .await?
+            .into_iter()
+            .filter(|connection| connection.verified)
+            .map(|connection| connection.uid)
+            .collect())
+    }
+
+    async fn set_connection(
+        uid: i32,
+        username: &str,
+        verified: bool,
+        private: bool,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        let connection = database::gi::connections::DbConnection {
+            uid,
+            username: username.to_string(),
+            verified,
+            private,
+        };
+
+        database::gi::connections::set(&connection, conn).await
+    }
+}
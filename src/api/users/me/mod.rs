@@ -1,28 +1,41 @@
 mod achievements;
-mod email;
-mod export;
+mod deletion;
+pub mod email;
+pub mod export;
 mod gi;
 mod import;
 mod password;
+mod sessions;
+mod tokens;
+mod two_factor;
 mod uids;
 mod username;
 mod zzz;
 
 use actix_session::Session;
-use actix_web::{get, web, HttpResponse, Responder};
-use serde::Serialize;
+use actix_web::{delete, get, web, HttpResponse, Responder};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
 
-use crate::{api::ApiResult, database};
+use crate::{
+    access_tokens, account_deletion,
+    api::ApiResult,
+    database::{self, user_tokens::UserTokenKind, users_deletions::DbUserDeletion},
+    permissions::{self, Permission, Role},
+};
 
 #[derive(OpenApi)]
 #[openapi(
     tags((name = "users/me")),
-    paths(get_me),
+    paths(get_me, delete_me),
     components(schemas(
         Uid,
         User,
+        AccountDeletion,
+        Role,
+        Permission,
     ))
 )]
 struct ApiDoc;
@@ -30,11 +43,15 @@ struct ApiDoc;
 pub fn openapi() -> utoipa::openapi::OpenApi {
     let mut openapi = ApiDoc::openapi();
     openapi.merge(achievements::openapi());
+    openapi.merge(deletion::openapi());
     openapi.merge(email::openapi());
     openapi.merge(export::openapi());
     openapi.merge(gi::openapi());
     openapi.merge(import::openapi());
     openapi.merge(password::openapi());
+    openapi.merge(sessions::openapi());
+    openapi.merge(tokens::openapi());
+    openapi.merge(two_factor::openapi());
     openapi.merge(uids::openapi());
     openapi.merge(username::openapi());
     openapi.merge(zzz::openapi());
@@ -43,12 +60,17 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(get_me)
+        .service(delete_me)
         .configure(achievements::configure)
+        .configure(deletion::configure)
         .configure(email::configure)
         .configure(export::configure)
         .configure(gi::configure)
         .configure(import::configure)
         .configure(password::configure)
+        .configure(sessions::configure)
+        .configure(tokens::configure)
+        .configure(two_factor::configure)
         .configure(uids::configure)
         .configure(username::configure)
         .configure(zzz::configure);
@@ -64,8 +86,14 @@ pub struct Uid {
 #[derive(Serialize, ToSchema)]
 pub struct User {
     username: String,
+    /// Has any role
     admin: bool,
+    roles: Vec<Role>,
+    permissions: Vec<Permission>,
     email: Option<String>,
+    email_verified: bool,
+    /// When the account gets deleted, unless cancelled
+    deletion_scheduled_at: Option<DateTime<Utc>>,
     uids: Vec<Uid>,
     zzz_uids: Vec<Uid>,
     gi_uids: Vec<Uid>,
@@ -90,13 +118,28 @@ async fn get_me(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Res
 
     //session.renew();
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let roles = permissions::roles(&username, &pool).await?;
+
+    let admin = !roles.is_empty();
+
+    let mut permissions = Vec::new();
+
+    for permission in roles.iter().flat_map(|role| role.permissions()) {
+        if !permissions.contains(permission) {
+            permissions.push(*permission);
+        }
+    }
 
     let user = database::users::get_one_by_username(&username, &pool).await?;
 
     let email = user.email;
+    let email_verified = user.email_verified;
+
+    let deletion_scheduled_at = database::users_deletions::get_one_by_username(&username, &pool)
+        .await?
+        .map(|user_deletion| user_deletion.scheduled_at);
 
-    let uids = database::connections::get_by_username(&username, &pool)
+    let uids = database::connections::get_by_username(&username, pool.get_ref())
         .await?
         .into_iter()
         .map(|c| Uid {
@@ -106,7 +149,7 @@ async fn get_me(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Res
         })
         .collect();
 
-    let zzz_uids = database::zzz::connections::get_by_username(&username, &pool)
+    let zzz_uids = database::zzz::connections::get_by_username(&username, pool.get_ref())
         .await?
         .into_iter()
         .map(|c| Uid {
@@ -116,7 +159,7 @@ async fn get_me(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Res
         })
         .collect();
 
-    let gi_uids = database::gi::connections::get_by_username(&username, &pool)
+    let gi_uids = database::gi::connections::get_by_username(&username, pool.get_ref())
         .await?
         .into_iter()
         .map(|c| Uid {
@@ -142,7 +185,11 @@ async fn get_me(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Res
     let user = User {
         username,
         admin,
+        roles,
+        permissions,
         email,
+        email_verified,
+        deletion_scheduled_at,
         uids,
         zzz_uids,
         gi_uids,
@@ -152,3 +199,93 @@ async fn get_me(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Res
 
     Ok(HttpResponse::Ok().json(user))
 }
+
+#[derive(Deserialize, ToSchema)]
+pub struct AccountDeletion {
+    password: Option<String>,
+    /// Login token from /api/users/auth/request-token, instead of the password
+    token: Option<String>,
+    /// TOTP or recovery code, required with 2FA enabled
+    code: Option<String>,
+    /// Also delete the warps, wishes and signals of uids only you verified
+    #[serde(default)]
+    purge_uids: bool,
+    /// Skip the grace period
+    #[serde(default)]
+    immediate: bool,
+}
+
+#[utoipa::path(
+    tag = "users/me",
+    delete,
+    path = "/api/users/me",
+    request_body = AccountDeletion,
+    responses(
+        (status = 200, description = "Deleted account"),
+        (status = 202, description = "Scheduled the deletion and logged out everywhere. Logging in again and deleting /api/users/me/deletion cancels it", body = DateTime<Utc>),
+        (status = 400, description = "Not logged in or wrong password, token or code"),
+    )
+)]
+#[delete("/api/users/me")]
+async fn delete_me(
+    session: Session,
+    account_deletion: web::Json<AccountDeletion>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let confirmed = match (&account_deletion.password, &account_deletion.token) {
+        (Some(password), _) => {
+            let user = database::users::get_one_by_username(&username, &pool).await?;
+
+            argon2::verify_encoded(&user.password, password.as_bytes()).unwrap_or_default()
+        }
+        (None, Some(token)) => database::user_tokens::consume(
+            &access_tokens::hash(token.trim()),
+            UserTokenKind::Login,
+            &pool,
+        )
+        .await?
+        .is_some_and(|user_token| user_token.username == username),
+        (None, None) => false,
+    };
+
+    if !confirmed {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    if crate::two_factor::enabled(&username, &pool).await? {
+        let Some(code) = &account_deletion.code else {
+            return Ok(HttpResponse::BadRequest().finish());
+        };
+
+        if !crate::two_factor::verify(&username, code, &pool).await? {
+            return Ok(HttpResponse::BadRequest().finish());
+        }
+    }
+
+    if account_deletion.immediate {
+        account_deletion::delete(&username, account_deletion.purge_uids, &pool).await?;
+
+        session.purge();
+
+        return Ok(HttpResponse::Ok().finish());
+    }
+
+    let user_deletion = DbUserDeletion {
+        username: username.clone(),
+        purge_uids: account_deletion.purge_uids,
+        scheduled_at: Utc::now() + Duration::days(account_deletion::GRACE_DAYS),
+    };
+
+    database::users_deletions::set(&user_deletion, &pool).await?;
+
+    database::sessions::delete_by_username(&username, &pool).await?;
+    database::access_tokens::delete_by_username(&username, &pool).await?;
+
+    session.purge();
+
+    Ok(HttpResponse::Accepted().json(user_deletion.scheduled_at))
+}
@@ -5,8 +5,15 @@ use rand::Rng;
 use serde::Deserialize;
 use sqlx::PgPool;
 use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
 
-use crate::{api::ApiResult, database};
+use crate::{
+    api::{users::me::email, ApiResult},
+    database,
+    mailer::{templates, Mailer},
+    pg_session_store::SESSION_UUID,
+    Language,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -29,6 +36,9 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 #[derive(Deserialize, ToSchema)]
 pub struct PasswordUpdate {
     password: String,
+    /// Language of the notification email
+    #[serde(default)]
+    language: Language,
 }
 
 #[utoipa::path(
@@ -37,13 +47,14 @@ pub struct PasswordUpdate {
     path = "/api/users/me/password",
     request_body = PasswordUpdate,
     responses(
-        (status = 200, description = "Updated password"),
+        (status = 200, description = "Updated password. All other sessions and all access tokens are revoked and the verified email is notified"),
     )
 )]
 #[put("/api/users/me/password")]
 async fn put_password(
     session: Session,
     password_update: web::Json<PasswordUpdate>,
+    mailer: web::Data<dyn Mailer>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let Ok(Some(username)) = session.get::<String>("username") else {
@@ -60,5 +71,24 @@ async fn put_password(
 
     database::users::update_password_by_username(&username, &password, &pool).await?;
 
+    let current = session
+        .get::<Uuid>(SESSION_UUID)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    database::sessions::delete_by_username_except_uuid(&username, current, &pool).await?;
+
+    database::access_tokens::delete_by_username(&username, &pool).await?;
+
+    let user = database::users::get_one_by_username(&username, &pool).await?;
+
+    email::notify(
+        &user,
+        |to| templates::password_changed(to, password_update.language, &username),
+        mailer.get_ref(),
+    )
+    .await;
+
     Ok(HttpResponse::Ok().finish())
 }
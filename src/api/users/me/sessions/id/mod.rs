@@ -0,0 +1,101 @@
+use actix_session::Session;
+use actix_web::{delete, put, web, HttpResponse, Responder};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::{api::ApiResult, database, pg_session_store::SESSION_UUID};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/sessions/{id}")),
+    paths(put_session, delete_session),
+    components(schemas(SessionUpdate))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(put_session).service(delete_session);
+}
+
+#[derive(Deserialize, ToSchema)]
+struct SessionUpdate {
+    /// e.g. "Work laptop". Null removes the label
+    label: Option<String>,
+}
+
+#[utoipa::path(
+    tag = "users/me/sessions/{id}",
+    put,
+    path = "/api/users/me/sessions/{id}",
+    request_body = SessionUpdate,
+    responses(
+        (status = 200, description = "Updated label"),
+        (status = 400, description = "Not logged in or label too long"),
+        (status = 404, description = "No such session"),
+    )
+)]
+#[put("/api/users/me/sessions/{id}")]
+async fn put_session(
+    session: Session,
+    id: web::Path<Uuid>,
+    session_update: web::Json<SessionUpdate>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let label = session_update
+        .label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty());
+
+    if label.is_some_and(|label| label.chars().count() > 64) {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    if !database::sessions::update_label_by_id_and_username(*id, &username, label, &pool).await? {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "users/me/sessions/{id}",
+    delete,
+    path = "/api/users/me/sessions/{id}",
+    responses(
+        (status = 200, description = "Revoked session. Revoking the current one logs out"),
+        (status = 400, description = "Not logged in"),
+        (status = 404, description = "No such session"),
+    )
+)]
+#[delete("/api/users/me/sessions/{id}")]
+async fn delete_session(
+    session: Session,
+    id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let Some(uuid) = database::sessions::delete_by_id_and_username(*id, &username, &pool).await?
+    else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+
+    if session.get::<Uuid>(SESSION_UUID).ok().flatten() == Some(uuid) {
+        session.purge();
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,107 @@
+mod id;
+
+use actix_session::Session;
+use actix_web::{delete, get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::{api::ApiResult, database, pg_session_store::SESSION_UUID};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/sessions")),
+    paths(get_sessions, delete_sessions),
+    components(schemas(UserSession))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(id::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_sessions)
+        .service(delete_sessions)
+        .configure(id::configure);
+}
+
+#[derive(Serialize, ToSchema)]
+struct UserSession {
+    #[schema(value_type = String)]
+    id: Uuid,
+    label: Option<String>,
+    user_agent: Option<String>,
+    /// Sha256 of the ip, to tell sessions from different networks apart
+    ip_hash: Option<String>,
+    created_at: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+    expiry: DateTime<Utc>,
+    /// The session making this request
+    current: bool,
+}
+
+#[utoipa::path(
+    tag = "users/me/sessions",
+    get,
+    path = "/api/users/me/sessions",
+    responses(
+        (status = 200, description = "Active sessions, most recently seen first", body = Vec<UserSession>),
+        (status = 400, description = "Not logged in"),
+    )
+)]
+#[get("/api/users/me/sessions")]
+async fn get_sessions(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let current = session.get::<Uuid>(SESSION_UUID).ok().flatten();
+
+    let sessions: Vec<_> = database::sessions::get_by_username(&username, &pool)
+        .await?
+        .into_iter()
+        .map(|db_session| UserSession {
+            id: db_session.id,
+            label: db_session.label,
+            user_agent: db_session.user_agent,
+            ip_hash: db_session.ip_hash,
+            created_at: db_session.created_at,
+            last_seen: db_session.last_seen,
+            expiry: db_session.expiry,
+            current: Some(db_session.uuid) == current,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+#[utoipa::path(
+    tag = "users/me/sessions",
+    delete,
+    path = "/api/users/me/sessions",
+    responses(
+        (status = 200, description = "Revoked all sessions except the current one"),
+        (status = 400, description = "Not logged in"),
+    )
+)]
+#[delete("/api/users/me/sessions")]
+async fn delete_sessions(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let current = session
+        .get::<Uuid>(SESSION_UUID)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    database::sessions::delete_by_username_except_uuid(&username, current, &pool).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,49 @@
+use actix_session::Session;
+use actix_web::{delete, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+use uuid::Uuid;
+
+use crate::{api::ApiResult, database};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/tokens/{id}")),
+    paths(delete_token)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(delete_token);
+}
+
+#[utoipa::path(
+    tag = "users/me/tokens/{id}",
+    delete,
+    path = "/api/users/me/tokens/{id}",
+    responses(
+        (status = 200, description = "Revoked access token"),
+        (status = 400, description = "Not logged in"),
+        (status = 404, description = "No such token"),
+    )
+)]
+#[delete("/api/users/me/tokens/{id}")]
+async fn delete_token(
+    session: Session,
+    id: web::Path<Uuid>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !database::access_tokens::delete_by_id_and_username(*id, &username, &pool).await? {
+        return Ok(HttpResponse::NotFound().finish());
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,151 @@
+mod id;
+
+use actix_session::Session;
+use actix_web::{get, post, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    access_tokens::{self, Scope},
+    api::ApiResult,
+    database,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/tokens")),
+    paths(get_tokens, post_token),
+    components(schemas(AccessToken, NewAccessToken, CreatedAccessToken, Scope))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(id::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_tokens)
+        .service(post_token)
+        .configure(id::configure);
+}
+
+#[derive(Serialize, ToSchema)]
+struct AccessToken {
+    #[schema(value_type = String)]
+    id: Uuid,
+    name: String,
+    scopes: Vec<Scope>,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<database::access_tokens::DbAccessToken> for AccessToken {
+    fn from(access_token: database::access_tokens::DbAccessToken) -> Self {
+        Self {
+            id: access_token.id,
+            name: access_token.name,
+            scopes: access_token
+                .scopes
+                .iter()
+                .filter_map(|scope| scope.parse().ok())
+                .collect(),
+            created_at: access_token.created_at,
+            last_used_at: access_token.last_used_at,
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct NewAccessToken {
+    name: String,
+    scopes: Vec<Scope>,
+}
+
+#[derive(Serialize, ToSchema)]
+struct CreatedAccessToken {
+    #[serde(flatten)]
+    access_token: AccessToken,
+    /// Only shown once
+    token: String,
+}
+
+#[utoipa::path(
+    tag = "users/me/tokens",
+    get,
+    path = "/api/users/me/tokens",
+    responses(
+        (status = 200, description = "Access tokens", body = Vec<AccessToken>),
+        (status = 400, description = "Not logged in"),
+    )
+)]
+#[get("/api/users/me/tokens")]
+async fn get_tokens(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let access_tokens: Vec<AccessToken> =
+        database::access_tokens::get_by_username(&username, &pool)
+            .await?
+            .into_iter()
+            .map(AccessToken::from)
+            .collect();
+
+    Ok(HttpResponse::Ok().json(access_tokens))
+}
+
+#[utoipa::path(
+    tag = "users/me/tokens",
+    post,
+    path = "/api/users/me/tokens",
+    request_body = NewAccessToken,
+    responses(
+        (status = 200, description = "Created access token", body = CreatedAccessToken),
+        (status = 400, description = "Not logged in or no scopes"),
+    )
+)]
+#[post("/api/users/me/tokens")]
+async fn post_token(
+    session: Session,
+    new_access_token: web::Json<NewAccessToken>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if new_access_token.scopes.is_empty() {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let token = access_tokens::generate();
+
+    let mut scopes: Vec<_> = new_access_token
+        .scopes
+        .iter()
+        .map(|scope| scope.to_string())
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+
+    let access_token = database::access_tokens::DbAccessToken {
+        id: Uuid::new_v4(),
+        username,
+        name: new_access_token.name.clone(),
+        scopes,
+        created_at: Utc::now(),
+        last_used_at: None,
+    };
+
+    database::access_tokens::set(&access_token, &access_tokens::hash(&token), &pool).await?;
+
+    Ok(HttpResponse::Ok().json(CreatedAccessToken {
+        access_token: access_token.into(),
+        token,
+    }))
+}
@@ -0,0 +1,230 @@
+use actix_session::Session;
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{api::ApiResult, database, two_factor};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "users/me/2fa")),
+    paths(
+        get_two_factor,
+        post_two_factor,
+        put_two_factor,
+        delete_two_factor,
+        post_recovery_codes
+    ),
+    components(schemas(
+        TwoFactor,
+        TwoFactorSetup,
+        TwoFactorCode,
+    ))
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_two_factor)
+        .service(post_two_factor)
+        .service(put_two_factor)
+        .service(delete_two_factor)
+        .service(post_recovery_codes);
+}
+
+#[derive(Serialize, ToSchema)]
+struct TwoFactor {
+    enabled: bool,
+    /// Unused recovery codes
+    recovery_codes: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+struct TwoFactorSetup {
+    /// Base32 secret for manual entry
+    secret: String,
+    /// otpauth:// URI to render as QR code
+    uri: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+struct TwoFactorCode {
+    /// TOTP code, or a recovery code where noted
+    code: String,
+}
+
+async fn replace_recovery_codes(username: &str, pool: &PgPool) -> Result<Vec<String>> {
+    let codes = two_factor::generate_recovery_codes();
+
+    let hashes: Vec<_> = codes
+        .iter()
+        .map(|code| two_factor::hash_recovery_code(code))
+        .collect();
+
+    database::users_recovery_codes::delete_by_username(username, pool).await?;
+    database::users_recovery_codes::set_all(username, &hashes, pool).await?;
+
+    Ok(codes)
+}
+
+#[utoipa::path(
+    tag = "users/me/2fa",
+    get,
+    path = "/api/users/me/2fa",
+    responses(
+        (status = 200, description = "2FA status", body = TwoFactor),
+        (status = 400, description = "Not logged in"),
+    )
+)]
+#[get("/api/users/me/2fa")]
+async fn get_two_factor(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let enabled = two_factor::enabled(&username, &pool).await?;
+
+    let recovery_codes = if enabled {
+        database::users_recovery_codes::count_unused_by_username(&username, &pool).await?
+    } else {
+        0
+    };
+
+    Ok(HttpResponse::Ok().json(TwoFactor {
+        enabled,
+        recovery_codes,
+    }))
+}
+
+#[utoipa::path(
+    tag = "users/me/2fa",
+    post,
+    path = "/api/users/me/2fa",
+    responses(
+        (status = 200, description = "Started enrolment. Confirm it with a code through PUT", body = TwoFactorSetup),
+        (status = 400, description = "Not logged in"),
+        (status = 409, description = "2FA is already enabled"),
+    )
+)]
+#[post("/api/users/me/2fa")]
+async fn post_two_factor(session: Session, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if two_factor::enabled(&username, &pool).await? {
+        return Ok(HttpResponse::Conflict().finish());
+    }
+
+    let secret = two_factor::generate_secret();
+
+    database::users_totp::set(&username, &secret, &pool).await?;
+
+    let uri = two_factor::uri(&username, &secret);
+
+    Ok(HttpResponse::Ok().json(TwoFactorSetup { secret, uri }))
+}
+
+#[utoipa::path(
+    tag = "users/me/2fa",
+    put,
+    path = "/api/users/me/2fa",
+    request_body = TwoFactorCode,
+    responses(
+        (status = 200, description = "Enabled 2FA. The recovery codes are only shown once", body = Vec<String>),
+        (status = 400, description = "Not logged in, no enrolment or wrong code"),
+        (status = 409, description = "2FA is already enabled"),
+    )
+)]
+#[put("/api/users/me/2fa")]
+async fn put_two_factor(
+    session: Session,
+    two_factor_code: web::Json<TwoFactorCode>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let Some(totp) = database::users_totp::get_one_by_username(&username, &pool).await? else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if totp.enabled {
+        return Ok(HttpResponse::Conflict().finish());
+    }
+
+    let Some(step) = two_factor::verify_code(&totp.secret, two_factor_code.code.trim()) else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    database::users_totp::update_enabled_by_username(&username, step, &pool).await?;
+
+    let codes = replace_recovery_codes(&username, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(codes))
+}
+
+#[utoipa::path(
+    tag = "users/me/2fa",
+    delete,
+    path = "/api/users/me/2fa",
+    request_body(content = TwoFactorCode, description = "TOTP or recovery code"),
+    responses(
+        (status = 200, description = "Disabled 2FA"),
+        (status = 400, description = "Not logged in or wrong code"),
+    )
+)]
+#[delete("/api/users/me/2fa")]
+async fn delete_two_factor(
+    session: Session,
+    two_factor_code: web::Json<TwoFactorCode>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !two_factor::verify(&username, &two_factor_code.code, &pool).await? {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    database::users_totp::delete_by_username(&username, &pool).await?;
+    database::users_recovery_codes::delete_by_username(&username, &pool).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "users/me/2fa",
+    post,
+    path = "/api/users/me/2fa/recovery-codes",
+    request_body(content = TwoFactorCode, description = "TOTP or recovery code"),
+    responses(
+        (status = 200, description = "New recovery codes. The old ones stop working", body = Vec<String>),
+        (status = 400, description = "Not logged in, 2FA disabled or wrong code"),
+    )
+)]
+#[post("/api/users/me/2fa/recovery-codes")]
+async fn post_recovery_codes(
+    session: Session,
+    two_factor_code: web::Json<TwoFactorCode>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    if !two_factor::verify(&username, &two_factor_code.code, &pool).await? {
+        return Ok(HttpResponse::BadRequest().finish());
+    }
+
+    let codes = replace_recovery_codes(&username, &pool).await?;
+
+    Ok(HttpResponse::Ok().json(codes))
+}
@@ -39,7 +39,7 @@ async fn get_user_uids(session: Session, pool: web::Data<PgPool>) -> ApiResult<i
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let uids: Vec<_> = database::connections::get_by_username(&username, &pool)
+    let uids: Vec<_> = database::connections::get_by_username(&username, pool.get_ref())
         .await?
         .iter()
         .map(|c| c.uid)
@@ -86,7 +86,7 @@ async fn put_user_uid(
         database::mihomo::set(&db_mihomo, &pool).await?;
     }
 
-    database::connections::set(&connection, &pool).await?;
+    database::connections::set(&connection, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -43,7 +43,7 @@ async fn put_zzz_user_achievement_completed(
     let id = *id;
     let db_complete =
         database::zzz::users_achievements_completed::DbUserAchievementCompleted { username, id };
-    database::zzz::users_achievements_completed::add(&db_complete, &pool).await?;
+    database::zzz::users_achievements_completed::add(&db_complete, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -81,7 +81,7 @@ async fn put_zzz_user_achievements_completed(
     for id in ids.0 {
         complete.id = id;
 
-        database::zzz::users_achievements_completed::add(&complete, &pool).await?;
+        database::zzz::users_achievements_completed::add(&complete, pool.get_ref()).await?;
     }
 
     Ok(HttpResponse::Ok().finish())
@@ -80,7 +80,7 @@ async fn put_zzz_user_achievements_favorites(
     for id in ids.0 {
         favorite.id = id;
 
-        database::zzz::users_achievements_favorites::add(&favorite, &pool).await?;
+        database::zzz::users_achievements_favorites::add(&favorite, pool.get_ref()).await?;
     }
 
     Ok(HttpResponse::Ok().finish())
@@ -39,7 +39,7 @@ async fn get_user_zzz_uids(session: Session, pool: web::Data<PgPool>) -> ApiResu
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let uids: Vec<_> = database::zzz::connections::get_by_username(&username, &pool)
+    let uids: Vec<_> = database::zzz::connections::get_by_username(&username, pool.get_ref())
         .await?
         .iter()
         .map(|c| c.uid)
@@ -60,7 +60,7 @@ async fn put_user_zzz_uid(
     };
 
     database::zzz::uids::set(&database::zzz::uids::DbUid { uid: *uid }, &pool).await?;
-    database::zzz::connections::set(&connection, &pool).await?;
+    database::zzz::connections::set(&connection, pool.get_ref()).await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -1,9 +1,11 @@
 use actix_web::{get, web, HttpResponse, Responder};
+use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::api::{
-    warps_import::{WarpsImportInfo, WarpsImportInfos},
-    ApiResult,
+use crate::{
+    api::ApiResult,
+    gacha::{self, hsr::Warps, ImportInfo},
+    GachaType,
 };
 
 #[derive(OpenApi)]
@@ -26,19 +28,17 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     get,
     path = "/api/warps-import/{uid}",
     responses(
-        (status = 200, description = "WarpsImportInfo", body = WarpsImportInfo)
+        (status = 200, description = "WarpsImportInfo", body = ImportInfo<GachaType>)
     )
 )]
 #[get("/api/warps-import/{uid}")]
 async fn get_warps_import(
     uid: web::Path<i32>,
-    warps_import_infos: web::Data<WarpsImportInfos>,
+    pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
-    let Some(info) = warps_import_infos.lock().await.get(&*uid).cloned() else {
+    let Some(info) = gacha::get_import_info::<Warps>(*uid, &pool).await? else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let info = info.lock().await.clone();
-
     Ok(HttpResponse::Ok().json(info))
 }
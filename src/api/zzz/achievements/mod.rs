@@ -8,7 +8,9 @@ use utoipa::{OpenApi, ToSchema};
 
 use crate::{
     api::{ApiResult, LanguageParams},
-    database, Difficulty,
+    database,
+    permissions::{self, Permission},
+    Difficulty, Language,
 };
 
 #[derive(OpenApi)]
@@ -74,9 +76,11 @@ impl From<database::zzz::achievements::DbAchievement> for Achievement {
             impossible: db_achievement.impossible,
             set: db_achievement.set,
             related: None,
-            percent: (!db_achievement.impossible)
-                .then_some(db_achievement.percent.unwrap_or_default())
-                .unwrap_or_default(),
+            percent: if db_achievement.impossible {
+                0.0
+            } else {
+                db_achievement.percent.unwrap_or_default()
+            },
             arcade: db_achievement.arcade,
         }
     }
@@ -110,7 +114,7 @@ async fn get_zzz_achievements(
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
     let admin = if let Ok(Some(username)) = session.get::<String>("username") {
-        database::admins::exists(&username, &pool).await?
+        permissions::has(&username, Permission::EditAchievements, &pool).await?
     } else {
         false
     };
@@ -130,8 +134,12 @@ async fn get_zzz_achievements(
     for achievement in &mut achievements {
         if let Some(set) = achievement.set {
             achievement.related = Some(
-                database::zzz::achievements::get_all_related_ids(achievement.id, set, &pool)
-                    .await?,
+                database::zzz::achievements::get_all_related_ids(
+                    achievement.id,
+                    set,
+                    pool.get_ref(),
+                )
+                .await?,
             );
         }
     }
@@ -172,17 +180,21 @@ async fn put_zzz_achievements(
     achievements: web::Json<Vec<UpdateAchievement>>,
     pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
-    let admin = if let Ok(Some(username)) = session.get::<String>("username") {
-        database::admins::exists(&username, &pool).await?
-    } else {
-        false
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::Forbidden().finish());
     };
 
-    if !admin {
+    if !permissions::has(&username, Permission::EditAchievements, &pool).await? {
         return Ok(HttpResponse::Forbidden().finish());
     }
 
     for achievement in achievements.iter() {
+        let before =
+            database::zzz::achievements::get_one_by_id(achievement.id, Language::En, &pool)
+                .await
+                .ok()
+                .map(Achievement::from);
+
         let update_achievement = database::zzz::achievements::DbUpdateAchievement {
             id: achievement.id,
             version: achievement.version.clone(),
@@ -197,6 +209,21 @@ async fn put_zzz_achievements(
         };
 
         database::zzz::achievements::update_achievement_by_id(&update_achievement, &pool).await?;
+
+        let after = database::zzz::achievements::get_one_by_id(achievement.id, Language::En, &pool)
+            .await
+            .ok()
+            .map(Achievement::from);
+
+        database::audit_log::add(
+            &username,
+            "achievement.update",
+            &format!("zzz/achievements/{}", achievement.id),
+            before.map(serde_json::to_value).transpose()?,
+            after.map(serde_json::to_value).transpose()?,
+            &pool,
+        )
+        .await?;
     }
 
     Ok(HttpResponse::Ok().finish())
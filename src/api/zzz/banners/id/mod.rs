@@ -0,0 +1,210 @@
+use actix_session::Session;
+use actix_web::{delete, get, put, rt, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{zzz::banners::ZzzBanner, ApiResult},
+    database,
+    gacha::{self, zzz::Signals},
+    permissions::{self, Permission},
+    ZzzGachaType,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "zzz/banners/{id}")),
+    paths(get_zzz_banner, put_zzz_banner, delete_zzz_banner)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_banner)
+        .service(put_zzz_banner)
+        .service(delete_zzz_banner);
+}
+
+#[utoipa::path(
+    tag = "zzz/banners/{id}",
+    get,
+    path = "/api/zzz/banners/{id}",
+    responses(
+        (status = 200, description = "ZzzBanner", body = ZzzBanner),
+    )
+)]
+#[get("/api/zzz/banners/{id}")]
+async fn get_zzz_banner(id: web::Path<i32>, pool: web::Data<PgPool>) -> ApiResult<impl Responder> {
+    let banner: ZzzBanner = database::zzz::banners::get_by_id(*id, &pool).await?.into();
+
+    Ok(HttpResponse::Ok().json(banner))
+}
+
+#[derive(Deserialize, ToSchema)]
+struct PutZzzBanner {
+    name: String,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    character: Option<i32>,
+    w_engine: Option<i32>,
+}
+
+#[utoipa::path(
+    tag = "zzz/banners/{id}",
+    put,
+    path = "/api/zzz/banners/{id}",
+    responses((status = 201)),
+)]
+#[put("/api/zzz/banners/{id}")]
+async fn put_zzz_banner(
+    session: Session,
+    id: web::Path<i32>,
+    banner: web::Json<PutZzzBanner>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
+
+    if !admin {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let db_banner = database::zzz::banners::DbBanner {
+        id: *id,
+        name: banner.name.clone(),
+        start: banner.start,
+        end: banner.end,
+        character: banner.character,
+        w_engine: banner.w_engine,
+    };
+
+    let before = database::zzz::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(ZzzBanner::from);
+
+    database::zzz::banners::set(&db_banner, &pool).await?;
+
+    let after = database::zzz::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(ZzzBanner::from);
+
+    database::audit_log::add(
+        &username,
+        "banner.update",
+        &format!("zzz/banners/{id}"),
+        before.as_ref().map(serde_json::to_value).transpose()?,
+        after.as_ref().map(serde_json::to_value).transpose()?,
+        &pool,
+    )
+    .await?;
+
+    recalculate(before.iter().chain(after.iter()), pool.get_ref().clone());
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[utoipa::path(
+    tag = "zzz/banners/{id}",
+    delete,
+    path = "/api/zzz/banners/{id}",
+    responses((status = 200)),
+)]
+#[delete("/api/zzz/banners/{id}")]
+async fn delete_zzz_banner(
+    session: Session,
+    id: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let Ok(Some(username)) = session.get::<String>("username") else {
+        return Ok(HttpResponse::BadRequest().finish());
+    };
+
+    let admin = permissions::has(&username, Permission::EditBanners, &pool).await?;
+
+    if !admin {
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
+    let before = database::zzz::banners::get_by_id(*id, &pool)
+        .await
+        .ok()
+        .map(ZzzBanner::from);
+
+    database::zzz::banners::delete_by_id(*id, &pool).await?;
+
+    database::audit_log::add(
+        &username,
+        "banner.delete",
+        &format!("zzz/banners/{id}"),
+        before.as_ref().map(serde_json::to_value).transpose()?,
+        None,
+        &pool,
+    )
+    .await?;
+
+    recalculate(before.iter(), pool.get_ref().clone());
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Recomputes the stored win rates a banner change can affect in the background. A lost 50/50
+/// carries a guarantee forward, so every uid with pulls since the earliest changed start is redone
+fn recalculate<'a>(banners: impl Iterator<Item = &'a ZzzBanner>, pool: PgPool) {
+    let mut special = None;
+    let mut w_engine = None;
+
+    for banner in banners {
+        let start = if banner.character.is_some() {
+            &mut special
+        } else if banner.w_engine.is_some() {
+            &mut w_engine
+        } else {
+            continue;
+        };
+
+        *start = Some(start.map_or(banner.start, |s: DateTime<Utc>| s.min(banner.start)));
+    }
+
+    rt::spawn(async move {
+        for (gacha_type, start) in [
+            (ZzzGachaType::Special, special),
+            (ZzzGachaType::WEngine, w_engine),
+        ] {
+            let Some(start) = start else {
+                continue;
+            };
+
+            let uids = match gacha_type {
+                ZzzGachaType::Special => {
+                    database::zzz::signals::special::get_uids_since(start, &pool).await
+                }
+                _ => database::zzz::signals::w_engine::get_uids_since(start, &pool).await,
+            };
+
+            let uids = match uids {
+                Ok(uids) => uids,
+                Err(e) => {
+                    error!("Getting uids to recalculate failed with {e}");
+
+                    continue;
+                }
+            };
+
+            for uid in uids {
+                if let Err(e) = gacha::update_stats::<Signals>(uid, gacha_type, &pool).await {
+                    error!("Recalculating {uid} failed with {e}");
+                }
+            }
+        }
+    });
+}
@@ -1,5 +1,7 @@
 mod achievements;
+mod banners;
 mod rng_import;
+mod scores;
 mod signals;
 mod signals_import;
 
@@ -13,7 +15,9 @@ struct ApiDoc;
 pub fn openapi() -> utoipa::openapi::OpenApi {
     let mut openapi = ApiDoc::openapi();
     openapi.merge(achievements::openapi());
+    openapi.merge(banners::openapi());
     openapi.merge(rng_import::openapi());
+    openapi.merge(scores::openapi());
     openapi.merge(signals::openapi());
     openapi.merge(signals_import::openapi());
     openapi
@@ -21,7 +25,9 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.configure(achievements::configure)
+        .configure(banners::configure)
         .configure(rng_import::configure)
+        .configure(scores::configure)
         .configure(signals::configure)
         .configure(signals_import::configure);
 }
@@ -3,7 +3,12 @@ use actix_web::{post, web, HttpResponse, Responder};
 use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::{api::ApiResult, database, ZzzGachaType};
+use crate::{
+    api::ApiResult,
+    database,
+    permissions::{self, Permission},
+    ZzzGachaType,
+};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -63,14 +68,14 @@ async fn post_rng_signals_import(
     };
     let uid = uid as i32;
 
-    let admin = database::admins::exists(&username, &pool).await?;
+    let admin = permissions::has(&username, Permission::AccessAllUids, &pool).await?;
 
     if !admin && database::zzz::uids::get_by_uid(uid, &pool).await.is_err() {
         return Ok(HttpResponse::BadRequest().finish());
     }
 
     let allowed = admin
-        || database::zzz::connections::get_by_username(&username, &pool)
+        || database::zzz::connections::get_by_username(&username, pool.get_ref())
             .await?
             .iter()
             .find(|c| c.uid == uid)
@@ -144,16 +149,16 @@ async fn post_rng_signals_import(
 
         match gacha_type {
             ZzzGachaType::Standard => {
-                database::zzz::signals::standard::set_all(&set_all, &pool).await?
+                database::zzz::signals::standard::set_all(&set_all, pool.get_ref()).await?
             }
             ZzzGachaType::Special => {
-                database::zzz::signals::special::set_all(&set_all, &pool).await?
+                database::zzz::signals::special::set_all(&set_all, pool.get_ref()).await?
             }
             ZzzGachaType::WEngine => {
-                database::zzz::signals::w_engine::set_all(&set_all, &pool).await?
+                database::zzz::signals::w_engine::set_all(&set_all, pool.get_ref()).await?
             }
             ZzzGachaType::Bangboo => {
-                database::zzz::signals::bangboo::set_all(&set_all, &pool).await?
+                database::zzz::signals::bangboo::set_all(&set_all, pool.get_ref()).await?
             }
         }
     }
@@ -0,0 +1,97 @@
+mod uid;
+
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{scores::ScoresParams, ApiResult, Region},
+    database,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "zzz/scores/achievements")),
+    paths(get_zzz_scores_achievements),
+    components(schemas(
+        ZzzScoreAchievement
+    ))
+)]
+struct ApiDoc;
+
+#[derive(Serialize, ToSchema)]
+struct ZzzScoreAchievement {
+    global_rank: i64,
+    regional_rank: i64,
+    uid: i32,
+    region: Region,
+    name: String,
+    level: i32,
+    signature: String,
+    avatar_id: i32,
+    achievement_count: i32,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<database::zzz::profiles::DbProfile> for ZzzScoreAchievement {
+    fn from(db_profile: database::zzz::profiles::DbProfile) -> Self {
+        ZzzScoreAchievement {
+            global_rank: db_profile.global_rank.unwrap(),
+            regional_rank: db_profile.regional_rank.unwrap(),
+            uid: db_profile.uid,
+            region: db_profile.region.parse().unwrap(),
+            name: db_profile.name,
+            level: db_profile.level,
+            signature: db_profile.signature,
+            avatar_id: db_profile.avatar_id,
+            achievement_count: db_profile.achievement_count,
+            updated_at: db_profile.updated_at,
+        }
+    }
+}
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(uid::openapi());
+    openapi
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_scores_achievements)
+        .configure(uid::configure);
+}
+
+#[utoipa::path(
+    tag = "zzz/scores/achievements",
+    get,
+    path = "/api/zzz/scores/achievements",
+    params(
+        ScoresParams
+    ),
+    responses(
+        (status = 200, description = "[ZzzScoreAchievement]", body = Vec<ZzzScoreAchievement>),
+    )
+)]
+#[get("/api/zzz/scores/achievements")]
+async fn get_zzz_scores_achievements(
+    scores_params: web::Query<ScoresParams>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let db_profiles = database::zzz::profiles::get(
+        scores_params.region.map(|r| r.to_string()).as_deref(),
+        scores_params.query.as_deref(),
+        scores_params.limit,
+        scores_params.offset,
+        &pool,
+    )
+    .await?;
+
+    let scores: Vec<_> = db_profiles
+        .into_iter()
+        .map(ZzzScoreAchievement::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(scores))
+}
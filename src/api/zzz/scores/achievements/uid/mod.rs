@@ -0,0 +1,66 @@
+use actix_web::{get, put, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::{zzz::scores::achievements::ZzzScoreAchievement, ApiResult},
+    database, enka,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "zzz/scores/achievements/{uid}")),
+    paths(get_zzz_score_achievement, put_zzz_score_achievement)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_score_achievement)
+        .service(put_zzz_score_achievement);
+}
+
+#[utoipa::path(
+    tag = "zzz/scores/achievements/{uid}",
+    get,
+    path = "/api/zzz/scores/achievements/{uid}",
+    responses(
+        (status = 200, description = "ZzzScoreAchievement", body = ZzzScoreAchievement),
+    )
+)]
+#[get("/api/zzz/scores/achievements/{uid}")]
+async fn get_zzz_score_achievement(
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let score: ZzzScoreAchievement = database::zzz::profiles::get_by_uid(*uid, &pool)
+        .await?
+        .into();
+
+    Ok(HttpResponse::Ok().json(score))
+}
+
+#[utoipa::path(
+    tag = "zzz/scores/achievements/{uid}",
+    put,
+    path = "/api/zzz/scores/achievements/{uid}",
+    responses(
+        (status = 200, description = "ZzzScoreAchievement", body = ZzzScoreAchievement),
+    )
+)]
+#[put("/api/zzz/scores/achievements/{uid}")]
+async fn put_zzz_score_achievement(
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    enka::zzz::update_and_get(*uid, &pool).await?;
+
+    let score: ZzzScoreAchievement = database::zzz::profiles::get_by_uid(*uid, &pool)
+        .await?
+        .into();
+
+    Ok(HttpResponse::Ok().json(score))
+}
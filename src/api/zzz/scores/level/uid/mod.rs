@@ -0,0 +1,43 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use sqlx::PgPool;
+use utoipa::OpenApi;
+
+use crate::{
+    api::{zzz::scores::level::ZzzScoreLevel, ApiResult},
+    database,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    tags((name = "zzz/scores/level/{uid}")),
+    paths(get_zzz_score_level)
+)]
+struct ApiDoc;
+
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_zzz_score_level);
+}
+
+#[utoipa::path(
+    tag = "zzz/scores/level/{uid}",
+    get,
+    path = "/api/zzz/scores/level/{uid}",
+    responses(
+        (status = 200, description = "ZzzScoreLevel", body = ZzzScoreLevel),
+    )
+)]
+#[get("/api/zzz/scores/level/{uid}")]
+async fn get_zzz_score_level(
+    uid: web::Path<i32>,
+    pool: web::Data<PgPool>,
+) -> ApiResult<impl Responder> {
+    let score: ZzzScoreLevel = database::zzz::profiles::get_by_uid_by_level(*uid, &pool)
+        .await?
+        .into();
+
+    Ok(HttpResponse::Ok().json(score))
+}
@@ -1,9 +1,11 @@
 use actix_web::{get, web, HttpResponse, Responder};
+use sqlx::PgPool;
 use utoipa::OpenApi;
 
-use crate::api::{
-    zzz::signals_import::{SignalsImportInfo, SignalsImportInfos},
-    ApiResult,
+use crate::{
+    api::ApiResult,
+    gacha::{self, zzz::Signals, ImportInfo},
+    ZzzGachaType,
 };
 
 #[derive(OpenApi)]
@@ -26,19 +28,17 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     get,
     path = "/api/zzz/signals-import/{uid}",
     responses(
-        (status = 200, description = "SignalsImportInfo", body = SignalsImportInfo)
+        (status = 200, description = "SignalsImportInfo", body = ImportInfo<ZzzGachaType>)
     )
 )]
 #[get("/api/zzz/signals-import/{uid}")]
 async fn get_zzz_signals_import(
     uid: web::Path<i32>,
-    signals_import_infos: web::Data<SignalsImportInfos>,
+    pool: web::Data<PgPool>,
 ) -> ApiResult<impl Responder> {
-    let Some(info) = signals_import_infos.lock().await.get(&*uid).cloned() else {
+    let Some(info) = gacha::get_import_info::<Signals>(*uid, &pool).await? else {
         return Ok(HttpResponse::BadRequest().finish());
     };
 
-    let info = info.lock().await.clone();
-
     Ok(HttpResponse::Ok().json(info))
 }
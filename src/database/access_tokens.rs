@@ -0,0 +1,74 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub struct DbAccessToken {
+    pub id: Uuid,
+    pub username: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+pub async fn set(access_token: &DbAccessToken, hash: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/access_tokens/set.sql",
+        access_token.id,
+        access_token.username,
+        access_token.name,
+        hash,
+        &access_token.scopes,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_by_username(username: &str, pool: &PgPool) -> Result<Vec<DbAccessToken>> {
+    Ok(sqlx::query_file_as!(
+        DbAccessToken,
+        "sql/access_tokens/get_by_username.sql",
+        username
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn get_by_hash(hash: &str, pool: &PgPool) -> Result<Option<DbAccessToken>> {
+    Ok(
+        sqlx::query_file_as!(DbAccessToken, "sql/access_tokens/get_by_hash.sql", hash)
+            .fetch_optional(pool)
+            .await?,
+    )
+}
+
+pub async fn update_last_used_at_by_id(id: Uuid, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/access_tokens/update_last_used_at_by_id.sql", id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete_by_id_and_username(id: Uuid, username: &str, pool: &PgPool) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/access_tokens/delete_by_id_and_username.sql",
+        id,
+        username
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        != 0)
+}
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/access_tokens/delete_by_username.sql", username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -36,3 +36,19 @@ pub async fn set_all(
 
     Ok(())
 }
+
+pub struct DbAchievementText {
+    pub id: i32,
+    pub language: String,
+    pub name: String,
+    pub description: String,
+}
+
+pub async fn get_all(pool: &PgPool) -> Result<Vec<DbAchievementText>> {
+    Ok(sqlx::query_as!(
+        DbAchievementText,
+        "SELECT id, language, name, description FROM achievements_text"
+    )
+    .fetch_all(pool)
+    .await?)
+}
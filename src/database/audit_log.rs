@@ -0,0 +1,77 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+
+pub struct DbAuditLog {
+    pub id: i64,
+    pub username: String,
+    pub action: String,
+    pub target: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+pub struct AuditLogFilter {
+    pub username: Option<String>,
+    pub action: Option<String>,
+    pub target: Option<String>,
+    /// Only entries older than this id
+    pub before_id: Option<i64>,
+    pub limit: i64,
+}
+
+pub async fn add(
+    username: &str,
+    action: &str,
+    target: &str,
+    before: Option<Value>,
+    after: Option<Value>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/audit_log/add.sql",
+        username,
+        action,
+        target,
+        before,
+        after,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get(filter: &AuditLogFilter, pool: &PgPool) -> Result<Vec<DbAuditLog>> {
+    Ok(sqlx::query_file_as!(
+        DbAuditLog,
+        "sql/audit_log/get.sql",
+        filter.username,
+        filter.action,
+        filter.target,
+        filter.before_id,
+        filter.limit,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Replaces the username as actor and as target, the only change the log allows
+pub async fn pseudonymise_by_username(
+    username: &str,
+    pseudonym: &str,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/audit_log/pseudonymise_by_username.sql",
+        username,
+        pseudonym,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, Serialize, ToSchema)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogEntryKind {
+    AchievementAdded,
+    AchievementRemoved,
+    AchievementName,
+    AchievementDescription,
+    /// Jades, primogems or polychromes
+    AchievementReward,
+    CharacterAdded,
+    LightConeAdded,
+    WeaponAdded,
+    WEngineAdded,
+}
+
+pub struct DbChangelogEntry {
+    pub changelog: i64,
+    pub kind: String,
+    pub id: i32,
+    pub language: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Default)]
+pub struct SetAll {
+    pub kind: Vec<String>,
+    pub id: Vec<i32>,
+    pub language: Vec<Option<String>>,
+    pub before: Vec<Option<String>>,
+    pub after: Vec<Option<String>>,
+}
+
+pub async fn set_all(changelog: i64, set_all: &SetAll, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/changelog_entries/set_all.sql",
+        changelog,
+        &set_all.kind,
+        &set_all.id,
+        &set_all.language as &[Option<String>],
+        &set_all.before as &[Option<String>],
+        &set_all.after as &[Option<String>],
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_by_changelogs(changelogs: &[i64], pool: &PgPool) -> Result<Vec<DbChangelogEntry>> {
+    Ok(sqlx::query_file_as!(
+        DbChangelogEntry,
+        "sql/changelog_entries/get_by_changelogs.sql",
+        changelogs,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn get_ids_by_game_and_kind(
+    game: &str,
+    kind: ChangelogEntryKind,
+    pool: &PgPool,
+) -> Result<Vec<i32>> {
+    Ok(sqlx::query_file!(
+        "sql/changelog_entries/get_ids_by_game_and_kind.sql",
+        game,
+        kind.to_string(),
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| r.id)
+    .collect())
+}
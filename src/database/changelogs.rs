@@ -0,0 +1,34 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct DbChangelog {
+    pub id: i64,
+    /// Upstream datamine commit
+    pub commit: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub async fn set(game: &str, commit: &str, pool: &PgPool) -> Result<i64> {
+    Ok(sqlx::query_file!("sql/changelogs/set.sql", game, commit)
+        .fetch_one(pool)
+        .await?
+        .id)
+}
+
+pub async fn get_by_game(
+    game: &str,
+    before_id: Option<i64>,
+    limit: i64,
+    pool: &PgPool,
+) -> Result<Vec<DbChangelog>> {
+    Ok(sqlx::query_file_as!(
+        DbChangelog,
+        "sql/changelogs/get_by_game.sql",
+        game,
+        before_id,
+        limit,
+    )
+    .fetch_all(pool)
+    .await?)
+}
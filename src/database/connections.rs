@@ -1,5 +1,5 @@
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 pub struct DbConnection {
     pub uid: i32,
@@ -8,7 +8,7 @@ pub struct DbConnection {
     pub private: bool,
 }
 
-pub async fn set(connection: &DbConnection, pool: &PgPool) -> Result<()> {
+pub async fn set(connection: &DbConnection, executor: impl PgExecutor<'_>) -> Result<()> {
     sqlx::query!(
         "INSERT INTO connections
             (uid, username, verified, private) 
@@ -24,7 +24,7 @@ pub async fn set(connection: &DbConnection, pool: &PgPool) -> Result<()> {
         connection.verified,
         connection.private,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
@@ -52,13 +52,16 @@ pub async fn get_by_uid(uid: i32, pool: &PgPool) -> Result<Vec<DbConnection>> {
     .await?)
 }
 
-pub async fn get_by_username(username: &str, pool: &PgPool) -> Result<Vec<DbConnection>> {
+pub async fn get_by_username(
+    username: &str,
+    executor: impl PgExecutor<'_>,
+) -> Result<Vec<DbConnection>> {
     Ok(sqlx::query_as!(
         DbConnection,
         "SELECT * FROM connections WHERE username = $1",
         username
     )
-    .fetch_all(pool)
+    .fetch_all(executor)
     .await?)
 }
 
@@ -94,3 +97,21 @@ pub async fn update_private_by_uid_and_username(
 
     Ok(())
 }
+
+/// Verified uids that no other user verified
+pub async fn get_exclusively_verified_uids_by_username(
+    username: &str,
+    pool: &PgPool,
+) -> Result<Vec<i32>> {
+    Ok(sqlx::query!(
+        "SELECT uid FROM connections WHERE username = $1 AND verified AND NOT EXISTS (
+            SELECT * FROM connections other WHERE other.uid = connections.uid AND other.username != $1 AND other.verified
+        )",
+        username
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| r.uid)
+    .collect())
+}
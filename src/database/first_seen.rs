@@ -0,0 +1,68 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Clone, Copy, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum FirstSeenKind {
+    Achievement,
+    Character,
+    LightCone,
+    Weapon,
+    WEngine,
+}
+
+pub struct DbVersionChange {
+    pub kind: String,
+    pub id: i32,
+    pub old_version: Option<String>,
+    pub version: Option<String>,
+}
+
+/// Returns the ids that weren't seen before
+pub async fn set_all(
+    game: &str,
+    kind: FirstSeenKind,
+    ids: &[i32],
+    seen_at: Option<DateTime<Utc>>,
+    version: Option<&str>,
+    pool: &PgPool,
+) -> Result<Vec<i32>> {
+    Ok(sqlx::query_file!(
+        "sql/first_seen/set_all.sql",
+        game,
+        kind.to_string(),
+        ids,
+        seen_at,
+        version,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| r.id)
+    .collect())
+}
+
+/// Tags everything seen by the updater with the version whose release it was seen before, `lead_days`
+/// included. Returns what changed
+pub async fn update_versions_by_game(
+    game: &str,
+    lead_days: i32,
+    pool: &PgPool,
+) -> Result<Vec<DbVersionChange>> {
+    Ok(sqlx::query_file!(
+        "sql/first_seen/update_versions_by_game.sql",
+        game,
+        lead_days
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| DbVersionChange {
+        kind: r.kind,
+        id: r.id,
+        old_version: r.old_version,
+        version: r.new_version,
+    })
+    .collect())
+}
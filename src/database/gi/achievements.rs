@@ -1,5 +1,5 @@
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use crate::Language;
 
@@ -70,10 +70,14 @@ pub async fn get_one_by_id(id: i32, language: Language, pool: &PgPool) -> Result
     .await?)
 }
 
-pub async fn get_all_related_ids(id: i32, set: i32, pool: &PgPool) -> Result<Vec<i32>> {
+pub async fn get_all_related_ids(
+    id: i32,
+    set: i32,
+    executor: impl PgExecutor<'_>,
+) -> Result<Vec<i32>> {
     Ok(
         sqlx::query_file!("sql/gi/achievements/get_all_related_ids.sql", id, set)
-            .fetch_all(pool)
+            .fetch_all(executor)
             .await?
             .iter_mut()
             .map(|id| id.id)
@@ -136,6 +140,42 @@ pub async fn update_version_by_id(id: i32, version: Option<&str>, pool: &PgPool)
     Ok(())
 }
 
+/// Moves achievements from one version to another, leaving ones that were changed in the meantime
+pub async fn update_version_where_version_by_ids(
+    ids: &[i32],
+    old_version: Option<&str>,
+    version: Option<&str>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/gi/achievements/update_version_where_version_by_ids.sql",
+        ids,
+        old_version,
+        version
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Fills in the version of achievements that don't have one yet
+pub async fn update_version_where_null_by_ids(
+    ids: &[i32],
+    version: &str,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/gi/achievements/update_version_where_null_by_ids.sql",
+        ids,
+        version
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn update_comment_by_id(id: i32, comment: Option<&str>, pool: &PgPool) -> Result<()> {
     sqlx::query_file!("sql/gi/achievements/update_comment_by_id.sql", id, comment)
         .execute(pool)
@@ -183,3 +223,13 @@ pub async fn update_impossible_by_id(id: i32, impossible: bool, pool: &PgPool) -
 
     Ok(())
 }
+
+/// Id and primogems of every achievement, hidden ones included
+pub async fn get_all_rewards(pool: &PgPool) -> Result<Vec<(i32, i32)>> {
+    Ok(sqlx::query_file!("sql/gi/achievements/get_all_rewards.sql")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| (r.id, r.primogems))
+        .collect())
+}
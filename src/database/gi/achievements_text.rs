@@ -24,3 +24,18 @@ pub async fn set_all(
 
     Ok(())
 }
+
+pub struct DbAchievementText {
+    pub id: i32,
+    pub language: String,
+    pub name: String,
+    pub description: String,
+}
+
+pub async fn get_all(pool: &PgPool) -> Result<Vec<DbAchievementText>> {
+    Ok(
+        sqlx::query_file_as!(DbAchievementText, "sql/gi/achievements_text/get_all.sql")
+            .fetch_all(pool)
+            .await?,
+    )
+}
@@ -23,3 +23,12 @@ pub async fn get_by_paimon_moe_id(id: &str, pool: &PgPool) -> Result<DbCharacter
     .fetch_one(pool)
     .await?)
 }
+
+pub async fn get_all_ids(pool: &PgPool) -> Result<Vec<i32>> {
+    Ok(sqlx::query_file!("sql/gi/characters/get_all_ids.sql")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 
 pub struct DbProfile {
@@ -20,3 +21,97 @@ pub async fn get_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<DbProfile> {
             .await?,
     )
 }
+
+#[derive(Default)]
+pub struct DbProfileScore {
+    pub global_rank: Option<i64>,
+    pub regional_rank: Option<i64>,
+    pub uid: i32,
+    pub region: String,
+    pub name: String,
+    pub level: i32,
+    pub signature: String,
+    pub name_card: i32,
+    pub profile_picture: i32,
+    pub achievement_count: i32,
+    pub timestamp: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn set_enka(profile: &DbProfileScore, pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query_file!(
+        "sql/gi/profiles/set_enka.sql",
+        profile.uid,
+        profile.name,
+        profile.region,
+        profile.level,
+        profile.signature,
+        profile.name_card,
+        profile.profile_picture,
+        profile.achievement_count,
+        profile.timestamp,
+        profile.updated_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_scores(
+    region: Option<&str>,
+    query: Option<&str>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    pool: &PgPool,
+) -> anyhow::Result<Vec<DbProfileScore>> {
+    Ok(sqlx::query_file_as!(
+        DbProfileScore,
+        "sql/gi/profiles/get_scores.sql",
+        region,
+        query,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn count(
+    region: Option<&str>,
+    query: Option<&str>,
+    pool: &PgPool,
+) -> anyhow::Result<i64> {
+    Ok(
+        sqlx::query_file!("sql/gi/profiles/count.sql", region, query)
+            .fetch_one(pool)
+            .await?
+            .count
+            .unwrap_or_default(),
+    )
+}
+
+pub async fn get_score_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<DbProfileScore> {
+    Ok(
+        sqlx::query_file_as!(DbProfileScore, "sql/gi/profiles/get_score_by_uid.sql", uid)
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+pub async fn get_uids(pool: &PgPool) -> anyhow::Result<Vec<i32>> {
+    Ok(sqlx::query_file!("sql/gi/profiles/get_uids.sql")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| r.uid)
+        .collect())
+}
+
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query_file!("sql/gi/profiles/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
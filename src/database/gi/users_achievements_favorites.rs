@@ -1,5 +1,5 @@
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{Acquire, PgPool, Postgres};
 
 use crate::database::gi::achievements::DbAchievement;
 
@@ -8,13 +8,18 @@ pub struct DbUserAchievementFavorite {
     pub id: i32,
 }
 
-pub async fn add(user_achievement: &DbUserAchievementFavorite, pool: &PgPool) -> Result<()> {
+pub async fn add(
+    user_achievement: &DbUserAchievementFavorite,
+    executor: impl Acquire<'_, Database = Postgres>,
+) -> Result<()> {
+    let mut conn = executor.acquire().await?;
+
     sqlx::query_file!(
         "sql/gi/users/achievements/favorites/set.sql",
         user_achievement.username,
         user_achievement.id,
     )
-    .execute(pool)
+    .execute(&mut *conn)
     .await?;
 
     if let Some(set) = sqlx::query_file_as!(
@@ -23,19 +28,19 @@ pub async fn add(user_achievement: &DbUserAchievementFavorite, pool: &PgPool) ->
         user_achievement.id,
         "en",
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await?
     .set
     {
         for related in
-            super::achievements::get_all_related_ids(user_achievement.id, set, pool).await?
+            super::achievements::get_all_related_ids(user_achievement.id, set, &mut *conn).await?
         {
             sqlx::query_file!(
                 "sql/gi/users/achievements/favorites/delete.sql",
                 user_achievement.username,
                 related,
             )
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
         }
     }
@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use crate::Language;
 
 use super::{DbWish, SetAll};
 
-pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
+pub async fn set_all(set_all: &SetAll, executor: impl PgExecutor<'_>) -> anyhow::Result<()> {
     sqlx::query_file!(
         "sql/gi/wishes/beginner/set_all.sql",
         &set_all.id,
@@ -15,7 +15,7 @@ pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
         &set_all.timestamp as &[DateTime<Utc>],
         &set_all.official,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
@@ -38,6 +38,16 @@ pub async fn get_by_uid(
     .await?)
 }
 
+pub async fn get_count_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<i64> {
+    Ok(
+        sqlx::query_file!("sql/gi/wishes/beginner/get_count_by_uid.sql", uid)
+            .fetch_one(pool)
+            .await?
+            .count
+            .unwrap(),
+    )
+}
+
 pub async fn get_earliest_timestamp_by_uid(
     uid: i32,
     pool: &PgPool,
@@ -42,3 +42,12 @@ pub async fn get_uids(pool: &PgPool) -> anyhow::Result<Vec<i32>> {
         .map(|r| r.uid)
         .collect())
 }
+
+/// Deletes all wishes, stats and import jobs of the uid
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query_file!("sql/gi/wishes/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use crate::Language;
 
 use super::{DbWish, DbWishInfo, SetAll};
 
-pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
+pub async fn set_all(set_all: &SetAll, executor: impl PgExecutor<'_>) -> anyhow::Result<()> {
     sqlx::query_file!(
         "sql/gi/wishes/standard/set_all.sql",
         &set_all.id,
@@ -15,7 +15,7 @@ pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
         &set_all.timestamp as &[DateTime<Utc>],
         &set_all.official,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
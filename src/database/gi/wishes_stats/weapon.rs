@@ -8,6 +8,8 @@ pub struct DbWishesStatWeapon {
     pub win_rate: f64,
     pub win_streak: i32,
     pub loss_streak: i32,
+    /// Wishes the stats were calculated from
+    pub count: i32,
 }
 
 pub async fn set(stat: &DbWishesStatWeapon, pool: &PgPool) -> Result<()> {
@@ -19,6 +21,7 @@ pub async fn set(stat: &DbWishesStatWeapon, pool: &PgPool) -> Result<()> {
         stat.win_rate,
         stat.win_streak,
         stat.loss_streak,
+        stat.count,
     )
     .execute(pool)
     .await?;
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct DbImportJob {
+    pub game: String,
+    pub uid: i32,
+    pub url: String,
+    pub ignore_timestamps: bool,
+    pub status: String,
+    pub gacha_type: Option<String>,
+    pub attempts: i32,
+    pub error: Option<String>,
+}
+
+pub async fn set(job: &DbImportJob, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/import_jobs/set.sql",
+        job.game,
+        job.uid,
+        job.url,
+        job.ignore_timestamps,
+        job.status,
+        job.error,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_by_game_and_uid(
+    game: &str,
+    uid: i32,
+    pool: &PgPool,
+) -> Result<Option<DbImportJob>> {
+    Ok(sqlx::query_file_as!(
+        DbImportJob,
+        "sql/import_jobs/get_by_game_and_uid.sql",
+        game,
+        uid,
+    )
+    .fetch_optional(pool)
+    .await?)
+}
+
+pub async fn claim(pool: &PgPool) -> Result<Option<DbImportJob>> {
+    Ok(
+        sqlx::query_file_as!(DbImportJob, "sql/import_jobs/claim.sql")
+            .fetch_optional(pool)
+            .await?,
+    )
+}
+
+pub async fn update_status(
+    game: &str,
+    uid: i32,
+    status: &str,
+    gacha_type: Option<&str>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/import_jobs/update_status.sql",
+        game,
+        uid,
+        status,
+        gacha_type,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn update_error(
+    game: &str,
+    uid: i32,
+    status: &str,
+    error: &str,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!("sql/import_jobs/update_error.sql", game, uid, status, error)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn finish(
+    game: &str,
+    uid: i32,
+    status: &str,
+    error: Option<&str>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!("sql/import_jobs/finish.sql", game, uid, status, error)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn heartbeat(game: &str, uid: i32, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/import_jobs/heartbeat.sql", game, uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct DbImportJobCursor {
+    pub game: String,
+    pub uid: i32,
+    pub gacha_type: String,
+    pub end_id: String,
+    pub latest_timestamp: Option<DateTime<Utc>>,
+    pub count: i32,
+    pub finished: bool,
+}
+
+pub async fn set(cursor: &DbImportJobCursor, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/import_jobs_cursors/set.sql",
+        cursor.game,
+        cursor.uid,
+        cursor.gacha_type,
+        cursor.end_id,
+        cursor.latest_timestamp,
+        cursor.count,
+        cursor.finished,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_by_game_and_uid(
+    game: &str,
+    uid: i32,
+    pool: &PgPool,
+) -> Result<Vec<DbImportJobCursor>> {
+    Ok(sqlx::query_file_as!(
+        DbImportJobCursor,
+        "sql/import_jobs_cursors/get_by_game_and_uid.sql",
+        game,
+        uid,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn delete_by_game_and_uid(game: &str, uid: i32, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/import_jobs_cursors/delete_by_game_and_uid.sql",
+        game,
+        uid,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -96,3 +96,12 @@ pub async fn get_by_id(id: i32, language: Language, pool: &PgPool) -> Result<DbL
     .fetch_one(pool)
     .await?)
 }
+
+pub async fn get_all_ids(pool: &PgPool) -> Result<Vec<i32>> {
+    Ok(sqlx::query!("SELECT id FROM light_cones")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+}
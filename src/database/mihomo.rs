@@ -55,3 +55,11 @@ pub async fn get_all_uids(pool: &PgPool) -> Result<Vec<i32>> {
         .map(|r| r.uid)
         .collect())
 }
+
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/mihomo/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -1,22 +1,35 @@
+pub mod access_tokens;
 pub mod achievement_scores;
 pub mod achievement_series;
 pub mod achievement_series_text;
 pub mod achievements;
 pub mod achievements_percent;
 pub mod achievements_text;
-pub mod admins;
+pub mod audit_log;
 pub mod banners;
+pub mod changelog_entries;
+pub mod changelogs;
 pub mod characters;
 pub mod characters_text;
 pub mod connections;
+pub mod first_seen;
 pub mod gi;
+pub mod import_jobs;
+pub mod import_jobs_cursors;
 pub mod light_cones;
 pub mod light_cones_text;
 pub mod mihomo;
+pub mod rate_limits;
 pub mod sessions;
+pub mod user_tokens;
 pub mod users;
 pub mod users_achievements_completed;
 pub mod users_achievements_favorites;
+pub mod users_deletions;
+pub mod users_recovery_codes;
+pub mod users_roles;
+pub mod users_totp;
+pub mod versions;
 pub mod warps;
 pub mod warps_stats;
 pub mod warps_stats_global;
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct DbRateLimit {
+    pub tokens: f64,
+    pub allowed: bool,
+}
+
+pub async fn take(key: &str, capacity: f64, rate: f64, pool: &PgPool) -> Result<DbRateLimit> {
+    Ok(
+        sqlx::query_file_as!(DbRateLimit, "sql/rate_limits/take.sql", key, capacity, rate)
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+pub async fn delete_stale(pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/rate_limits/delete_stale.sql")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
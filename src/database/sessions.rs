@@ -7,6 +7,23 @@ pub struct DbSession {
     pub uuid: Uuid,
     pub username: String,
     pub expiry: DateTime<Utc>,
+    /// Still waiting for the second factor
+    pub pending: bool,
+    pub user_agent: Option<String>,
+    pub ip_hash: Option<String>,
+    /// Ignored by set and update_by_uuid, which always count as seen
+    pub last_seen: DateTime<Utc>,
+}
+
+pub struct DbSessionInfo {
+    pub id: Uuid,
+    pub uuid: Uuid,
+    pub label: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub expiry: DateTime<Utc>,
 }
 
 pub async fn set(session: &DbSession, pool: &PgPool) -> Result<()> {
@@ -15,6 +32,9 @@ pub async fn set(session: &DbSession, pool: &PgPool) -> Result<()> {
         session.uuid,
         session.username,
         session.expiry,
+        session.pending,
+        session.user_agent,
+        session.ip_hash,
     )
     .execute(pool)
     .await?;
@@ -22,18 +42,41 @@ pub async fn set(session: &DbSession, pool: &PgPool) -> Result<()> {
     Ok(())
 }
 
-pub async fn delete_oldest_by_username(username: &str, pool: &PgPool) -> Result<()> {
-    sqlx::query_file!("sql/sessions/delete_oldest_by_username.sql", username)
-        .execute(pool)
-        .await?;
+/// Returns false if the session doesn't exist (anymore)
+pub async fn update_by_uuid(session: &DbSession, pool: &PgPool) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/sessions/update_by_uuid.sql",
+        session.uuid,
+        session.username,
+        session.expiry,
+        session.pending,
+        session.user_agent,
+        session.ip_hash,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        != 0)
+}
+
+/// Pending and logged in sessions are capped separately, so a login that never passes the second
+/// factor can't push out real sessions
+pub async fn delete_oldest_by_username(username: &str, pending: bool, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/sessions/delete_oldest_by_username.sql",
+        username,
+        pending,
+    )
+    .execute(pool)
+    .await?;
 
     Ok(())
 }
 
-pub async fn get_one_by_uuid(uuid: Uuid, pool: &PgPool) -> Result<DbSession> {
+pub async fn get_one_by_uuid(uuid: Uuid, pool: &PgPool) -> Result<Option<DbSession>> {
     Ok(
         sqlx::query_file_as!(DbSession, "sql/sessions/get_one_by_uuid.sql", uuid)
-            .fetch_one(pool)
+            .fetch_optional(pool)
             .await?,
     )
 }
@@ -53,3 +96,75 @@ pub async fn delete_by_uuid(uuid: Uuid, pool: &PgPool) -> Result<()> {
 
     Ok(())
 }
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/sessions/delete_by_username.sql", username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn update_last_seen_by_uuid(uuid: Uuid, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/sessions/update_last_seen_by_uuid.sql", uuid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_by_username(username: &str, pool: &PgPool) -> Result<Vec<DbSessionInfo>> {
+    Ok(
+        sqlx::query_file_as!(DbSessionInfo, "sql/sessions/get_by_username.sql", username)
+            .fetch_all(pool)
+            .await?,
+    )
+}
+
+pub async fn update_label_by_id_and_username(
+    id: Uuid,
+    username: &str,
+    label: Option<&str>,
+    pool: &PgPool,
+) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/sessions/update_label_by_id_and_username.sql",
+        id,
+        username,
+        label,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        != 0)
+}
+
+/// Returns the session key of the deleted session
+pub async fn delete_by_id_and_username(
+    id: Uuid,
+    username: &str,
+    pool: &PgPool,
+) -> Result<Option<Uuid>> {
+    Ok(
+        sqlx::query_file!("sql/sessions/delete_by_id_and_username.sql", id, username)
+            .fetch_optional(pool)
+            .await?
+            .map(|r| r.uuid),
+    )
+}
+
+pub async fn delete_by_username_except_uuid(
+    username: &str,
+    uuid: Uuid,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/sessions/delete_by_username_except_uuid.sql",
+        username,
+        uuid
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Clone, Copy, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum UserTokenKind {
+    Login,
+    VerifyEmail,
+    ResetPassword,
+}
+
+pub struct DbUserToken {
+    pub username: String,
+    pub kind: UserTokenKind,
+    /// Address the token was sent to for email verification
+    pub email: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct DbConsumedUserToken {
+    pub username: String,
+    pub email: Option<String>,
+}
+
+pub async fn set(user_token: &DbUserToken, hash: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/user_tokens/set.sql",
+        hash,
+        user_token.username,
+        user_token.kind.to_string(),
+        user_token.email,
+        user_token.expires_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Marks the token as used and returns it if it was still valid
+pub async fn consume(
+    hash: &str,
+    kind: UserTokenKind,
+    pool: &PgPool,
+) -> Result<Option<DbConsumedUserToken>> {
+    Ok(sqlx::query_file_as!(
+        DbConsumedUserToken,
+        "sql/user_tokens/consume.sql",
+        hash,
+        kind.to_string(),
+    )
+    .fetch_optional(pool)
+    .await?)
+}
+
+pub async fn exists_active_by_username_and_kind(
+    username: &str,
+    kind: UserTokenKind,
+    pool: &PgPool,
+) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/user_tokens/exists_active_by_username_and_kind.sql",
+        username,
+        kind.to_string(),
+    )
+    .fetch_one(pool)
+    .await?
+    .exists)
+}
+
+pub async fn delete_unused_by_username_and_kind(
+    username: &str,
+    kind: UserTokenKind,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!(
+        "sql/user_tokens/delete_unused_by_username_and_kind.sql",
+        username,
+        kind.to_string(),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_expired(pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/user_tokens/delete_expired.sql")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
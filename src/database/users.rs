@@ -5,6 +5,7 @@ pub struct DbUser {
     pub username: String,
     pub password: String,
     pub email: Option<String>,
+    pub email_verified: bool,
 }
 
 pub async fn set(user: &DbUser, pool: &PgPool) -> Result<()> {
@@ -28,6 +29,15 @@ pub async fn get_one_by_username(username: &str, pool: &PgPool) -> Result<DbUser
     )
 }
 
+pub async fn exists_by_username(username: &str, pool: &PgPool) -> Result<bool> {
+    Ok(
+        sqlx::query_file!("sql/users/exists_by_username.sql", username)
+            .fetch_one(pool)
+            .await?
+            .exists,
+    )
+}
+
 pub async fn update_email_by_username(username: &str, email: &str, pool: &PgPool) -> Result<()> {
     sqlx::query_file!("sql/users/update_email_by_username.sql", username, email,)
         .execute(pool)
@@ -59,3 +69,28 @@ pub async fn delete_email_by_username(username: &str, pool: &PgPool) -> Result<(
 
     Ok(())
 }
+
+/// Only verifies if the address hasn't changed since the link was sent
+pub async fn update_email_verified_by_username_and_email(
+    username: &str,
+    email: &str,
+    pool: &PgPool,
+) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/users/update_email_verified_by_username_and_email.sql",
+        username,
+        email,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        != 0)
+}
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users/delete_by_username.sql", username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
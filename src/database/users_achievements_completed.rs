@@ -1,17 +1,22 @@
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{Acquire, PgPool, Postgres};
 
 pub struct DbUserAchievementCompleted {
     pub username: String,
     pub id: i32,
 }
 
-pub async fn add(user_achievement: &DbUserAchievementCompleted, pool: &PgPool) -> Result<()> {
+pub async fn add(
+    user_achievement: &DbUserAchievementCompleted,
+    executor: impl Acquire<'_, Database = Postgres>,
+) -> Result<()> {
+    let mut conn = executor.acquire().await?;
+
     if sqlx::query!(
         "SELECT impossible FROM achievements WHERE id = $1",
         user_achievement.id
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await?
     .impossible
     {
@@ -23,26 +28,26 @@ pub async fn add(user_achievement: &DbUserAchievementCompleted, pool: &PgPool) -
         user_achievement.username,
         user_achievement.id,
     )
-    .execute(pool)
+    .execute(&mut *conn)
     .await?;
 
     if let Some(set) = sqlx::query!(
         "SELECT set FROM achievements WHERE id = $1",
         user_achievement.id,
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await?
     .set
     {
         for related in
-            super::achievements::get_all_related_ids(user_achievement.id, set, pool).await?
+            super::achievements::get_all_related_ids(user_achievement.id, set, &mut *conn).await?
         {
             sqlx::query!(
                 "DELETE FROM users_achievements_completed WHERE username = $1 AND id = $2",
                 user_achievement.username,
                 related,
             )
-            .execute(pool)
+            .execute(&mut *conn)
             .await?;
         }
     }
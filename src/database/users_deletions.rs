@@ -0,0 +1,51 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct DbUserDeletion {
+    pub username: String,
+    /// Also delete the gacha data of uids only this user verified
+    pub purge_uids: bool,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+pub async fn set(user_deletion: &DbUserDeletion, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/users_deletions/set.sql",
+        user_deletion.username,
+        user_deletion.purge_uids,
+        user_deletion.scheduled_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_one_by_username(username: &str, pool: &PgPool) -> Result<Option<DbUserDeletion>> {
+    Ok(sqlx::query_file_as!(
+        DbUserDeletion,
+        "sql/users_deletions/get_one_by_username.sql",
+        username
+    )
+    .fetch_optional(pool)
+    .await?)
+}
+
+pub async fn get_due(pool: &PgPool) -> Result<Vec<DbUserDeletion>> {
+    Ok(
+        sqlx::query_file_as!(DbUserDeletion, "sql/users_deletions/get_due.sql")
+            .fetch_all(pool)
+            .await?,
+    )
+}
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<bool> {
+    Ok(
+        sqlx::query_file!("sql/users_deletions/delete_by_username.sql", username)
+            .execute(pool)
+            .await?
+            .rows_affected()
+            != 0,
+    )
+}
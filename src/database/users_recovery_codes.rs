@@ -0,0 +1,38 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub async fn set_all(username: &str, hashes: &[String], pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users_recovery_codes/set_all.sql", username, hashes)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn consume(username: &str, hash: &str, pool: &PgPool) -> Result<bool> {
+    Ok(
+        sqlx::query_file!("sql/users_recovery_codes/consume.sql", username, hash)
+            .execute(pool)
+            .await?
+            .rows_affected()
+            != 0,
+    )
+}
+
+pub async fn count_unused_by_username(username: &str, pool: &PgPool) -> Result<i64> {
+    Ok(sqlx::query_file!(
+        "sql/users_recovery_codes/count_unused_by_username.sql",
+        username
+    )
+    .fetch_one(pool)
+    .await?
+    .count)
+}
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users_recovery_codes/delete_by_username.sql", username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
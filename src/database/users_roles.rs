@@ -0,0 +1,31 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub async fn get_by_username(username: &str, pool: &PgPool) -> Result<Vec<String>> {
+    Ok(
+        sqlx::query_file!("sql/users_roles/get_by_username.sql", username)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|r| r.role)
+            .collect(),
+    )
+}
+
+pub async fn add(username: &str, role: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users_roles/add.sql", username, role)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete(username: &str, role: &str, pool: &PgPool) -> Result<bool> {
+    Ok(
+        sqlx::query_file!("sql/users_roles/delete.sql", username, role)
+            .execute(pool)
+            .await?
+            .rows_affected()
+            != 0,
+    )
+}
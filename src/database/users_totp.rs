@@ -0,0 +1,63 @@
+use anyhow::Result;
+use sqlx::PgPool;
+
+pub struct DbUserTotp {
+    pub secret: String,
+    pub enabled: bool,
+}
+
+/// Starts a new enrolment, which stays disabled until a code is confirmed
+pub async fn set(username: &str, secret: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users_totp/set.sql", username, secret)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_one_by_username(username: &str, pool: &PgPool) -> Result<Option<DbUserTotp>> {
+    Ok(sqlx::query_file_as!(
+        DbUserTotp,
+        "sql/users_totp/get_one_by_username.sql",
+        username
+    )
+    .fetch_optional(pool)
+    .await?)
+}
+
+pub async fn update_enabled_by_username(username: &str, step: i64, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/users_totp/update_enabled_by_username.sql",
+        username,
+        step
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// False if the step was already used
+pub async fn update_last_step_by_username(
+    username: &str,
+    step: i64,
+    pool: &PgPool,
+) -> Result<bool> {
+    Ok(sqlx::query_file!(
+        "sql/users_totp/update_last_step_by_username.sql",
+        username,
+        step
+    )
+    .execute(pool)
+    .await?
+    .rows_affected()
+        != 0)
+}
+
+pub async fn delete_by_username(username: &str, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/users_totp/delete_by_username.sql", username)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
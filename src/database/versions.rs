@@ -0,0 +1,50 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+pub struct DbVersion {
+    pub game: String,
+    pub version: String,
+    pub released_at: DateTime<Utc>,
+}
+
+pub async fn get_all(pool: &PgPool) -> Result<Vec<DbVersion>> {
+    Ok(sqlx::query_file_as!(DbVersion, "sql/versions/get_all.sql")
+        .fetch_all(pool)
+        .await?)
+}
+
+/// Latest version released at or before `at`
+pub async fn get_current_by_game(
+    game: &str,
+    at: DateTime<Utc>,
+    pool: &PgPool,
+) -> Result<Option<String>> {
+    Ok(
+        sqlx::query_file!("sql/versions/get_current_by_game.sql", game, at)
+            .fetch_optional(pool)
+            .await?
+            .map(|r| r.version),
+    )
+}
+
+pub async fn set(
+    game: &str,
+    version: &str,
+    released_at: DateTime<Utc>,
+    pool: &PgPool,
+) -> Result<()> {
+    sqlx::query_file!("sql/versions/set.sql", game, version, released_at)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn delete(game: &str, version: &str, pool: &PgPool) -> Result<bool> {
+    Ok(sqlx::query_file!("sql/versions/delete.sql", game, version)
+        .execute(pool)
+        .await?
+        .rows_affected()
+        != 0)
+}
@@ -98,3 +98,12 @@ pub async fn get_light_cones_count_by_uid(
     .fetch_all(pool)
     .await?)
 }
+
+/// Deletes all warps, stats and import jobs of the uid
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/warps/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
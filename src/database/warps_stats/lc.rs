@@ -8,6 +8,13 @@ pub struct DbWarpsStatLc {
     pub win_rate: f64,
     pub win_streak: i32,
     pub loss_streak: i32,
+    pub likelihood_4: f64,
+    pub likelihood_5: f64,
+    pub likelihood_win: f64,
+    pub expected_5: f64,
+    pub actual_5: i32,
+    /// Warps the stats were calculated from
+    pub count: i32,
 }
 
 pub async fn set(stat: &DbWarpsStatLc, pool: &PgPool) -> Result<()> {
@@ -19,6 +26,12 @@ pub async fn set(stat: &DbWarpsStatLc, pool: &PgPool) -> Result<()> {
         stat.win_rate,
         stat.win_streak,
         stat.loss_streak,
+        stat.likelihood_4,
+        stat.likelihood_5,
+        stat.likelihood_win,
+        stat.expected_5,
+        stat.actual_5,
+        stat.count,
     )
     .execute(pool)
     .await?;
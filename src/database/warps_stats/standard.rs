@@ -5,6 +5,12 @@ pub struct DbWarpsStatStandard {
     pub uid: i32,
     pub luck_4: f64,
     pub luck_5: f64,
+    pub likelihood_4: f64,
+    pub likelihood_5: f64,
+    pub expected_5: f64,
+    pub actual_5: i32,
+    /// Warps the stats were calculated from
+    pub count: i32,
 }
 
 pub async fn set(stat: &DbWarpsStatStandard, pool: &PgPool) -> Result<()> {
@@ -13,6 +19,11 @@ pub async fn set(stat: &DbWarpsStatStandard, pool: &PgPool) -> Result<()> {
         stat.uid,
         stat.luck_4,
         stat.luck_5,
+        stat.likelihood_4,
+        stat.likelihood_5,
+        stat.expected_5,
+        stat.actual_5,
+        stat.count,
     )
     .execute(pool)
     .await?;
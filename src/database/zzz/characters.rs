@@ -8,3 +8,12 @@ pub async fn set_all(id: &[i32], rarity: &[i32], pool: &PgPool) -> Result<()> {
 
     Ok(())
 }
+
+pub async fn get_all_ids(pool: &PgPool) -> Result<Vec<i32>> {
+    Ok(sqlx::query_file!("sql/zzz/characters/get_all_ids.sql")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+}
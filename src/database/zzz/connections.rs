@@ -1,5 +1,5 @@
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 pub struct DbConnection {
     pub uid: i32,
@@ -8,7 +8,7 @@ pub struct DbConnection {
     pub private: bool,
 }
 
-pub async fn set(connection: &DbConnection, pool: &PgPool) -> Result<()> {
+pub async fn set(connection: &DbConnection, executor: impl PgExecutor<'_>) -> Result<()> {
     sqlx::query_file!(
         "sql/zzz/connections/set.sql",
         connection.uid,
@@ -16,7 +16,7 @@ pub async fn set(connection: &DbConnection, pool: &PgPool) -> Result<()> {
         connection.verified,
         false,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
@@ -42,13 +42,16 @@ pub async fn get_by_uid(uid: i32, pool: &PgPool) -> Result<Vec<DbConnection>> {
     )
 }
 
-pub async fn get_by_username(username: &str, pool: &PgPool) -> Result<Vec<DbConnection>> {
+pub async fn get_by_username(
+    username: &str,
+    executor: impl PgExecutor<'_>,
+) -> Result<Vec<DbConnection>> {
     Ok(sqlx::query_file_as!(
         DbConnection,
         "sql/zzz/connections/get_by_username.sql",
         username,
     )
-    .fetch_all(pool)
+    .fetch_all(executor)
     .await?)
 }
 
@@ -84,3 +87,19 @@ pub async fn update_private_by_uid_and_username(
 
     Ok(())
 }
+
+/// Verified uids that no other user verified
+pub async fn get_exclusively_verified_uids_by_username(
+    username: &str,
+    pool: &PgPool,
+) -> Result<Vec<i32>> {
+    Ok(sqlx::query_file!(
+        "sql/zzz/connections/get_exclusively_verified_uids_by_username.sql",
+        username
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| r.uid)
+    .collect())
+}
@@ -5,9 +5,11 @@ pub mod achievements_percent;
 pub mod achievements_text;
 pub mod bangboos;
 pub mod bangboos_text;
+pub mod banners;
 pub mod characters;
 pub mod characters_text;
 pub mod connections;
+pub mod profiles;
 pub mod signals;
 pub mod signals_stats;
 pub mod signals_stats_global;
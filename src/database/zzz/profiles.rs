@@ -0,0 +1,119 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(Default)]
+pub struct DbProfile {
+    pub global_rank: Option<i64>,
+    pub regional_rank: Option<i64>,
+    pub uid: i32,
+    pub region: String,
+    pub name: String,
+    pub level: i32,
+    pub signature: String,
+    pub avatar_id: i32,
+    pub achievement_count: i32,
+    pub timestamp: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn set(profile: &DbProfile, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!(
+        "sql/zzz/profiles/set.sql",
+        profile.uid,
+        profile.region,
+        profile.name,
+        profile.level,
+        profile.signature,
+        profile.avatar_id,
+        profile.achievement_count,
+        profile.timestamp,
+        profile.updated_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get(
+    region: Option<&str>,
+    query: Option<&str>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    pool: &PgPool,
+) -> Result<Vec<DbProfile>> {
+    Ok(sqlx::query_file_as!(
+        DbProfile,
+        "sql/zzz/profiles/get.sql",
+        region,
+        query,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn count(region: Option<&str>, query: Option<&str>, pool: &PgPool) -> Result<i64> {
+    Ok(
+        sqlx::query_file!("sql/zzz/profiles/count.sql", region, query)
+            .fetch_one(pool)
+            .await?
+            .count
+            .unwrap_or_default(),
+    )
+}
+
+pub async fn get_by_uid(uid: i32, pool: &PgPool) -> Result<DbProfile> {
+    Ok(
+        sqlx::query_file_as!(DbProfile, "sql/zzz/profiles/get_by_uid.sql", uid)
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+pub async fn get_by_level(
+    region: Option<&str>,
+    query: Option<&str>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    pool: &PgPool,
+) -> Result<Vec<DbProfile>> {
+    Ok(sqlx::query_file_as!(
+        DbProfile,
+        "sql/zzz/profiles/get_by_level.sql",
+        region,
+        query,
+        limit,
+        offset,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+pub async fn get_by_uid_by_level(uid: i32, pool: &PgPool) -> Result<DbProfile> {
+    Ok(
+        sqlx::query_file_as!(DbProfile, "sql/zzz/profiles/get_by_uid_by_level.sql", uid)
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+pub async fn get_achievement_count_by_uid(uid: i32, pool: &PgPool) -> Result<i32> {
+    Ok(
+        sqlx::query_file!("sql/zzz/profiles/get_achievement_count_by_uid.sql", uid)
+            .fetch_optional(pool)
+            .await?
+            .and_then(|r| r.count)
+            .unwrap_or_default() as i32,
+    )
+}
+
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> Result<()> {
+    sqlx::query_file!("sql/zzz/profiles/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
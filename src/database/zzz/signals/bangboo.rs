@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use crate::Language;
 
 use super::{DbSignal, DbSignalInfo, SetAll};
 
-pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
+pub async fn set_all(set_all: &SetAll, executor: impl PgExecutor<'_>) -> anyhow::Result<()> {
     sqlx::query_file!(
         "sql/zzz/signals/bangboo/set_all.sql",
         &set_all.id,
@@ -15,7 +15,7 @@ pub async fn set_all(set_all: &SetAll, pool: &PgPool) -> anyhow::Result<()> {
         &set_all.timestamp as &[DateTime<Utc>],
         &set_all.official,
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
 
     Ok(())
@@ -56,6 +56,19 @@ pub async fn get_count_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<i64> {
     )
 }
 
+pub async fn get_earliest_timestamp_by_uid(
+    uid: i32,
+    pool: &PgPool,
+) -> anyhow::Result<Option<DateTime<Utc>>> {
+    Ok(sqlx::query_file!(
+        "sql/zzz/signals/bangboo/get_earliest_timestamp_by_uid.sql",
+        uid
+    )
+    .fetch_one(pool)
+    .await?
+    .min)
+}
+
 pub async fn delete_unofficial(uid: i32, pool: &PgPool) -> anyhow::Result<()> {
     sqlx::query_file!("sql/zzz/signals/bangboo/delete_unofficial.sql", uid)
         .execute(pool)
@@ -21,6 +21,7 @@ pub struct DbSignalInfo {
     pub character: Option<i32>,
     pub w_engine: Option<i32>,
     pub rarity: Option<i32>,
+    pub timestamp: DateTime<Utc>,
 }
 
 #[derive(Default)]
@@ -42,3 +43,12 @@ pub async fn get_uids(pool: &PgPool) -> anyhow::Result<Vec<i32>> {
         .map(|r| r.uid)
         .collect())
 }
+
+/// Deletes all signals, stats and import jobs of the uid
+pub async fn delete_by_uid(uid: i32, pool: &PgPool) -> anyhow::Result<()> {
+    sqlx::query_file!("sql/zzz/signals/delete_by_uid.sql", uid)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use anyhow::Result;
+
+use crate::{
+    database,
+    gacha::{gi::Wishes, Gacha},
+};
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Enka {
+    pub player_info: PlayerInfo,
+    #[serde(rename = "updated_at")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerInfo {
+    pub nickname: String,
+    pub level: i32,
+    #[serde(default)]
+    pub signature: String,
+    #[serde(default)]
+    pub name_card_id: i32,
+    pub profile_picture: Option<ProfilePicture>,
+    #[serde(default)]
+    pub finish_achievement_num: i32,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilePicture {
+    pub id: Option<i32>,
+    pub avatar_id: Option<i32>,
+}
+
+pub fn region(uid: i32) -> &'static str {
+    match uid / 100000000 % 10 {
+        6 => "na",
+        7 => "eu",
+        8 | 9 => "asia",
+        _ => "cn",
+    }
+}
+
+pub async fn get(uid: i32, pool: &PgPool) -> Result<Value> {
+    match super::read(&format!("enka/gi_{uid}.br"))? {
+        Some(json) => Ok(json),
+        None => update_and_get(uid, pool).await,
+    }
+}
+
+pub fn delete(uid: i32) -> Result<()> {
+    super::remove(&format!("enka/gi_{uid}.br"))
+}
+
+pub async fn update_and_get(uid: i32, pool: &PgPool) -> Result<Value> {
+    let now = Utc::now();
+
+    let mut json = super::fetch(&format!("https://enka.network/api/uid/{uid}?info")).await?;
+    if let Some(o) = json.as_object_mut() {
+        o.insert("updated_at".to_string(), serde_json::to_value(now)?);
+    }
+
+    let enka: Enka = serde_json::from_value(json.clone())?;
+
+    super::write(&format!("enka/gi_{uid}.br"), &json)?;
+
+    let re = Regex::new(r"<[^>]*>")?;
+
+    let player_info = enka.player_info;
+
+    let name = re
+        .replace_all(&player_info.nickname, |_: &Captures| "")
+        .to_string();
+    let region = region(uid).to_string();
+    let level = player_info.level;
+    let signature = re
+        .replace_all(&player_info.signature, |_: &Captures| "")
+        .to_string();
+    let name_card = player_info.name_card_id;
+    let profile_picture = player_info
+        .profile_picture
+        .and_then(|p| p.id.or(p.avatar_id))
+        .unwrap_or_default();
+    let achievement_count = player_info.finish_achievement_num;
+    let updated_at = enka.updated_at;
+    let timestamp = database::gi::profiles::get_score_by_uid(uid, pool)
+        .await
+        .ok()
+        .filter(|p| p.achievement_count == achievement_count)
+        .map(|p| p.timestamp)
+        .unwrap_or(now + chrono::Duration::try_hours(Wishes::time_zone(uid) as i64).unwrap());
+
+    let db_profile = database::gi::profiles::DbProfileScore {
+        uid,
+        region,
+        name,
+        level,
+        signature,
+        name_card,
+        profile_picture,
+        achievement_count,
+        timestamp,
+        updated_at,
+        ..Default::default()
+    };
+
+    database::gi::profiles::set_enka(&db_profile, pool).await?;
+
+    Ok(json)
+}
@@ -0,0 +1,51 @@
+pub mod gi;
+pub mod zzz;
+
+use std::{
+    fs::{self, File},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use anyhow::Result;
+use serde_json::Value;
+
+pub async fn fetch(url: &str) -> Result<Value> {
+    let client = reqwest::Client::new();
+
+    Ok(client
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "stardb")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+fn read(path: &str) -> Result<Option<Value>> {
+    if !PathBuf::from(path).exists() {
+        return Ok(None);
+    }
+
+    let decompressor = brotli::Decompressor::new(File::open(path)?, 4096);
+
+    Ok(Some(serde_json::from_reader(decompressor)?))
+}
+
+fn write(path: &str, json: &Value) -> Result<()> {
+    let file = File::create(path)?;
+
+    let writer = brotli::CompressorWriter::new(file, 4096, 4, 22);
+
+    serde_json::to_writer(writer, json)?;
+
+    Ok(())
+}
+
+fn remove(path: &str) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
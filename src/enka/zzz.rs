@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use anyhow::Result;
+
+use crate::{
+    database,
+    gacha::{zzz::Signals, Gacha},
+};
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub struct Enka {
+    pub player_info: PlayerInfo,
+    #[serde(rename = "updated_at")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub struct PlayerInfo {
+    pub social_detail: SocialDetail,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub struct SocialDetail {
+    pub profile_detail: ProfileDetail,
+    #[serde(default)]
+    pub desc: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProfileDetail {
+    pub nickname: String,
+    pub level: i32,
+    pub avatar_id: i32,
+}
+
+pub fn region(uid: i32) -> &'static str {
+    match uid / 100000000 {
+        10 => "na",
+        15 => "eu",
+        13 | 17 => "asia",
+        _ => "cn",
+    }
+}
+
+pub async fn get(uid: i32, pool: &PgPool) -> Result<Value> {
+    match super::read(&format!("enka/zzz_{uid}.br"))? {
+        Some(json) => Ok(json),
+        None => update_and_get(uid, pool).await,
+    }
+}
+
+pub fn delete(uid: i32) -> Result<()> {
+    super::remove(&format!("enka/zzz_{uid}.br"))
+}
+
+pub async fn update_and_get(uid: i32, pool: &PgPool) -> Result<Value> {
+    let now = Utc::now();
+
+    let mut json = super::fetch(&format!("https://enka.network/api/zzz/uid/{uid}")).await?;
+    if let Some(o) = json.as_object_mut() {
+        o.insert("updated_at".to_string(), serde_json::to_value(now)?);
+    }
+
+    let enka: Enka = serde_json::from_value(json.clone())?;
+
+    super::write(&format!("enka/zzz_{uid}.br"), &json)?;
+
+    let re = Regex::new(r"<[^>]*>")?;
+
+    let profile_detail = enka.player_info.social_detail.profile_detail;
+
+    let name = re
+        .replace_all(&profile_detail.nickname, |_: &Captures| "")
+        .to_string();
+    let region = region(uid).to_string();
+    let level = profile_detail.level;
+    let signature = re
+        .replace_all(&enka.player_info.social_detail.desc, |_: &Captures| "")
+        .to_string();
+    let avatar_id = profile_detail.avatar_id;
+    // Enka has no achievement count for ZZZ, so the verified owner's tracked completions are used
+    let achievement_count =
+        database::zzz::profiles::get_achievement_count_by_uid(uid, pool).await?;
+    let updated_at = enka.updated_at;
+    let timestamp = database::zzz::profiles::get_by_uid(uid, pool)
+        .await
+        .ok()
+        .filter(|p| p.achievement_count == achievement_count)
+        .map(|p| p.timestamp)
+        .unwrap_or(now + chrono::Duration::try_hours(Signals::time_zone(uid) as i64).unwrap());
+
+    database::zzz::uids::set(&database::zzz::uids::DbUid { uid }, pool).await?;
+
+    let db_profile = database::zzz::profiles::DbProfile {
+        uid,
+        region,
+        name,
+        level,
+        signature,
+        avatar_id,
+        achievement_count,
+        timestamp,
+        updated_at,
+        ..Default::default()
+    };
+
+    database::zzz::profiles::set(&db_profile, pool).await?;
+
+    Ok(json)
+}
@@ -0,0 +1,518 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use sqlx::{PgConnection, PgPool};
+
+use super::{
+    model::{Model, Rate},
+    Data, Entry, Featured, Gacha, Outcome, Pity, Pull, Server, Stats,
+};
+use crate::{
+    database, enka,
+    permissions::{self, Permission},
+    GiGachaType,
+};
+
+// A lost 50/50 raises the counter, at this value the next 50/50 is a guaranteed Capturing Radiance
+const RADIANCE_MAX: i32 = 3;
+
+pub struct Wishes;
+
+#[derive(Default, Clone, Copy)]
+pub struct State {
+    pub guarantee: bool,
+    pub radiance: i32,
+    pub fate_points: i32,
+}
+
+// 2.0, Epitomized Path
+fn path() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2021, 7, 20, 22, 0, 0).unwrap()
+}
+
+// 5.0, Capturing Radiance and a single fate point
+fn radiance() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 8, 27, 22, 0, 0).unwrap()
+}
+
+/// The log doesn't record the Epitomized or Chronicled Path course, so we assume it was set on
+/// a featured weapon, respectively a character, and that obtaining one completes it.
+pub fn state(
+    gacha_type: GiGachaType,
+    pulls: &[Pull],
+    featured: Option<&Featured>,
+) -> (Vec<Option<Outcome>>, State) {
+    let mut state = State::default();
+
+    let mut outcomes = Vec::with_capacity(pulls.len());
+
+    let mut last_5 = None;
+
+    for pull in pulls {
+        if pull.rarity != 5 {
+            outcomes.push(None);
+            continue;
+        }
+
+        let outcome = match (gacha_type, featured) {
+            (GiGachaType::Character, Some(featured)) => Some(character(&mut state, pull, featured)),
+            (GiGachaType::Weapon, Some(featured)) => {
+                if last_5.is_some_and(|last_5| !featured.same_banner(last_5, pull.timestamp)) {
+                    state.fate_points = 0;
+                }
+
+                Some(weapon(&mut state, pull, featured))
+            }
+            (GiGachaType::Chronicled, _) => Some(chronicled(&mut state, pull)),
+            _ => None,
+        };
+
+        last_5 = Some(pull.timestamp);
+
+        outcomes.push(outcome);
+    }
+
+    (outcomes, state)
+}
+
+fn character(state: &mut State, pull: &Pull, featured: &Featured) -> Outcome {
+    let radiance = pull.timestamp >= radiance();
+
+    if state.guarantee {
+        state.guarantee = false;
+
+        Outcome::Guarantee
+    } else if featured.contains(pull) {
+        if radiance && state.radiance >= RADIANCE_MAX {
+            state.radiance = 1;
+
+            Outcome::Radiance
+        } else {
+            state.radiance = (state.radiance - 1).max(0);
+
+            Outcome::Win
+        }
+    } else {
+        state.guarantee = true;
+
+        if radiance {
+            state.radiance = (state.radiance + 1).min(RADIANCE_MAX);
+        }
+
+        Outcome::Loss
+    }
+}
+
+fn weapon(state: &mut State, pull: &Pull, featured: &Featured) -> Outcome {
+    let max_fate_points = if pull.timestamp >= radiance() { 1 } else { 2 };
+
+    let outcome = if pull.timestamp >= path()
+        && state.fate_points >= max_fate_points
+        && featured.contains(pull)
+    {
+        state.guarantee = false;
+
+        Outcome::Path
+    } else if state.guarantee {
+        state.guarantee = false;
+
+        Outcome::Guarantee
+    } else if featured.contains(pull) {
+        Outcome::Win
+    } else {
+        state.guarantee = true;
+
+        Outcome::Loss
+    };
+
+    if outcome == Outcome::Loss && pull.timestamp >= path() {
+        state.fate_points = (state.fate_points + 1).min(max_fate_points);
+    } else if outcome != Outcome::Loss {
+        state.fate_points = 0;
+    }
+
+    outcome
+}
+
+fn chronicled(state: &mut State, pull: &Pull) -> Outcome {
+    let charted = pull.item.is_some_and(|item| item >= 10000000);
+
+    if !charted {
+        state.fate_points = 1;
+
+        Outcome::Loss
+    } else if state.fate_points > 0 {
+        state.fate_points = 0;
+
+        Outcome::Path
+    } else {
+        Outcome::Win
+    }
+}
+
+impl Gacha for Wishes {
+    type GachaType = GiGachaType;
+    type SetAll = database::gi::wishes::SetAll;
+
+    const GAME: &'static str = "gi";
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("lang", "en"), ("size", "20")];
+    const STATS: &'static [GiGachaType] = &[
+        GiGachaType::Standard,
+        GiGachaType::Character,
+        GiGachaType::Weapon,
+        GiGachaType::Chronicled,
+    ];
+    const CHARACTER: GiGachaType = GiGachaType::Character;
+    const WEAPON: GiGachaType = GiGachaType::Weapon;
+
+    fn endpoint(server: Server) -> &'static str {
+        match server {
+            Server::Global => {
+                "https://public-operation-hk4e-sg.hoyoverse.com/gacha_info/api/getGachaLog"
+            }
+            Server::Cn => "https://public-operation-hk4e.mihoyo.com/gacha_info/api/getGachaLog",
+        }
+    }
+
+    fn game_biz(_: Server) -> Option<&'static str> {
+        None
+    }
+
+    fn gacha_type_id(gacha_type: GiGachaType) -> i32 {
+        gacha_type.id()
+    }
+
+    fn region_time_zone(data: &Data) -> i32 {
+        match data.region.as_deref() {
+            Some("os_usa") => -5,
+            Some("os_eu") => 1,
+            _ => 8,
+        }
+    }
+
+    fn pity(_: GiGachaType) -> Pity {
+        Pity {
+            rarity_4: 4,
+            rarity_5: 5,
+            skip_first_5: false,
+        }
+    }
+
+    fn model(gacha_type: GiGachaType) -> Option<Model> {
+        match gacha_type {
+            GiGachaType::Beginner => None,
+            GiGachaType::Standard | GiGachaType::Character | GiGachaType::Chronicled => {
+                Some(Model {
+                    rate_4: Rate {
+                        base: 0.051,
+                        soft: 8,
+                        step: 0.51,
+                        hard: 10,
+                    },
+                    rate_5: Rate {
+                        base: 0.006,
+                        soft: 73,
+                        step: 0.06,
+                        hard: 90,
+                    },
+                    win: 0.5,
+                })
+            }
+            GiGachaType::Weapon => Some(Model {
+                rate_4: Rate {
+                    base: 0.06,
+                    soft: 7,
+                    step: 0.6,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.007,
+                    soft: 62,
+                    step: 0.07,
+                    hard: 80,
+                },
+                win: 0.75,
+            }),
+        }
+    }
+
+    fn outcomes(
+        gacha_type: GiGachaType,
+        pulls: &[Pull],
+        _: &Pity,
+        featured: Option<&Featured>,
+    ) -> Vec<Option<Outcome>> {
+        state(gacha_type, pulls, featured).0
+    }
+
+    async fn register(uid: i32, username: Option<String>, pool: &PgPool) -> Result<()> {
+        if enka::gi::update_and_get(uid, pool).await.is_err()
+            && database::gi::profiles::get_by_uid(uid, pool).await.is_err()
+        {
+            let name = String::new();
+
+            database::gi::profiles::set(&database::gi::profiles::DbProfile { uid, name }, pool)
+                .await?;
+        }
+
+        if let Some(username) = username {
+            let connection = database::gi::connections::DbConnection {
+                uid,
+                username,
+                verified: true,
+                private: false,
+            };
+
+            database::gi::connections::set(&connection, pool).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn forbidden(uid: i32, username: Option<String>, pool: &PgPool) -> Result<bool> {
+        let mut forbidden = database::gi::connections::get_by_uid(uid, pool)
+            .await?
+            .iter()
+            .any(|c| c.private);
+
+        if forbidden {
+            if let Some(username) = username {
+                if permissions::has(&username, Permission::AccessAllUids, pool).await? {
+                    forbidden = false;
+                } else if let Ok(connection) =
+                    database::gi::connections::get_by_uid_and_username(uid, &username, pool).await
+                {
+                    forbidden = !connection.verified;
+                }
+            }
+        }
+
+        Ok(forbidden)
+    }
+
+    async fn verified(uid: i32, username: &str, pool: &PgPool) -> Result<bool> {
+        Ok(
+            database::gi::connections::get_by_uid_and_username(uid, username, pool)
+                .await
+                .map(|c| c.verified)
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn get_earliest_timestamp(
+        uid: i32,
+        gacha_type: GiGachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(match gacha_type {
+            GiGachaType::Beginner => {
+                database::gi::wishes::beginner::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Standard => {
+                database::gi::wishes::standard::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Character => {
+                database::gi::wishes::character::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Weapon => {
+                database::gi::wishes::weapon::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Chronicled => {
+                database::gi::wishes::chronicled::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+        })
+    }
+
+    async fn get_latest_timestamp(
+        uid: i32,
+        gacha_type: GiGachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(match gacha_type {
+            GiGachaType::Beginner => {
+                database::gi::wishes::beginner::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Standard => {
+                database::gi::wishes::standard::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Character => {
+                database::gi::wishes::character::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Weapon => {
+                database::gi::wishes::weapon::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GiGachaType::Chronicled => {
+                database::gi::wishes::chronicled::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+        })
+    }
+
+    async fn push(
+        uid: i32,
+        entry: &Entry,
+        timestamp: DateTime<Utc>,
+        official: bool,
+        set_all: &mut database::gi::wishes::SetAll,
+        pool: &PgPool,
+    ) -> Result<()> {
+        let id = entry.id.parse()?;
+
+        let item: i32 = if let Ok(id) = entry.item_id.parse() {
+            id
+        } else if let Ok(id) =
+            database::gi::characters_text::get_id_by_name(&entry.name, pool).await
+        {
+            id
+        } else {
+            database::gi::weapons_text::get_id_by_name(&entry.name, pool).await?
+        };
+
+        let mut character = (entry.item_type == "Character").then_some(item);
+        let mut weapon = (entry.item_type == "Weapon").then_some(item);
+
+        if character.is_none() && weapon.is_none() {
+            if item >= 10000000 {
+                character = Some(item);
+            } else {
+                weapon = Some(item);
+            }
+        }
+
+        set_all.id.push(id);
+        set_all.uid.push(uid);
+        set_all.character.push(character);
+        set_all.weapon.push(weapon);
+        set_all.timestamp.push(timestamp);
+        set_all.official.push(official);
+
+        Ok(())
+    }
+
+    async fn set_all(
+        gacha_type: GiGachaType,
+        set_all: &database::gi::wishes::SetAll,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        match gacha_type {
+            GiGachaType::Beginner => database::gi::wishes::beginner::set_all(set_all, conn).await?,
+            GiGachaType::Standard => database::gi::wishes::standard::set_all(set_all, conn).await?,
+            GiGachaType::Character => {
+                database::gi::wishes::character::set_all(set_all, conn).await?
+            }
+            GiGachaType::Weapon => database::gi::wishes::weapon::set_all(set_all, conn).await?,
+            GiGachaType::Chronicled => {
+                database::gi::wishes::chronicled::set_all(set_all, conn).await?
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_pulls(uid: i32, gacha_type: GiGachaType, pool: &PgPool) -> Result<Vec<Pull>> {
+        let wishes = match gacha_type {
+            GiGachaType::Beginner => return Ok(Vec::new()),
+            GiGachaType::Standard => {
+                database::gi::wishes::standard::get_infos_by_uid(uid, pool).await?
+            }
+            GiGachaType::Character => {
+                database::gi::wishes::character::get_infos_by_uid(uid, pool).await?
+            }
+            GiGachaType::Weapon => {
+                database::gi::wishes::weapon::get_infos_by_uid(uid, pool).await?
+            }
+            GiGachaType::Chronicled => {
+                database::gi::wishes::chronicled::get_infos_by_uid(uid, pool).await?
+            }
+        };
+
+        Ok(wishes
+            .into_iter()
+            .map(|wish| Pull {
+                item: wish.character.or(wish.weapon),
+                rarity: wish.rarity.unwrap(),
+                timestamp: wish.timestamp,
+            })
+            .collect())
+    }
+
+    async fn get_featured(gacha_type: GiGachaType, pool: &PgPool) -> Result<Option<Featured>> {
+        if !matches!(gacha_type, GiGachaType::Character | GiGachaType::Weapon) {
+            return Ok(None);
+        }
+
+        let mut banners: HashMap<_, Vec<_>> = HashMap::new();
+
+        for banner in database::gi::banners::get_all(pool).await? {
+            if let Some(character) = banner.character {
+                banners
+                    .entry(character)
+                    .or_default()
+                    .push(banner.start..banner.end);
+            }
+
+            if let Some(weapon) = banner.weapon {
+                banners
+                    .entry(weapon)
+                    .or_default()
+                    .push(banner.start..banner.end);
+            }
+        }
+
+        Ok(Some(Featured::Banners(banners)))
+    }
+
+    async fn set_stats(
+        uid: i32,
+        gacha_type: GiGachaType,
+        stats: &Stats,
+        pool: &PgPool,
+    ) -> Result<()> {
+        match gacha_type {
+            GiGachaType::Beginner => {}
+            GiGachaType::Standard => {
+                let stat = database::gi::wishes_stats::standard::DbWishesStatStandard {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                };
+                database::gi::wishes_stats::standard::set(&stat, pool).await?;
+            }
+            GiGachaType::Character => {
+                let stat = database::gi::wishes_stats::character::DbWishesStatCharacter {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                    count: stats.count,
+                };
+                database::gi::wishes_stats::character::set(&stat, pool).await?;
+            }
+            GiGachaType::Weapon => {
+                let stat = database::gi::wishes_stats::weapon::DbWishesStatWeapon {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                    count: stats.count,
+                };
+                database::gi::wishes_stats::weapon::set(&stat, pool).await?;
+            }
+            GiGachaType::Chronicled => {
+                let stat = database::gi::wishes_stats::chronicled::DbWishesStatChronicled {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                };
+                database::gi::wishes_stats::chronicled::set(&stat, pool).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+
+use super::{
+    model::{Model, Rate},
+    Entry, Featured, Gacha, Pity, Pull, Server, Stats,
+};
+use crate::{
+    database, mihomo,
+    permissions::{self, Permission},
+    GachaType, Language,
+};
+
+pub struct Warps;
+
+impl Gacha for Warps {
+    type GachaType = GachaType;
+    type SetAll = database::warps::SetAll;
+
+    const GAME: &'static str = "hsr";
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("lang", "en"), ("size", "20")];
+    const STATS: &'static [GachaType] = &[GachaType::Standard, GachaType::Special, GachaType::Lc];
+    const CHARACTER: GachaType = GachaType::Special;
+    const WEAPON: GachaType = GachaType::Lc;
+
+    fn endpoint(server: Server) -> &'static str {
+        match server {
+            Server::Global => {
+                "https://public-operation-hkrpg-sg.hoyoverse.com/common/gacha_record/api/getGachaLog"
+            }
+            Server::Cn => {
+                "https://public-operation-hkrpg.mihoyo.com/common/gacha_record/api/getGachaLog"
+            }
+        }
+    }
+
+    fn game_biz(server: Server) -> Option<&'static str> {
+        Some(match server {
+            Server::Global => "hkrpg_global",
+            Server::Cn => "hkrpg_cn",
+        })
+    }
+
+    fn gacha_type_id(gacha_type: GachaType) -> i32 {
+        gacha_type.id()
+    }
+
+    fn pity(_: GachaType) -> Pity {
+        Pity {
+            rarity_4: 4,
+            rarity_5: 5,
+            skip_first_5: false,
+        }
+    }
+
+    fn model(gacha_type: GachaType) -> Option<Model> {
+        match gacha_type {
+            GachaType::Departure => None,
+            GachaType::Standard | GachaType::Special => Some(Model {
+                rate_4: Rate {
+                    base: 0.051,
+                    soft: 10,
+                    step: 0.0,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.006,
+                    soft: 73,
+                    step: 0.06,
+                    hard: 90,
+                },
+                win: 0.5,
+            }),
+            GachaType::Lc => Some(Model {
+                rate_4: Rate {
+                    base: 0.066,
+                    soft: 10,
+                    step: 0.0,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.008,
+                    soft: 65,
+                    step: 0.07,
+                    hard: 80,
+                },
+                win: 0.75,
+            }),
+        }
+    }
+
+    async fn register(uid: i32, username: Option<String>, pool: &PgPool) -> Result<()> {
+        // Wacky way to update the database in case the uid isn't in there
+        if !database::mihomo::exists(uid, pool).await?
+            && mihomo::get(uid, Language::En, pool).await.is_err()
+        {
+            let region = match uid.to_string().chars().next() {
+                Some('6') => "na",
+                Some('7') => "eu",
+                Some('8') | Some('9') => "asia",
+                _ => "cn",
+            }
+            .to_string();
+
+            let db_mihomo = database::mihomo::DbMihomo {
+                uid,
+                region,
+                ..Default::default()
+            };
+
+            database::mihomo::set(&db_mihomo, pool).await?;
+        }
+
+        if let Some(username) = username {
+            let connection = database::connections::DbConnection {
+                uid,
+                username,
+                verified: true,
+                private: false,
+            };
+
+            database::connections::set(&connection, pool).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn forbidden(uid: i32, username: Option<String>, pool: &PgPool) -> Result<bool> {
+        let mut forbidden = database::connections::get_by_uid(uid, pool)
+            .await?
+            .iter()
+            .any(|c| c.private);
+
+        if forbidden {
+            if let Some(username) = username {
+                if permissions::has(&username, Permission::AccessAllUids, pool).await? {
+                    forbidden = false;
+                } else if let Ok(connection) =
+                    database::connections::get_by_uid_and_username(uid, &username, pool).await
+                {
+                    forbidden = !connection.verified;
+                }
+            }
+        }
+
+        Ok(forbidden)
+    }
+
+    async fn verified(uid: i32, username: &str, pool: &PgPool) -> Result<bool> {
+        Ok(
+            database::connections::get_by_uid_and_username(uid, username, pool)
+                .await
+                .map(|c| c.verified)
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn get_earliest_timestamp(
+        uid: i32,
+        gacha_type: GachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(match gacha_type {
+            GachaType::Departure => {
+                database::warps::departure::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Standard => {
+                database::warps::standard::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Special => {
+                database::warps::special::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Lc => database::warps::lc::get_earliest_timestamp_by_uid(uid, pool).await?,
+        })
+    }
+
+    async fn get_latest_timestamp(
+        uid: i32,
+        gacha_type: GachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(match gacha_type {
+            GachaType::Departure => {
+                database::warps::departure::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Standard => {
+                database::warps::standard::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Special => {
+                database::warps::special::get_latest_timestamp_by_uid(uid, pool).await?
+            }
+            GachaType::Lc => database::warps::lc::get_latest_timestamp_by_uid(uid, pool).await?,
+        })
+    }
+
+    async fn push(
+        uid: i32,
+        entry: &Entry,
+        timestamp: DateTime<Utc>,
+        official: bool,
+        set_all: &mut database::warps::SetAll,
+        _: &PgPool,
+    ) -> Result<()> {
+        let id = entry.id.parse()?;
+
+        let item: i32 = entry.item_id.parse()?;
+
+        let mut character =
+            (entry.item_type == "Character" || entry.item_type == "角色").then_some(item);
+        let mut light_cone =
+            (entry.item_type == "Light Cone" || entry.item_type == "光錐").then_some(item);
+
+        if character.is_none() && light_cone.is_none() {
+            if item >= 20000 {
+                light_cone = Some(item);
+            } else if item <= 10000 {
+                character = Some(item);
+            } else {
+                return Err(anyhow::anyhow!("{} is weird...", entry.item_type));
+            }
+        }
+
+        set_all.id.push(id);
+        set_all.uid.push(uid);
+        set_all.character.push(character);
+        set_all.light_cone.push(light_cone);
+        set_all.timestamp.push(timestamp);
+        set_all.official.push(official);
+
+        Ok(())
+    }
+
+    async fn set_all(
+        gacha_type: GachaType,
+        set_all: &database::warps::SetAll,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        match gacha_type {
+            GachaType::Departure => database::warps::departure::set_all(set_all, conn).await?,
+            GachaType::Standard => database::warps::standard::set_all(set_all, conn).await?,
+            GachaType::Special => database::warps::special::set_all(set_all, conn).await?,
+            GachaType::Lc => database::warps::lc::set_all(set_all, conn).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn get_pulls(uid: i32, gacha_type: GachaType, pool: &PgPool) -> Result<Vec<Pull>> {
+        let warps = match gacha_type {
+            GachaType::Departure => return Ok(Vec::new()),
+            GachaType::Standard => database::warps::standard::get_infos_by_uid(uid, pool).await?,
+            GachaType::Special => database::warps::special::get_infos_by_uid(uid, pool).await?,
+            GachaType::Lc => database::warps::lc::get_infos_by_uid(uid, pool).await?,
+        };
+
+        Ok(warps
+            .into_iter()
+            .map(|warp| Pull {
+                item: warp.character.or(warp.light_cone),
+                rarity: warp.rarity.unwrap(),
+                timestamp: warp.timestamp,
+            })
+            .collect())
+    }
+
+    async fn get_featured(gacha_type: GachaType, pool: &PgPool) -> Result<Option<Featured>> {
+        if !matches!(gacha_type, GachaType::Special | GachaType::Lc) {
+            return Ok(None);
+        }
+
+        let mut banners: HashMap<_, Vec<_>> = HashMap::new();
+
+        for banner in database::banners::get_all(pool).await? {
+            if let Some(character) = banner.character {
+                banners
+                    .entry(character)
+                    .or_default()
+                    .push(banner.start..banner.end);
+            }
+
+            if let Some(light_cone) = banner.light_cone {
+                banners
+                    .entry(light_cone)
+                    .or_default()
+                    .push(banner.start..banner.end);
+            }
+        }
+
+        Ok(Some(Featured::Banners(banners)))
+    }
+
+    async fn set_stats(
+        uid: i32,
+        gacha_type: GachaType,
+        stats: &Stats,
+        pool: &PgPool,
+    ) -> Result<()> {
+        match gacha_type {
+            GachaType::Departure => {}
+            GachaType::Standard => {
+                let stat = database::warps_stats::standard::DbWarpsStatStandard {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                    likelihood_4: stats.likelihood_4,
+                    likelihood_5: stats.likelihood_5,
+                    expected_5: stats.expected_5,
+                    actual_5: stats.actual_5,
+                    count: stats.count,
+                };
+                database::warps_stats::standard::set(&stat, pool).await?;
+            }
+            GachaType::Special => {
+                let stat = database::warps_stats::special::DbWarpsStatSpecial {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                    likelihood_4: stats.likelihood_4,
+                    likelihood_5: stats.likelihood_5,
+                    likelihood_win: stats.likelihood_win,
+                    expected_5: stats.expected_5,
+                    actual_5: stats.actual_5,
+                    count: stats.count,
+                };
+                database::warps_stats::special::set(&stat, pool).await?;
+            }
+            GachaType::Lc => {
+                let stat = database::warps_stats::lc::DbWarpsStatLc {
+                    uid,
+                    luck_4: stats.luck_4,
+                    luck_5: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                    likelihood_4: stats.likelihood_4,
+                    likelihood_5: stats.likelihood_5,
+                    likelihood_win: stats.likelihood_win,
+                    expected_5: stats.expected_5,
+                    actual_5: stats.actual_5,
+                    count: stats.count,
+                };
+                database::warps_stats::lc::set(&stat, pool).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,861 @@
+pub mod gi;
+pub mod hsr;
+pub mod model;
+pub mod simulate;
+pub mod zzz;
+
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{self, Display},
+    hash::Hash,
+    ops::Range,
+    pin::pin,
+    time::Duration,
+};
+
+use actix_web::rt;
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use futures::future::{self, Either};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use strum::IntoEnumIterator;
+use url::Url;
+use utoipa::ToSchema;
+
+use crate::database::{self, import_jobs::DbImportJob, import_jobs_cursors::DbImportJobCursor};
+
+use self::{gi::Wishes, hsr::Warps, model::Model, zzz::Signals};
+
+const WORKERS: usize = 4;
+const MAX_ATTEMPTS: i32 = 3;
+// Well below the 5 minutes after which claim takes over a running job
+const HEARTBEAT: Duration = Duration::from_secs(60);
+// authkey error, authkey timeout and region mismatch
+const AUTHKEY_RETCODES: &[i32] = &[-100, -101, -111];
+
+#[derive(Deserialize)]
+pub struct GachaLog {
+    pub retcode: i32,
+    #[serde(default)]
+    pub message: String,
+    pub data: Option<Data>,
+}
+
+#[derive(Deserialize)]
+pub struct Data {
+    pub list: Vec<Entry>,
+    pub region: Option<String>,
+    pub region_time_zone: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct Entry {
+    pub id: String,
+    pub uid: String,
+    pub item_type: String,
+    #[serde(default)]
+    pub item_id: String,
+    #[serde(default)]
+    pub name: String,
+    pub time: String,
+}
+
+#[derive(Serialize, ToSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pending,
+    Calculating,
+    Finished,
+    Error(String),
+}
+
+#[derive(Serialize, ToSchema, Clone)]
+pub struct ImportInfo<T: Eq + Hash> {
+    pub gacha_type: T,
+    #[serde(flatten)]
+    pub counts: HashMap<T, usize>,
+    pub status: Status,
+}
+
+impl<T: Copy + Eq + Hash + IntoEnumIterator> ImportInfo<T> {
+    fn new(status: Status) -> Self {
+        Self {
+            gacha_type: T::iter().next().unwrap(),
+            counts: T::iter().map(|gacha_type| (gacha_type, 0)).collect(),
+            status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+pub enum Server {
+    #[strum(serialize = "global")]
+    Global,
+    #[strum(serialize = "China")]
+    Cn,
+}
+
+impl Server {
+    fn from_url(url: &Url) -> Option<Self> {
+        if let Some((_, game_biz)) = url.query_pairs().find(|(name, _)| name == "game_biz") {
+            if game_biz.ends_with("_cn") {
+                return Some(Server::Cn);
+            } else if game_biz.ends_with("_global") {
+                return Some(Server::Global);
+            }
+        }
+
+        match url.domain() {
+            Some(domain) if domain.ends_with("mihoyo.com") => Some(Server::Cn),
+            Some(domain) if domain.ends_with("hoyoverse.com") => Some(Server::Global),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AuthkeyError {
+    servers: Vec<Server>,
+    message: String,
+}
+
+impl Display for AuthkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let servers: Vec<_> = self.servers.iter().map(|s| s.to_string()).collect();
+
+        write!(
+            f,
+            "The authkey was rejected by the {} server{} ({}). Make sure the link is recent and from the same game and server as the account.",
+            servers.join(" and "),
+            if servers.len() > 1 { "s" } else { "" },
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for AuthkeyError {}
+
+#[derive(Debug)]
+pub struct NoDataError;
+
+impl Display for NoDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No data. The link has no pulls in any banner.")
+    }
+}
+
+impl std::error::Error for NoDataError {}
+
+pub struct Pull {
+    pub item: Option<i32>,
+    pub rarity: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+pub struct Pity {
+    pub rarity_4: i32,
+    pub rarity_5: i32,
+    pub skip_first_5: bool,
+}
+
+pub enum Featured {
+    Banners(HashMap<i32, Vec<Range<DateTime<Utc>>>>),
+    /// Catalogued banners where one covers the pull, everything outside the standard pool otherwise
+    Standard {
+        banners: HashMap<i32, Vec<Range<DateTime<Utc>>>>,
+        standard: &'static [i32],
+    },
+}
+
+impl Featured {
+    pub fn contains(&self, pull: &Pull) -> bool {
+        let Some(item) = pull.item else {
+            return false;
+        };
+
+        match self {
+            Featured::Banners(banners) => banners
+                .get(&item)
+                .map(|v| v.iter().any(|r| r.contains(&pull.timestamp)))
+                .unwrap_or_default(),
+            Featured::Standard { banners, standard } => {
+                if banners
+                    .values()
+                    .flatten()
+                    .any(|r| r.contains(&pull.timestamp))
+                {
+                    banners
+                        .get(&item)
+                        .map(|v| v.iter().any(|r| r.contains(&pull.timestamp)))
+                        .unwrap_or_default()
+                } else {
+                    !standard.contains(&item)
+                }
+            }
+        }
+    }
+
+    pub fn same_banner(&self, a: DateTime<Utc>, b: DateTime<Utc>) -> bool {
+        match self {
+            Featured::Banners(banners) => banners
+                .values()
+                .flatten()
+                .any(|r| r.contains(&a) && r.contains(&b)),
+            Featured::Standard { .. } => true,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Win,
+    Loss,
+    Guarantee,
+    Radiance,
+    Path,
+}
+
+pub struct Stats {
+    pub luck_4: f64,
+    pub luck_5: f64,
+    pub win_rate: f64,
+    pub win_streak: i32,
+    pub loss_streak: i32,
+    pub likelihood_4: f64,
+    pub likelihood_5: f64,
+    pub likelihood_win: f64,
+    pub expected_5: f64,
+    pub actual_5: i32,
+    pub count: i32,
+}
+
+pub trait Gacha: 'static {
+    type GachaType: Copy + Eq + Hash + Display + Serialize + IntoEnumIterator + 'static;
+    type SetAll: Default;
+
+    const GAME: &'static str;
+    const PARAMS: &'static [(&'static str, &'static str)];
+    const STATS: &'static [Self::GachaType];
+    const CHARACTER: Self::GachaType;
+    const WEAPON: Self::GachaType;
+
+    fn endpoint(server: Server) -> &'static str;
+
+    fn game_biz(server: Server) -> Option<&'static str>;
+
+    fn gacha_type_id(gacha_type: Self::GachaType) -> i32;
+
+    fn uigf_gacha_type(gacha_type: Self::GachaType) -> i32 {
+        Self::gacha_type_id(gacha_type)
+    }
+
+    fn region_time_zone(data: &Data) -> i32 {
+        data.region_time_zone.unwrap_or_default()
+    }
+
+    fn time_zone(uid: i32) -> i32 {
+        match uid.to_string().chars().next() {
+            Some('6') => -5,
+            Some('7') => 1,
+            _ => 8,
+        }
+    }
+
+    fn pity(gacha_type: Self::GachaType) -> Pity;
+
+    fn model(_: Self::GachaType) -> Option<Model> {
+        None
+    }
+
+    fn outcomes(
+        _: Self::GachaType,
+        pulls: &[Pull],
+        pity: &Pity,
+        featured: Option<&Featured>,
+    ) -> Vec<Option<Outcome>> {
+        outcomes(pulls, pity, featured)
+    }
+
+    async fn register(uid: i32, username: Option<String>, pool: &PgPool) -> Result<()>;
+
+    async fn forbidden(uid: i32, username: Option<String>, pool: &PgPool) -> Result<bool>;
+
+    async fn verified(uid: i32, username: &str, pool: &PgPool) -> Result<bool>;
+
+    async fn get_earliest_timestamp(
+        uid: i32,
+        gacha_type: Self::GachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>>;
+
+    async fn get_latest_timestamp(
+        uid: i32,
+        gacha_type: Self::GachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>>;
+
+    async fn push(
+        uid: i32,
+        entry: &Entry,
+        timestamp: DateTime<Utc>,
+        official: bool,
+        set_all: &mut Self::SetAll,
+        pool: &PgPool,
+    ) -> Result<()>;
+
+    async fn set_all(
+        gacha_type: Self::GachaType,
+        set_all: &Self::SetAll,
+        conn: &mut PgConnection,
+    ) -> Result<()>;
+
+    async fn get_pulls(uid: i32, gacha_type: Self::GachaType, pool: &PgPool) -> Result<Vec<Pull>>;
+
+    async fn get_featured(gacha_type: Self::GachaType, pool: &PgPool) -> Result<Option<Featured>>;
+
+    async fn set_stats(
+        uid: i32,
+        gacha_type: Self::GachaType,
+        stats: &Stats,
+        pool: &PgPool,
+    ) -> Result<()>;
+}
+
+pub async fn spawn(pool: PgPool) {
+    for _ in 0..WORKERS {
+        let pool = pool.clone();
+
+        actix::Arbiter::new().spawn(async move {
+            let mut interval = rt::time::interval(Duration::from_secs(1));
+
+            loop {
+                interval.tick().await;
+
+                let job = match database::import_jobs::claim(&pool).await {
+                    Ok(Some(job)) => job,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        error!("Claiming import job failed with {e}");
+
+                        continue;
+                    }
+                };
+
+                if let Err(e) = work(&job, &pool).await {
+                    error!("Import job {} {} failed with {e}", job.game, job.uid);
+                }
+            }
+        });
+    }
+}
+
+async fn work(job: &DbImportJob, pool: &PgPool) -> Result<()> {
+    if job.attempts > MAX_ATTEMPTS {
+        let error = job.error.as_deref().unwrap_or("Too many attempts");
+
+        return database::import_jobs::finish(&job.game, job.uid, "error", Some(error), pool).await;
+    }
+
+    let run = async {
+        match job.game.as_str() {
+            Warps::GAME => run::<Warps>(job, pool).await,
+            Wishes::GAME => run::<Wishes>(job, pool).await,
+            Signals::GAME => run::<Signals>(job, pool).await,
+            game => Err(anyhow::anyhow!("Unknown game {game}")),
+        }
+    };
+
+    // The stats calculation alone can take longer than the claim timeout
+    let result = match future::select(pin!(run), pin!(heartbeat(job, pool))).await {
+        Either::Left((result, _)) | Either::Right((result, _)) => result,
+    };
+
+    match result {
+        Ok(()) => database::import_jobs::finish(&job.game, job.uid, "finished", None, pool).await,
+        Err(e) if job.attempts < MAX_ATTEMPTS => {
+            database::import_jobs::update_error(&job.game, job.uid, "pending", &e.to_string(), pool)
+                .await
+        }
+        Err(e) => {
+            database::import_jobs::finish(&job.game, job.uid, "error", Some(&e.to_string()), pool)
+                .await
+        }
+    }
+}
+
+/// Only returns if updating the job failed
+async fn heartbeat(job: &DbImportJob, pool: &PgPool) -> Result<()> {
+    let mut interval = rt::time::interval(HEARTBEAT);
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        database::import_jobs::heartbeat(&job.game, job.uid, pool).await?;
+    }
+}
+
+async fn run<G: Gacha>(job: &DbImportJob, pool: &PgPool) -> Result<()> {
+    let url = Url::parse(&job.url)?;
+
+    for gacha_type in G::GachaType::iter() {
+        import::<G>(job.uid, &url, job.ignore_timestamps, gacha_type, pool).await?;
+    }
+
+    calculate_stats::<G>(job.uid, pool).await
+}
+
+pub async fn start_import<G: Gacha>(
+    url: &str,
+    ignore_timestamps: bool,
+    username: Option<String>,
+    pool: &PgPool,
+) -> Result<i32> {
+    let url = Url::parse(url)?;
+
+    let query = url.query_pairs().filter(|(name, _)| {
+        matches!(
+            name.to_string().as_str(),
+            "authkey" | "authkey_ver" | "sign_type"
+        )
+    });
+
+    // Never send an authkey to the other region's host when the link names its server
+    let servers = match Server::from_url(&url) {
+        Some(server) => vec![server],
+        None => vec![Server::Global, Server::Cn],
+    };
+
+    let query: Vec<_> = query.collect();
+
+    let mut endpoint = None;
+    let mut rejected = Vec::new();
+    let mut message = String::new();
+
+    for server in servers {
+        let mut url = endpoint_url(G::endpoint(server))?;
+
+        url.query_pairs_mut()
+            .extend_pairs(&query)
+            .extend_pairs(G::PARAMS)
+            .extend_pairs(G::game_biz(server).map(|game_biz| ("game_biz", game_biz)))
+            .finish();
+
+        match first_uid::<G>(&url).await {
+            Ok(uid) => {
+                endpoint = Some((url, uid));
+                break;
+            }
+            Err(e) => match e.downcast::<AuthkeyError>() {
+                Ok(e) => {
+                    rejected.push(server);
+                    message = e.message;
+                }
+                Err(e) => return Err(e),
+            },
+        }
+    }
+
+    let Some((url, uid)) = endpoint else {
+        return Err(AuthkeyError {
+            servers: rejected,
+            message,
+        }
+        .into());
+    };
+
+    let Some(uid) = uid else {
+        return Err(NoDataError.into());
+    };
+
+    G::register(uid, username, pool).await?;
+
+    if let Some(job) = database::import_jobs::get_by_game_and_uid(G::GAME, uid, pool).await? {
+        if matches!(job.status.as_str(), "pending" | "running" | "calculating") {
+            return Ok(uid);
+        }
+    }
+
+    database::import_jobs_cursors::delete_by_game_and_uid(G::GAME, uid, pool).await?;
+
+    let job = DbImportJob {
+        game: G::GAME.to_string(),
+        uid,
+        url: url.to_string(),
+        ignore_timestamps,
+        status: "pending".to_string(),
+        gacha_type: None,
+        attempts: 0,
+        error: None,
+    };
+
+    database::import_jobs::set(&job, pool).await?;
+
+    Ok(uid)
+}
+
+pub async fn get_import_info<G: Gacha>(
+    uid: i32,
+    pool: &PgPool,
+) -> Result<Option<ImportInfo<G::GachaType>>> {
+    let Some(job) = database::import_jobs::get_by_game_and_uid(G::GAME, uid, pool).await? else {
+        return Ok(None);
+    };
+
+    let status = match job.status.as_str() {
+        "calculating" => Status::Calculating,
+        "finished" => Status::Finished,
+        "error" => Status::Error(job.error.unwrap_or_default()),
+        _ => Status::Pending,
+    };
+
+    let mut info = ImportInfo::new(status);
+
+    if let Some(gacha_type) = job.gacha_type.as_deref().and_then(parse_gacha_type) {
+        info.gacha_type = gacha_type;
+    }
+
+    for cursor in database::import_jobs_cursors::get_by_game_and_uid(G::GAME, uid, pool).await? {
+        if let Some(gacha_type) = parse_gacha_type(&cursor.gacha_type) {
+            info.counts.insert(gacha_type, cursor.count as usize);
+        }
+    }
+
+    Ok(Some(info))
+}
+
+async fn first_uid<G: Gacha>(url: &Url) -> Result<Option<i32>> {
+    for gacha_type in G::GachaType::iter() {
+        let mut url = url.clone();
+
+        url.query_pairs_mut()
+            .append_pair("gacha_type", &G::gacha_type_id(gacha_type).to_string());
+
+        if let Some(entry) = fetch(&url, "0").await?.list.first() {
+            return Ok(Some(entry.uid.parse()?));
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_gacha_type<T: Display + IntoEnumIterator>(s: &str) -> Option<T> {
+    T::iter().find(|gacha_type| gacha_type.to_string() == s)
+}
+
+// HOYOVERSE_BASE_URL replaces scheme and host, e.g. to point imports at a local mock server
+fn endpoint_url(endpoint: &str) -> Result<Url> {
+    let url = Url::parse(endpoint)?;
+
+    Ok(match env::var("HOYOVERSE_BASE_URL") {
+        Ok(base_url) => Url::parse(&base_url)?.join(url.path())?,
+        Err(_) => url,
+    })
+}
+
+async fn fetch(url: &Url, end_id: &str) -> Result<Data> {
+    let mut i = 0;
+
+    loop {
+        let response = reqwest::get(format!("{url}&end_id={end_id}")).await?;
+        if let Ok(gacha_log) = response.json::<GachaLog>().await {
+            if let Some(data) = gacha_log.data {
+                return Ok(data);
+            }
+
+            if AUTHKEY_RETCODES.contains(&gacha_log.retcode) {
+                return Err(AuthkeyError {
+                    servers: Vec::new(),
+                    message: gacha_log.message,
+                }
+                .into());
+            }
+        }
+
+        if i > 2 {
+            return Err(anyhow::anyhow!("Unsure"));
+        }
+
+        rt::time::sleep(Duration::from_secs(1)).await;
+
+        i += 1;
+    }
+}
+
+async fn import<G: Gacha>(
+    uid: i32,
+    url: &Url,
+    ignore_timestamps: bool,
+    gacha_type: G::GachaType,
+    pool: &PgPool,
+) -> Result<()> {
+    let game = G::GAME;
+    let name = gacha_type.to_string();
+
+    let cursor = database::import_jobs_cursors::get_by_game_and_uid(game, uid, pool)
+        .await?
+        .into_iter()
+        .find(|cursor| cursor.gacha_type == name);
+
+    let mut cursor = match cursor {
+        Some(cursor) => cursor,
+        None => {
+            let latest_timestamp = if ignore_timestamps {
+                None
+            } else {
+                G::get_latest_timestamp(uid, gacha_type, pool).await?
+            };
+
+            DbImportJobCursor {
+                game: game.to_string(),
+                uid,
+                gacha_type: name.clone(),
+                end_id: "0".to_string(),
+                latest_timestamp,
+                count: 0,
+                finished: false,
+            }
+        }
+    };
+
+    let mut url = url.clone();
+
+    url.query_pairs_mut()
+        .append_pair("gacha_type", &G::gacha_type_id(gacha_type).to_string());
+
+    while !cursor.finished {
+        database::import_jobs::update_status(game, uid, "running", Some(&name), pool).await?;
+
+        let data = fetch(&url, &cursor.end_id).await?;
+
+        if data.list.is_empty() {
+            cursor.finished = true;
+        }
+
+        let tz = FixedOffset::east_opt(3600 * G::region_time_zone(&data)).unwrap();
+
+        let mut set_all = G::SetAll::default();
+
+        for entry in data.list {
+            let timestamp = NaiveDateTime::parse_from_str(&entry.time, "%Y-%m-%d %H:%M:%S")?
+                .and_local_timezone(tz)
+                .unwrap()
+                .to_utc();
+
+            if let Some(latest_timestamp) = cursor.latest_timestamp {
+                if timestamp <= latest_timestamp {
+                    cursor.finished = true;
+                    break;
+                }
+            }
+
+            cursor.end_id.clone_from(&entry.id);
+
+            G::push(uid, &entry, timestamp, true, &mut set_all, pool).await?;
+
+            cursor.count += 1;
+        }
+
+        G::set_all(gacha_type, &set_all, &mut *pool.acquire().await?).await?;
+
+        database::import_jobs_cursors::set(&cursor, pool).await?;
+    }
+
+    Ok(())
+}
+
+async fn calculate_stats<G: Gacha>(uid: i32, pool: &PgPool) -> Result<()> {
+    for &gacha_type in G::STATS {
+        let name = gacha_type.to_string();
+
+        database::import_jobs::update_status(G::GAME, uid, "calculating", Some(&name), pool)
+            .await?;
+
+        update_stats::<G>(uid, gacha_type, pool).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_stats<G: Gacha>(
+    uid: i32,
+    gacha_type: G::GachaType,
+    pool: &PgPool,
+) -> Result<()> {
+    let pulls = G::get_pulls(uid, gacha_type, pool).await?;
+    let featured = G::get_featured(gacha_type, pool).await?;
+
+    let pity = G::pity(gacha_type);
+    let outcomes = G::outcomes(gacha_type, &pulls, &pity, featured.as_ref());
+
+    let model = G::model(gacha_type);
+
+    // The luck model is O(pulls * hard pity * hits), keep it off the async workers
+    let stats =
+        rt::task::spawn_blocking(move || calculate(&pulls, &pity, &outcomes, model.as_ref()))
+            .await?;
+
+    G::set_stats(uid, gacha_type, &stats, pool).await
+}
+
+pub fn outcomes(pulls: &[Pull], pity: &Pity, featured: Option<&Featured>) -> Vec<Option<Outcome>> {
+    let mut first_5 = pity.skip_first_5;
+
+    let mut guarantee = false;
+
+    let mut outcomes = Vec::with_capacity(pulls.len());
+
+    for pull in pulls {
+        let outcome = match featured {
+            Some(featured) if pull.rarity == pity.rarity_5 && !first_5 => Some(if guarantee {
+                guarantee = false;
+
+                Outcome::Guarantee
+            } else if featured.contains(pull) {
+                Outcome::Win
+            } else {
+                guarantee = true;
+
+                Outcome::Loss
+            }),
+            _ => None,
+        };
+
+        if pull.rarity == pity.rarity_5 {
+            first_5 = false;
+        }
+
+        outcomes.push(outcome);
+    }
+
+    outcomes
+}
+
+pub fn calculate(
+    pulls: &[Pull],
+    pity: &Pity,
+    outcomes: &[Option<Outcome>],
+    model: Option<&Model>,
+) -> Stats {
+    let mut pull_4 = 0;
+    let mut sum_4 = 0;
+    let mut count_4 = 0;
+
+    let mut pull_5 = 0;
+    let mut sum_5 = 0;
+    let mut count_5 = 0;
+
+    let mut first_5 = pity.skip_first_5;
+
+    // Pulls before the skipped first 5* carry unknown pity, so the 5* model only sees the rest
+    let mut pulls_5 = 0;
+    let mut skipped_5 = 0;
+
+    let mut sum_win = 0;
+    let mut count_win = 0;
+
+    let mut win_streak = 0;
+    let mut max_win_streak = 0;
+
+    let mut loss_streak = 0;
+    let mut max_loss_streak = 0;
+
+    for (pull, outcome) in pulls.iter().zip(outcomes) {
+        pull_4 += 1;
+        pull_5 += 1;
+
+        if !first_5 {
+            pulls_5 += 1;
+        }
+
+        if pull.rarity == pity.rarity_4 {
+            count_4 += 1;
+            sum_4 += pull_4;
+            pull_4 = 0;
+        } else if pull.rarity == pity.rarity_5 {
+            if first_5 {
+                first_5 = false;
+                skipped_5 += 1;
+                pull_5 = 0;
+                continue;
+            }
+
+            count_5 += 1;
+            sum_5 += pull_5;
+            pull_5 = 0;
+
+            match outcome {
+                // A captured radiance is a won 50/50 too
+                Some(Outcome::Win | Outcome::Radiance) => {
+                    count_win += 1;
+                    sum_win += 1;
+
+                    loss_streak = 0;
+
+                    win_streak += 1;
+                    max_win_streak = max_win_streak.max(win_streak);
+                }
+                Some(Outcome::Loss) => {
+                    count_win += 1;
+
+                    win_streak = 0;
+
+                    loss_streak += 1;
+                    max_loss_streak = max_loss_streak.max(loss_streak);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let luck_4 = if count_4 != 0 {
+        sum_4 as f64 / count_4 as f64
+    } else {
+        0.0
+    };
+    let luck_5 = if count_5 != 0 {
+        sum_5 as f64 / count_5 as f64
+    } else {
+        0.0
+    };
+    let win_rate = if count_win != 0 {
+        sum_win as f64 / count_win as f64
+    } else {
+        0.0
+    };
+
+    let (likelihood_4, likelihood_5, likelihood_win, expected_5) = match model {
+        Some(model) => {
+            // Every 5* resets the 4* counter like a 4* does
+            let luck_4 = model
+                .rate_4
+                .luck(pulls.len(), count_4 + count_5 + skipped_5);
+            let luck_5 = model.rate_5.luck(pulls_5, count_5);
+
+            (
+                luck_4.likelihood,
+                luck_5.likelihood,
+                model::win_likelihood(model.win, count_win, sum_win),
+                luck_5.expected,
+            )
+        }
+        None => (0.0, 0.0, 0.0, 0.0),
+    };
+
+    Stats {
+        luck_4,
+        luck_5,
+        win_rate,
+        win_streak: max_win_streak,
+        loss_streak: max_loss_streak,
+        likelihood_4,
+        likelihood_5,
+        likelihood_win,
+        expected_5,
+        actual_5: count_5 as i32,
+        count: pulls.len() as i32,
+    }
+}
@@ -0,0 +1,102 @@
+pub struct Rate {
+    pub base: f64,
+    pub soft: usize,
+    pub step: f64,
+    pub hard: usize,
+}
+
+impl Rate {
+    pub fn probability(&self, pull: usize) -> f64 {
+        if pull >= self.hard {
+            1.0
+        } else {
+            (self.base + self.step * pull.saturating_sub(self.soft) as f64).min(1.0)
+        }
+    }
+
+    fn gaps(&self) -> Vec<f64> {
+        let mut gaps = Vec::with_capacity(self.hard);
+        let mut miss = 1.0;
+
+        for pull in 1..=self.hard {
+            let probability = self.probability(pull);
+
+            gaps.push(miss * probability);
+            miss *= 1.0 - probability;
+        }
+
+        gaps
+    }
+
+    pub fn luck(&self, pulls: usize, actual: usize) -> Luck {
+        let gaps = self.gaps();
+
+        // at_least[c] = P(at least c hits in pulls) = P(c-th hit happens within pulls)
+        let mut at_least = Vec::new();
+
+        let mut dist = vec![0.0; pulls + 1];
+        dist[0] = 1.0;
+
+        loop {
+            let probability: f64 = dist.iter().sum();
+            at_least.push(probability);
+
+            if at_least.len() > actual + 1 && probability < 1e-12 {
+                break;
+            }
+
+            let mut next = vec![0.0; pulls + 1];
+
+            for (n, &p) in dist.iter().enumerate() {
+                if p == 0.0 {
+                    continue;
+                }
+
+                for (k, &gap) in gaps.iter().enumerate() {
+                    let Some(next) = next.get_mut(n + k + 1) else {
+                        break;
+                    };
+
+                    *next += p * gap;
+                }
+            }
+
+            dist = next;
+        }
+
+        let below = 1.0 - at_least[actual];
+        let exactly = at_least[actual] - at_least[actual + 1];
+
+        Luck {
+            likelihood: below + exactly / 2.0,
+            expected: at_least[1..].iter().sum(),
+        }
+    }
+}
+
+pub struct Model {
+    pub rate_4: Rate,
+    pub rate_5: Rate,
+    pub win: f64,
+}
+
+pub struct Luck {
+    pub likelihood: f64,
+    pub expected: f64,
+}
+
+pub fn win_likelihood(win: f64, count: usize, wins: usize) -> f64 {
+    let mut dist = vec![0.0; count + 1];
+    dist[0] = 1.0;
+
+    for i in 0..count {
+        for j in (0..=i).rev() {
+            dist[j + 1] += dist[j] * win;
+            dist[j] *= 1.0 - win;
+        }
+    }
+
+    let below: f64 = dist[..wins].iter().sum();
+
+    below + dist[wins] / 2.0
+}
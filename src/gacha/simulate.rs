@@ -0,0 +1,236 @@
+use anyhow::Result;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use super::{model::Model, Gacha, Outcome, Pity, Pull};
+
+pub struct Target {
+    pub character: usize,
+    pub weapon: usize,
+    pub budget: usize,
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+#[derive(Clone, Copy, Serialize, ToSchema)]
+pub struct State {
+    pub pull_5: usize,
+    pub guarantee: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Point {
+    pub pulls: usize,
+    pub probability: f64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Simulation {
+    pub seed: u64,
+    pub iterations: usize,
+    pub character: State,
+    pub weapon: State,
+    pub probability: f64,
+    pub distribution: Vec<Point>,
+}
+
+pub fn state(pulls: &[Pull], pity: &Pity, outcomes: &[Option<Outcome>]) -> State {
+    let mut pull_5 = 0;
+    let mut guarantee = false;
+
+    for (pull, outcome) in pulls.iter().zip(outcomes) {
+        pull_5 += 1;
+
+        if pull.rarity != pity.rarity_5 {
+            continue;
+        }
+
+        pull_5 = 0;
+
+        if let Some(outcome) = outcome {
+            guarantee = *outcome == Outcome::Loss;
+        }
+    }
+
+    State { pull_5, guarantee }
+}
+
+/// Pity model and current state of one banner type
+pub struct Banner {
+    pub model: Model,
+    pub state: State,
+}
+
+/// Deterministic for the same target, seed included
+pub fn simulate(target: &Target, character: &Banner, weapon: &Banner) -> Simulation {
+    let mut rng = StdRng::seed_from_u64(target.seed);
+
+    let mut successes = Vec::new();
+
+    for _ in 0..target.iterations {
+        let mut used = 0;
+
+        let copies = run(
+            &character.model,
+            character.state,
+            target.character,
+            target.budget,
+            &mut used,
+            &mut rng,
+        );
+
+        if copies < target.character {
+            continue;
+        }
+
+        let copies = run(
+            &weapon.model,
+            weapon.state,
+            target.weapon,
+            target.budget,
+            &mut used,
+            &mut rng,
+        );
+
+        if copies < target.weapon {
+            continue;
+        }
+
+        successes.push(used);
+    }
+
+    successes.sort_unstable();
+
+    let iterations = target.iterations.max(1) as f64;
+
+    let mut distribution = Vec::new();
+
+    for pulls in (10..target.budget).step_by(10).chain([target.budget]) {
+        let count = successes.partition_point(|&used| used <= pulls);
+
+        distribution.push(Point {
+            pulls,
+            probability: count as f64 / iterations,
+        });
+    }
+
+    Simulation {
+        seed: target.seed,
+        iterations: target.iterations,
+        character: character.state,
+        weapon: weapon.state,
+        probability: successes.len() as f64 / iterations,
+        distribution,
+    }
+}
+
+pub async fn load<G: Gacha>(uid: i32, gacha_type: G::GachaType, pool: &PgPool) -> Result<Banner> {
+    let model = G::model(gacha_type).ok_or_else(|| anyhow::anyhow!("No model for {gacha_type}"))?;
+
+    let pulls = G::get_pulls(uid, gacha_type, pool).await?;
+    let featured = G::get_featured(gacha_type, pool).await?;
+
+    let pity = G::pity(gacha_type);
+    let outcomes = G::outcomes(gacha_type, &pulls, &pity, featured.as_ref());
+
+    let state = state(&pulls, &pity, &outcomes);
+
+    Ok(Banner { model, state })
+}
+
+fn run(
+    model: &Model,
+    mut state: State,
+    target: usize,
+    budget: usize,
+    used: &mut usize,
+    rng: &mut StdRng,
+) -> usize {
+    let mut copies = 0;
+
+    while copies < target && *used < budget {
+        *used += 1;
+        state.pull_5 += 1;
+
+        if rng.random::<f64>() >= model.rate_5.probability(state.pull_5) {
+            continue;
+        }
+
+        state.pull_5 = 0;
+
+        if state.guarantee || rng.random::<f64>() < model.win {
+            state.guarantee = false;
+            copies += 1;
+        } else {
+            state.guarantee = true;
+        }
+    }
+
+    copies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gacha::hsr::Warps;
+
+    fn banner(gacha_type: <Warps as Gacha>::GachaType, pull_5: usize) -> Banner {
+        Banner {
+            model: Warps::model(gacha_type).unwrap(),
+            state: State {
+                pull_5,
+                guarantee: false,
+            },
+        }
+    }
+
+    fn target(seed: u64) -> Target {
+        Target {
+            character: 2,
+            weapon: 1,
+            budget: 400,
+            iterations: 2000,
+            seed,
+        }
+    }
+
+    fn run(seed: u64) -> serde_json::Value {
+        let simulation = simulate(
+            &target(seed),
+            &banner(Warps::CHARACTER, 40),
+            &banner(Warps::WEAPON, 10),
+        );
+
+        serde_json::to_value(simulation).unwrap()
+    }
+
+    #[test]
+    fn same_seed_same_simulation() {
+        assert_eq!(run(42), run(42));
+    }
+
+    #[test]
+    fn different_seed_different_simulation() {
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn distribution_is_monotonic() {
+        let simulation = simulate(
+            &target(7),
+            &banner(Warps::CHARACTER, 0),
+            &banner(Warps::WEAPON, 0),
+        );
+
+        assert!(simulation
+            .distribution
+            .windows(2)
+            .all(|w| w[0].probability <= w[1].probability));
+        assert_eq!(
+            simulation.distribution.last().unwrap().probability,
+            simulation.probability
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+
+use super::{
+    model::{Model, Rate},
+    Entry, Featured, Gacha, Pity, Pull, Server, Stats,
+};
+use crate::{
+    database,
+    permissions::{self, Permission},
+    ZzzGachaType,
+};
+
+pub struct Signals;
+
+impl Gacha for Signals {
+    type GachaType = ZzzGachaType;
+    type SetAll = database::zzz::signals::SetAll;
+
+    const GAME: &'static str = "zzz";
+
+    const PARAMS: &'static [(&'static str, &'static str)] = &[("lang", "en"), ("size", "20")];
+    const STATS: &'static [ZzzGachaType] = &[
+        ZzzGachaType::Standard,
+        ZzzGachaType::Special,
+        ZzzGachaType::WEngine,
+        ZzzGachaType::Bangboo,
+    ];
+    const CHARACTER: ZzzGachaType = ZzzGachaType::Special;
+    const WEAPON: ZzzGachaType = ZzzGachaType::WEngine;
+
+    fn endpoint(server: Server) -> &'static str {
+        match server {
+            Server::Global => {
+                "https://public-operation-nap-sg.hoyoverse.com/common/gacha_record/api/getGachaLog"
+            }
+            Server::Cn => {
+                "https://public-operation-nap.mihoyo.com/common/gacha_record/api/getGachaLog"
+            }
+        }
+    }
+
+    fn game_biz(server: Server) -> Option<&'static str> {
+        Some(match server {
+            Server::Global => "nap_global",
+            Server::Cn => "nap_cn",
+        })
+    }
+
+    fn gacha_type_id(gacha_type: ZzzGachaType) -> i32 {
+        gacha_type.id()
+    }
+
+    fn uigf_gacha_type(gacha_type: ZzzGachaType) -> i32 {
+        gacha_type.id() / 1000
+    }
+
+    fn time_zone(uid: i32) -> i32 {
+        match uid / 100000000 {
+            10 => -5,
+            15 => 1,
+            _ => 8,
+        }
+    }
+
+    fn pity(gacha_type: ZzzGachaType) -> Pity {
+        Pity {
+            rarity_4: 3,
+            rarity_5: 4,
+            skip_first_5: gacha_type == ZzzGachaType::Standard,
+        }
+    }
+
+    fn model(gacha_type: ZzzGachaType) -> Option<Model> {
+        match gacha_type {
+            ZzzGachaType::Standard | ZzzGachaType::Special => Some(Model {
+                rate_4: Rate {
+                    base: 0.094,
+                    soft: 10,
+                    step: 0.0,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.006,
+                    soft: 73,
+                    step: 0.06,
+                    hard: 90,
+                },
+                win: 0.5,
+            }),
+            ZzzGachaType::WEngine => Some(Model {
+                rate_4: Rate {
+                    base: 0.15,
+                    soft: 10,
+                    step: 0.0,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.01,
+                    soft: 64,
+                    step: 0.07,
+                    hard: 80,
+                },
+                win: 0.75,
+            }),
+            ZzzGachaType::Bangboo => Some(Model {
+                rate_4: Rate {
+                    base: 0.15,
+                    soft: 10,
+                    step: 0.0,
+                    hard: 10,
+                },
+                rate_5: Rate {
+                    base: 0.01,
+                    soft: 73,
+                    step: 0.06,
+                    hard: 80,
+                },
+                win: 1.0,
+            }),
+        }
+    }
+
+    async fn register(uid: i32, username: Option<String>, pool: &PgPool) -> Result<()> {
+        database::zzz::uids::set(&database::zzz::uids::DbUid { uid }, pool).await?;
+
+        if let Some(username) = username {
+            let connection = database::zzz::connections::DbConnection {
+                uid,
+                username,
+                verified: true,
+                private: false,
+            };
+
+            database::zzz::connections::set(&connection, pool).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn forbidden(uid: i32, username: Option<String>, pool: &PgPool) -> Result<bool> {
+        let mut forbidden = database::zzz::connections::get_by_uid(uid, pool)
+            .await?
+            .iter()
+            .any(|c| c.private);
+
+        if forbidden {
+            if let Some(username) = username {
+                if permissions::has(&username, Permission::AccessAllUids, pool).await? {
+                    forbidden = false;
+                } else if let Ok(connection) =
+                    database::zzz::connections::get_by_uid_and_username(uid, &username, pool).await
+                {
+                    forbidden = !connection.verified;
+                }
+            }
+        }
+
+        Ok(forbidden)
+    }
+
+    async fn verified(uid: i32, username: &str, pool: &PgPool) -> Result<bool> {
+        Ok(
+            database::zzz::connections::get_by_uid_and_username(uid, username, pool)
+                .await
+                .map(|c| c.verified)
+                .unwrap_or_default(),
+        )
+    }
+
+    async fn get_earliest_timestamp(
+        uid: i32,
+        gacha_type: ZzzGachaType,
+        pool: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(match gacha_type {
+            ZzzGachaType::Standard => {
+                database::zzz::signals::standard::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::Special => {
+                database::zzz::signals::special::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::WEngine => {
+                database::zzz::signals::w_engine::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::Bangboo => {
+                database::zzz::signals::bangboo::get_earliest_timestamp_by_uid(uid, pool).await?
+            }
+        })
+    }
+
+    async fn get_latest_timestamp(
+        _: i32,
+        _: ZzzGachaType,
+        _: &PgPool,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(None)
+    }
+
+    async fn push(
+        uid: i32,
+        entry: &Entry,
+        timestamp: DateTime<Utc>,
+        official: bool,
+        set_all: &mut database::zzz::signals::SetAll,
+        _: &PgPool,
+    ) -> Result<()> {
+        let id = entry.id.parse()?;
+
+        let item: i32 = entry.item_id.parse()?;
+
+        let mut character =
+            (entry.item_type == "Agents" || entry.item_type == "代理人").then_some(item);
+        let mut w_engine =
+            (entry.item_type == "W-Engines" || entry.item_type == "音擎").then_some(item);
+        let mut bangboo =
+            (entry.item_type == "Bangboo" || entry.item_type == "邦布").then_some(item);
+
+        if character.is_none() && w_engine.is_none() && bangboo.is_none() {
+            if item >= 50000 {
+                bangboo = Some(item);
+            } else if item >= 12000 {
+                w_engine = Some(item);
+            } else {
+                character = Some(item);
+            }
+        }
+
+        set_all.id.push(id);
+        set_all.uid.push(uid);
+        set_all.character.push(character);
+        set_all.w_engine.push(w_engine);
+        set_all.bangboo.push(bangboo);
+        set_all.timestamp.push(timestamp);
+        set_all.official.push(official);
+
+        Ok(())
+    }
+
+    async fn set_all(
+        gacha_type: ZzzGachaType,
+        set_all: &database::zzz::signals::SetAll,
+        conn: &mut PgConnection,
+    ) -> Result<()> {
+        match gacha_type {
+            ZzzGachaType::Standard => {
+                database::zzz::signals::standard::set_all(set_all, conn).await?
+            }
+            ZzzGachaType::Special => {
+                database::zzz::signals::special::set_all(set_all, conn).await?
+            }
+            ZzzGachaType::WEngine => {
+                database::zzz::signals::w_engine::set_all(set_all, conn).await?
+            }
+            ZzzGachaType::Bangboo => {
+                database::zzz::signals::bangboo::set_all(set_all, conn).await?
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_pulls(uid: i32, gacha_type: ZzzGachaType, pool: &PgPool) -> Result<Vec<Pull>> {
+        let signals = match gacha_type {
+            ZzzGachaType::Standard => {
+                database::zzz::signals::standard::get_infos_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::Special => {
+                database::zzz::signals::special::get_infos_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::WEngine => {
+                database::zzz::signals::w_engine::get_infos_by_uid(uid, pool).await?
+            }
+            ZzzGachaType::Bangboo => {
+                database::zzz::signals::bangboo::get_infos_by_uid(uid, pool).await?
+            }
+        };
+
+        Ok(signals
+            .into_iter()
+            .map(|signal| Pull {
+                item: signal.character.or(signal.w_engine),
+                rarity: signal.rarity.unwrap(),
+                timestamp: signal.timestamp,
+            })
+            .collect())
+    }
+
+    async fn get_featured(gacha_type: ZzzGachaType, pool: &PgPool) -> Result<Option<Featured>> {
+        let standard: &'static [i32] = match gacha_type {
+            ZzzGachaType::Special => &[1021, 1041, 1101, 1141, 1181, 1211],
+            ZzzGachaType::WEngine => &[14102, 14104, 14110, 14114, 14118, 14121],
+            _ => return Ok(None),
+        };
+
+        let mut banners: HashMap<_, Vec<_>> = HashMap::new();
+
+        for banner in database::zzz::banners::get_all(pool).await? {
+            let item = if gacha_type == ZzzGachaType::Special {
+                banner.character
+            } else {
+                banner.w_engine
+            };
+
+            if let Some(item) = item {
+                banners
+                    .entry(item)
+                    .or_default()
+                    .push(banner.start..banner.end);
+            }
+        }
+
+        Ok(Some(Featured::Standard { banners, standard }))
+    }
+
+    async fn set_stats(
+        uid: i32,
+        gacha_type: ZzzGachaType,
+        stats: &Stats,
+        pool: &PgPool,
+    ) -> Result<()> {
+        match gacha_type {
+            ZzzGachaType::Standard => {
+                let stat = database::zzz::signals_stats::standard::DbSignalsStatStandard {
+                    uid,
+                    luck_a: stats.luck_4,
+                    luck_s: stats.luck_5,
+                };
+                database::zzz::signals_stats::standard::set(&stat, pool).await?;
+            }
+            ZzzGachaType::Special => {
+                let stat = database::zzz::signals_stats::special::DbSignalsStatSpecial {
+                    uid,
+                    luck_a: stats.luck_4,
+                    luck_s: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                };
+                database::zzz::signals_stats::special::set(&stat, pool).await?;
+            }
+            ZzzGachaType::WEngine => {
+                let stat = database::zzz::signals_stats::w_engine::DbSignalsStatWEngine {
+                    uid,
+                    luck_a: stats.luck_4,
+                    luck_s: stats.luck_5,
+                    win_rate: stats.win_rate,
+                    win_streak: stats.win_streak,
+                    loss_streak: stats.loss_streak,
+                };
+                database::zzz::signals_stats::w_engine::set(&stat, pool).await?;
+            }
+            ZzzGachaType::Bangboo => {
+                let stat = database::zzz::signals_stats::bangboo::DbSignalsStatBangboo {
+                    uid,
+                    luck_a: stats.luck_4,
+                    luck_s: stats.luck_5,
+                };
+                database::zzz::signals_stats::bangboo::set(&stat, pool).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use uuid::Uuid;
+
+use super::{Mail, Mailer};
+
+/// Drops every mail as an .eml file into MAIL_DIR
+pub struct File {
+    dir: PathBuf,
+}
+
+impl File {
+    pub fn from_env() -> Self {
+        Self {
+            dir: env::var("MAIL_DIR")
+                .unwrap_or_else(|_| "mail".to_string())
+                .into(),
+        }
+    }
+}
+
+impl Mailer for File {
+    fn send<'a>(&'a self, mail: &'a Mail) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            fs::create_dir_all(&self.dir)?;
+
+            let path = self.dir.join(format!(
+                "{}-{}.eml",
+                Utc::now().format("%Y%m%d%H%M%S"),
+                Uuid::new_v4()
+            ));
+
+            fs::write(&path, mail.message()?.formatted())?;
+
+            info!("Wrote mail to {}", path.display());
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+
+use super::{Mail, Mailer};
+
+pub struct Log;
+
+impl Mailer for Log {
+    fn send<'a>(&'a self, mail: &'a Mail) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            info!("Mail to {} ({}):\n{}", mail.to, mail.subject, mail.text);
+
+            Ok(())
+        })
+    }
+}
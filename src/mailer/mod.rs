@@ -0,0 +1,65 @@
+mod file;
+mod log;
+mod smtp;
+pub mod templates;
+
+use std::{env, sync::Arc};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use lettre::{
+    message::{Mailbox, MultiPart},
+    Address, Message,
+};
+
+pub struct Mail {
+    pub to: Mailbox,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+impl Mail {
+    fn message(&self) -> Result<Message> {
+        let from =
+            env::var("MAIL_FROM").unwrap_or_else(|_| "stardb <noreply@stardb.gg>".to_string());
+
+        Ok(Message::builder()
+            .from(from.parse()?)
+            .to(self.to.clone())
+            .subject(&self.subject)
+            .multipart(MultiPart::alternative_plain_html(
+                self.text.clone(),
+                self.html.clone(),
+            ))?)
+    }
+}
+
+pub fn mailbox(username: &str, email: &str) -> Result<Mailbox> {
+    Ok(Mailbox::new(
+        Some(username.to_string()),
+        email.parse::<Address>()?,
+    ))
+}
+
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, mail: &'a Mail) -> BoxFuture<'a, Result<()>>;
+}
+
+// MAILER=smtp|file|log, falls back to log when there are no SMTP credentials
+pub fn from_env() -> Result<Arc<dyn Mailer>> {
+    let mailer = env::var("MAILER").unwrap_or_else(|_| {
+        if env::var("SMTP_USERNAME").is_ok() {
+            "smtp".to_string()
+        } else {
+            "log".to_string()
+        }
+    });
+
+    Ok(match mailer.as_str() {
+        "smtp" => Arc::new(smtp::Smtp::from_env()?),
+        "file" => Arc::new(file::File::from_env()),
+        "log" => Arc::new(log::Log),
+        _ => return Err(anyhow::anyhow!("Unknown mailer {mailer}")),
+    })
+}
@@ -0,0 +1,35 @@
+use std::env;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use lettre::{
+    transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport,
+    Tokio1Executor,
+};
+
+use super::{Mail, Mailer};
+
+pub struct Smtp(AsyncSmtpTransport<Tokio1Executor>);
+
+impl Smtp {
+    pub fn from_env() -> Result<Self> {
+        let host = env::var("SMTP_HOST").unwrap_or_else(|_| "smtppro.zoho.eu".to_string());
+        let credentials = Credentials::new(env::var("SMTP_USERNAME")?, env::var("SMTP_PASSWORD")?);
+
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(&host)?
+            .credentials(credentials)
+            .build();
+
+        Ok(Self(transport))
+    }
+}
+
+impl Mailer for Smtp {
+    fn send<'a>(&'a self, mail: &'a Mail) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.0.send(mail.message()?).await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,543 @@
+use lettre::message::Mailbox;
+
+use super::Mail;
+use crate::Language;
+
+// {username} and {minutes} are replaced in intro and outro
+struct Template {
+    subject: &'static str,
+    intro: &'static str,
+    action: &'static str,
+    outro: &'static str,
+}
+
+fn login_template(language: Language) -> Template {
+    match language {
+        Language::ZhCn => Template {
+            subject: "StarDB.GG 紧急登录",
+            intro: "请使用以下链接登录 StarDB.GG：",
+            action: "登录",
+            outro: "该链接将在 {minutes} 分钟后失效，且只能使用一次。如果这不是你本人的请求，请忽略此邮件。",
+        },
+        Language::ZhTw => Template {
+            subject: "StarDB.GG 緊急登入",
+            intro: "請使用以下連結登入 StarDB.GG：",
+            action: "登入",
+            outro: "此連結將在 {minutes} 分鐘後失效，且只能使用一次。如果這不是你本人的請求，請忽略此郵件。",
+        },
+        Language::De => Template {
+            subject: "StarDB.GG Notfall-Anmeldung",
+            intro: "Nutze den folgenden Link, um dich bei StarDB.GG anzumelden:",
+            action: "Anmelden",
+            outro: "Der Link ist {minutes} Minuten gültig und kann nur einmal verwendet werden. Falls du ihn nicht angefordert hast, kannst du diese E-Mail ignorieren.",
+        },
+        Language::En => Template {
+            subject: "StarDB.GG Emergency Login",
+            intro: "Use the following link to log in to StarDB.GG:",
+            action: "Log in",
+            outro: "The link expires in {minutes} minutes and can only be used once. If you didn't request it, you can ignore this email.",
+        },
+        Language::EsEs => Template {
+            subject: "Inicio de sesión de emergencia en StarDB.GG",
+            intro: "Usa el siguiente enlace para iniciar sesión en StarDB.GG:",
+            action: "Iniciar sesión",
+            outro: "El enlace caduca en {minutes} minutos y solo se puede usar una vez. Si no lo has solicitado, puedes ignorar este correo.",
+        },
+        Language::Fr => Template {
+            subject: "Connexion d'urgence StarDB.GG",
+            intro: "Utilise le lien suivant pour te connecter à StarDB.GG :",
+            action: "Se connecter",
+            outro: "Le lien expire dans {minutes} minutes et ne peut être utilisé qu'une seule fois. Si tu ne l'as pas demandé, tu peux ignorer cet e-mail.",
+        },
+        Language::Id => Template {
+            subject: "Login Darurat StarDB.GG",
+            intro: "Gunakan tautan berikut untuk masuk ke StarDB.GG:",
+            action: "Masuk",
+            outro: "Tautan ini kedaluwarsa dalam {minutes} menit dan hanya dapat digunakan sekali. Jika kamu tidak memintanya, abaikan email ini.",
+        },
+        Language::Ja => Template {
+            subject: "StarDB.GG 緊急ログイン",
+            intro: "以下のリンクから StarDB.GG にログインしてください：",
+            action: "ログイン",
+            outro: "このリンクの有効期限は {minutes} 分で、一度しか使用できません。心当たりがない場合は、このメールを無視してください。",
+        },
+        Language::Ko => Template {
+            subject: "StarDB.GG 긴급 로그인",
+            intro: "다음 링크를 사용하여 StarDB.GG에 로그인하세요:",
+            action: "로그인",
+            outro: "이 링크는 {minutes}분 후에 만료되며 한 번만 사용할 수 있습니다. 요청하지 않으셨다면 이 메일을 무시하셔도 됩니다.",
+        },
+        Language::PtPt => Template {
+            subject: "Início de sessão de emergência no StarDB.GG",
+            intro: "Usa a seguinte ligação para iniciar sessão no StarDB.GG:",
+            action: "Iniciar sessão",
+            outro: "A ligação expira em {minutes} minutos e só pode ser usada uma vez. Se não a pediste, podes ignorar este e-mail.",
+        },
+        Language::Ru => Template {
+            subject: "Экстренный вход в StarDB.GG",
+            intro: "Используйте следующую ссылку, чтобы войти в StarDB.GG:",
+            action: "Войти",
+            outro: "Ссылка действительна {minutes} минут и может быть использована только один раз. Если вы не запрашивали её, просто проигнорируйте это письмо.",
+        },
+        Language::Th => Template {
+            subject: "เข้าสู่ระบบฉุกเฉิน StarDB.GG",
+            intro: "ใช้ลิงก์ต่อไปนี้เพื่อเข้าสู่ระบบ StarDB.GG:",
+            action: "เข้าสู่ระบบ",
+            outro: "ลิงก์นี้จะหมดอายุใน {minutes} นาทีและใช้ได้เพียงครั้งเดียว หากคุณไม่ได้ร้องขอ สามารถเพิกเฉยต่ออีเมลนี้ได้",
+        },
+        Language::Vi => Template {
+            subject: "Đăng nhập khẩn cấp StarDB.GG",
+            intro: "Sử dụng liên kết sau để đăng nhập vào StarDB.GG:",
+            action: "Đăng nhập",
+            outro: "Liên kết sẽ hết hạn sau {minutes} phút và chỉ có thể sử dụng một lần. Nếu bạn không yêu cầu, hãy bỏ qua email này.",
+        },
+    }
+}
+
+fn verify_email_template(language: Language) -> Template {
+    match language {
+        Language::ZhCn => Template {
+            subject: "验证你的 StarDB.GG 邮箱",
+            intro: "请确认此邮箱属于 StarDB.GG 账号 {username}：",
+            action: "验证邮箱",
+            outro: "该链接将在 {minutes} 分钟后失效。如果你没有添加此邮箱，请忽略此邮件。",
+        },
+        Language::ZhTw => Template {
+            subject: "驗證你的 StarDB.GG 電子郵件",
+            intro: "請確認此電子郵件屬於 StarDB.GG 帳號 {username}：",
+            action: "驗證電子郵件",
+            outro: "此連結將在 {minutes} 分鐘後失效。如果你沒有新增此電子郵件，請忽略此郵件。",
+        },
+        Language::De => Template {
+            subject: "Bestätige deine E-Mail-Adresse für StarDB.GG",
+            intro: "Bestätige, dass diese Adresse zum StarDB.GG-Konto {username} gehört:",
+            action: "E-Mail bestätigen",
+            outro: "Der Link ist {minutes} Minuten gültig. Falls du diese Adresse nicht hinzugefügt hast, kannst du diese E-Mail ignorieren.",
+        },
+        Language::En => Template {
+            subject: "Verify your StarDB.GG email",
+            intro: "Confirm that this address belongs to the StarDB.GG account {username}:",
+            action: "Verify email",
+            outro: "The link expires in {minutes} minutes. If you didn't add this address, you can ignore this email.",
+        },
+        Language::EsEs => Template {
+            subject: "Verifica tu correo de StarDB.GG",
+            intro: "Confirma que esta dirección pertenece a la cuenta de StarDB.GG {username}:",
+            action: "Verificar correo",
+            outro: "El enlace caduca en {minutes} minutos. Si no has añadido esta dirección, puedes ignorar este correo.",
+        },
+        Language::Fr => Template {
+            subject: "Vérifie ton adresse e-mail StarDB.GG",
+            intro: "Confirme que cette adresse appartient au compte StarDB.GG {username} :",
+            action: "Vérifier l'adresse",
+            outro: "Le lien expire dans {minutes} minutes. Si tu n'as pas ajouté cette adresse, tu peux ignorer cet e-mail.",
+        },
+        Language::Id => Template {
+            subject: "Verifikasi email StarDB.GG kamu",
+            intro: "Konfirmasi bahwa alamat ini milik akun StarDB.GG {username}:",
+            action: "Verifikasi email",
+            outro: "Tautan ini kedaluwarsa dalam {minutes} menit. Jika kamu tidak menambahkan alamat ini, abaikan email ini.",
+        },
+        Language::Ja => Template {
+            subject: "StarDB.GG のメールアドレスを確認してください",
+            intro: "このアドレスが StarDB.GG アカウント {username} のものであることを確認してください：",
+            action: "メールアドレスを確認",
+            outro: "このリンクの有効期限は {minutes} 分です。心当たりがない場合は、このメールを無視してください。",
+        },
+        Language::Ko => Template {
+            subject: "StarDB.GG 이메일을 인증하세요",
+            intro: "이 주소가 StarDB.GG 계정 {username}의 것인지 확인하세요:",
+            action: "이메일 인증",
+            outro: "이 링크는 {minutes}분 후에 만료됩니다. 이 주소를 추가하지 않으셨다면 이 메일을 무시하셔도 됩니다.",
+        },
+        Language::PtPt => Template {
+            subject: "Verifica o teu e-mail do StarDB.GG",
+            intro: "Confirma que este endereço pertence à conta do StarDB.GG {username}:",
+            action: "Verificar e-mail",
+            outro: "A ligação expira em {minutes} minutos. Se não adicionaste este endereço, podes ignorar este e-mail.",
+        },
+        Language::Ru => Template {
+            subject: "Подтвердите email для StarDB.GG",
+            intro: "Подтвердите, что этот адрес принадлежит аккаунту StarDB.GG {username}:",
+            action: "Подтвердить email",
+            outro: "Ссылка действительна {minutes} минут. Если вы не добавляли этот адрес, просто проигнорируйте это письмо.",
+        },
+        Language::Th => Template {
+            subject: "ยืนยันอีเมล StarDB.GG ของคุณ",
+            intro: "ยืนยันว่าที่อยู่นี้เป็นของบัญชี StarDB.GG {username}:",
+            action: "ยืนยันอีเมล",
+            outro: "ลิงก์นี้จะหมดอายุใน {minutes} นาที หากคุณไม่ได้เพิ่มที่อยู่นี้ สามารถเพิกเฉยต่ออีเมลนี้ได้",
+        },
+        Language::Vi => Template {
+            subject: "Xác minh email StarDB.GG của bạn",
+            intro: "Xác nhận địa chỉ này thuộc về tài khoản StarDB.GG {username}:",
+            action: "Xác minh email",
+            outro: "Liên kết sẽ hết hạn sau {minutes} phút. Nếu bạn không thêm địa chỉ này, hãy bỏ qua email này.",
+        },
+    }
+}
+
+fn reset_password_template(language: Language) -> Template {
+    match language {
+        Language::ZhCn => Template {
+            subject: "重置你的 StarDB.GG 密码",
+            intro: "请使用以下链接为 StarDB.GG 账号 {username} 设置新密码：",
+            action: "重置密码",
+            outro: "该链接将在 {minutes} 分钟后失效，且只能使用一次。如果这不是你本人的请求，请忽略此邮件。",
+        },
+        Language::ZhTw => Template {
+            subject: "重設你的 StarDB.GG 密碼",
+            intro: "請使用以下連結為 StarDB.GG 帳號 {username} 設定新密碼：",
+            action: "重設密碼",
+            outro: "此連結將在 {minutes} 分鐘後失效，且只能使用一次。如果這不是你本人的請求，請忽略此郵件。",
+        },
+        Language::De => Template {
+            subject: "Setze dein StarDB.GG-Passwort zurück",
+            intro: "Nutze den folgenden Link, um ein neues Passwort für das StarDB.GG-Konto {username} festzulegen:",
+            action: "Passwort zurücksetzen",
+            outro: "Der Link ist {minutes} Minuten gültig und kann nur einmal verwendet werden. Falls du ihn nicht angefordert hast, kannst du diese E-Mail ignorieren.",
+        },
+        Language::En => Template {
+            subject: "Reset your StarDB.GG password",
+            intro: "Use the following link to set a new password for the StarDB.GG account {username}:",
+            action: "Reset password",
+            outro: "The link expires in {minutes} minutes and can only be used once. If you didn't request it, you can ignore this email.",
+        },
+        Language::EsEs => Template {
+            subject: "Restablece tu contraseña de StarDB.GG",
+            intro: "Usa el siguiente enlace para establecer una nueva contraseña para la cuenta de StarDB.GG {username}:",
+            action: "Restablecer contraseña",
+            outro: "El enlace caduca en {minutes} minutos y solo se puede usar una vez. Si no lo has solicitado, puedes ignorar este correo.",
+        },
+        Language::Fr => Template {
+            subject: "Réinitialise ton mot de passe StarDB.GG",
+            intro: "Utilise le lien suivant pour définir un nouveau mot de passe pour le compte StarDB.GG {username} :",
+            action: "Réinitialiser le mot de passe",
+            outro: "Le lien expire dans {minutes} minutes et ne peut être utilisé qu'une seule fois. Si tu ne l'as pas demandé, tu peux ignorer cet e-mail.",
+        },
+        Language::Id => Template {
+            subject: "Atur ulang kata sandi StarDB.GG kamu",
+            intro: "Gunakan tautan berikut untuk mengatur kata sandi baru untuk akun StarDB.GG {username}:",
+            action: "Atur ulang kata sandi",
+            outro: "Tautan ini kedaluwarsa dalam {minutes} menit dan hanya dapat digunakan sekali. Jika kamu tidak memintanya, abaikan email ini.",
+        },
+        Language::Ja => Template {
+            subject: "StarDB.GG のパスワードをリセット",
+            intro: "以下のリンクから StarDB.GG アカウント {username} の新しいパスワードを設定してください：",
+            action: "パスワードをリセット",
+            outro: "このリンクの有効期限は {minutes} 分で、一度しか使用できません。心当たりがない場合は、このメールを無視してください。",
+        },
+        Language::Ko => Template {
+            subject: "StarDB.GG 비밀번호 재설정",
+            intro: "다음 링크를 사용하여 StarDB.GG 계정 {username}의 새 비밀번호를 설정하세요:",
+            action: "비밀번호 재설정",
+            outro: "이 링크는 {minutes}분 후에 만료되며 한 번만 사용할 수 있습니다. 요청하지 않으셨다면 이 메일을 무시하셔도 됩니다.",
+        },
+        Language::PtPt => Template {
+            subject: "Repõe a tua palavra-passe do StarDB.GG",
+            intro: "Usa a seguinte ligação para definir uma nova palavra-passe para a conta do StarDB.GG {username}:",
+            action: "Repor palavra-passe",
+            outro: "A ligação expira em {minutes} minutos e só pode ser usada uma vez. Se não a pediste, podes ignorar este e-mail.",
+        },
+        Language::Ru => Template {
+            subject: "Сброс пароля StarDB.GG",
+            intro: "Используйте следующую ссылку, чтобы задать новый пароль для аккаунта StarDB.GG {username}:",
+            action: "Сбросить пароль",
+            outro: "Ссылка действительна {minutes} минут и может быть использована только один раз. Если вы не запрашивали её, просто проигнорируйте это письмо.",
+        },
+        Language::Th => Template {
+            subject: "รีเซ็ตรหัสผ่าน StarDB.GG ของคุณ",
+            intro: "ใช้ลิงก์ต่อไปนี้เพื่อตั้งรหัสผ่านใหม่สำหรับบัญชี StarDB.GG {username}:",
+            action: "รีเซ็ตรหัสผ่าน",
+            outro: "ลิงก์นี้จะหมดอายุใน {minutes} นาทีและใช้ได้เพียงครั้งเดียว หากคุณไม่ได้ร้องขอ สามารถเพิกเฉยต่ออีเมลนี้ได้",
+        },
+        Language::Vi => Template {
+            subject: "Đặt lại mật khẩu StarDB.GG",
+            intro: "Sử dụng liên kết sau để đặt mật khẩu mới cho tài khoản StarDB.GG {username}:",
+            action: "Đặt lại mật khẩu",
+            outro: "Liên kết sẽ hết hạn sau {minutes} phút và chỉ có thể sử dụng một lần. Nếu bạn không yêu cầu, hãy bỏ qua email này.",
+        },
+    }
+}
+
+fn email_changed_template(language: Language) -> Template {
+    match language {
+        Language::ZhCn => Template {
+            subject: "你的 StarDB.GG 邮箱已更改",
+            intro: "StarDB.GG 账号 {username} 的邮箱已被更改，此地址将不再接收该账号的邮件。",
+            action: "",
+            outro: "如果这不是你本人的操作，请立即重置密码。",
+        },
+        Language::ZhTw => Template {
+            subject: "你的 StarDB.GG 電子郵件已變更",
+            intro: "StarDB.GG 帳號 {username} 的電子郵件已被變更，此地址將不再收到該帳號的郵件。",
+            action: "",
+            outro: "如果這不是你本人的操作，請立即重設密碼。",
+        },
+        Language::De => Template {
+            subject: "Deine StarDB.GG-E-Mail-Adresse wurde geändert",
+            intro: "Die E-Mail-Adresse des StarDB.GG-Kontos {username} wurde geändert. Diese Adresse erhält keine E-Mails mehr für das Konto.",
+            action: "",
+            outro: "Falls du das nicht warst, setze sofort dein Passwort zurück.",
+        },
+        Language::En => Template {
+            subject: "Your StarDB.GG email was changed",
+            intro: "The email address of the StarDB.GG account {username} was changed. This address will no longer receive emails for the account.",
+            action: "",
+            outro: "If this wasn't you, reset your password right away.",
+        },
+        Language::EsEs => Template {
+            subject: "Se ha cambiado tu correo de StarDB.GG",
+            intro: "Se ha cambiado la dirección de correo de la cuenta de StarDB.GG {username}. Esta dirección ya no recibirá correos de la cuenta.",
+            action: "",
+            outro: "Si no has sido tú, restablece tu contraseña de inmediato.",
+        },
+        Language::Fr => Template {
+            subject: "Ton adresse e-mail StarDB.GG a été modifiée",
+            intro: "L'adresse e-mail du compte StarDB.GG {username} a été modifiée. Cette adresse ne recevra plus d'e-mails pour ce compte.",
+            action: "",
+            outro: "Si ce n'était pas toi, réinitialise ton mot de passe immédiatement.",
+        },
+        Language::Id => Template {
+            subject: "Email StarDB.GG kamu telah diubah",
+            intro: "Alamat email akun StarDB.GG {username} telah diubah. Alamat ini tidak akan lagi menerima email untuk akun tersebut.",
+            action: "",
+            outro: "Jika ini bukan kamu, segera atur ulang kata sandimu.",
+        },
+        Language::Ja => Template {
+            subject: "StarDB.GG のメールアドレスが変更されました",
+            intro: "StarDB.GG アカウント {username} のメールアドレスが変更されました。このアドレスには今後アカウントのメールは届きません。",
+            action: "",
+            outro: "心当たりがない場合は、すぐにパスワードをリセットしてください。",
+        },
+        Language::Ko => Template {
+            subject: "StarDB.GG 이메일이 변경되었습니다",
+            intro: "StarDB.GG 계정 {username}의 이메일 주소가 변경되었습니다. 이 주소로는 더 이상 계정 메일이 발송되지 않습니다.",
+            action: "",
+            outro: "본인이 변경하지 않았다면 즉시 비밀번호를 재설정하세요.",
+        },
+        Language::PtPt => Template {
+            subject: "O teu e-mail do StarDB.GG foi alterado",
+            intro: "O endereço de e-mail da conta do StarDB.GG {username} foi alterado. Este endereço deixará de receber e-mails da conta.",
+            action: "",
+            outro: "Se não foste tu, repõe a tua palavra-passe imediatamente.",
+        },
+        Language::Ru => Template {
+            subject: "Email в StarDB.GG изменён",
+            intro: "Адрес электронной почты аккаунта StarDB.GG {username} был изменён. Этот адрес больше не будет получать письма для аккаунта.",
+            action: "",
+            outro: "Если это были не вы, немедленно сбросьте пароль.",
+        },
+        Language::Th => Template {
+            subject: "อีเมล StarDB.GG ของคุณถูกเปลี่ยนแล้ว",
+            intro: "ที่อยู่อีเมลของบัญชี StarDB.GG {username} ถูกเปลี่ยนแล้ว ที่อยู่นี้จะไม่ได้รับอีเมลของบัญชีอีกต่อไป",
+            action: "",
+            outro: "หากคุณไม่ได้ดำเนินการนี้ โปรดรีเซ็ตรหัสผ่านทันที",
+        },
+        Language::Vi => Template {
+            subject: "Email StarDB.GG của bạn đã được thay đổi",
+            intro: "Địa chỉ email của tài khoản StarDB.GG {username} đã được thay đổi. Địa chỉ này sẽ không còn nhận email của tài khoản nữa.",
+            action: "",
+            outro: "Nếu không phải bạn, hãy đặt lại mật khẩu ngay lập tức.",
+        },
+    }
+}
+
+fn password_changed_template(language: Language) -> Template {
+    match language {
+        Language::ZhCn => Template {
+            subject: "你的 StarDB.GG 密码已更改",
+            intro: "StarDB.GG 账号 {username} 的密码已被更改。",
+            action: "",
+            outro: "如果这不是你本人的操作，请立即重置密码。",
+        },
+        Language::ZhTw => Template {
+            subject: "你的 StarDB.GG 密碼已變更",
+            intro: "StarDB.GG 帳號 {username} 的密碼已被變更。",
+            action: "",
+            outro: "如果這不是你本人的操作，請立即重設密碼。",
+        },
+        Language::De => Template {
+            subject: "Dein StarDB.GG-Passwort wurde geändert",
+            intro: "Das Passwort des StarDB.GG-Kontos {username} wurde geändert.",
+            action: "",
+            outro: "Falls du das nicht warst, setze sofort dein Passwort zurück.",
+        },
+        Language::En => Template {
+            subject: "Your StarDB.GG password was changed",
+            intro: "The password of the StarDB.GG account {username} was changed.",
+            action: "",
+            outro: "If this wasn't you, reset your password right away.",
+        },
+        Language::EsEs => Template {
+            subject: "Se ha cambiado tu contraseña de StarDB.GG",
+            intro: "Se ha cambiado la contraseña de la cuenta de StarDB.GG {username}.",
+            action: "",
+            outro: "Si no has sido tú, restablece tu contraseña de inmediato.",
+        },
+        Language::Fr => Template {
+            subject: "Ton mot de passe StarDB.GG a été modifié",
+            intro: "Le mot de passe du compte StarDB.GG {username} a été modifié.",
+            action: "",
+            outro: "Si ce n'était pas toi, réinitialise ton mot de passe immédiatement.",
+        },
+        Language::Id => Template {
+            subject: "Kata sandi StarDB.GG kamu telah diubah",
+            intro: "Kata sandi akun StarDB.GG {username} telah diubah.",
+            action: "",
+            outro: "Jika ini bukan kamu, segera atur ulang kata sandimu.",
+        },
+        Language::Ja => Template {
+            subject: "StarDB.GG のパスワードが変更されました",
+            intro: "StarDB.GG アカウント {username} のパスワードが変更されました。",
+            action: "",
+            outro: "心当たりがない場合は、すぐにパスワードをリセットしてください。",
+        },
+        Language::Ko => Template {
+            subject: "StarDB.GG 비밀번호가 변경되었습니다",
+            intro: "StarDB.GG 계정 {username}의 비밀번호가 변경되었습니다.",
+            action: "",
+            outro: "본인이 변경하지 않았다면 즉시 비밀번호를 재설정하세요.",
+        },
+        Language::PtPt => Template {
+            subject: "A tua palavra-passe do StarDB.GG foi alterada",
+            intro: "A palavra-passe da conta do StarDB.GG {username} foi alterada.",
+            action: "",
+            outro: "Se não foste tu, repõe a tua palavra-passe imediatamente.",
+        },
+        Language::Ru => Template {
+            subject: "Пароль в StarDB.GG изменён",
+            intro: "Пароль аккаунта StarDB.GG {username} был изменён.",
+            action: "",
+            outro: "Если это были не вы, немедленно сбросьте пароль.",
+        },
+        Language::Th => Template {
+            subject: "รหัสผ่าน StarDB.GG ของคุณถูกเปลี่ยนแล้ว",
+            intro: "รหัสผ่านของบัญชี StarDB.GG {username} ถูกเปลี่ยนแล้ว",
+            action: "",
+            outro: "หากคุณไม่ได้ดำเนินการนี้ โปรดรีเซ็ตรหัสผ่านทันที",
+        },
+        Language::Vi => Template {
+            subject: "Mật khẩu StarDB.GG của bạn đã được thay đổi",
+            intro: "Mật khẩu của tài khoản StarDB.GG {username} đã được thay đổi.",
+            action: "",
+            outro: "Nếu không phải bạn, hãy đặt lại mật khẩu ngay lập tức.",
+        },
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn render(
+    to: Mailbox,
+    template: Template,
+    link: Option<&str>,
+    username: &str,
+    minutes: i64,
+) -> Mail {
+    let fill = |s: &str, username: &str| {
+        s.replace("{username}", username)
+            .replace("{minutes}", &minutes.to_string())
+    };
+
+    let text = match link {
+        Some(link) => format!(
+            "{}\n{link}\n\n{}\n",
+            fill(template.intro, username),
+            fill(template.outro, username)
+        ),
+        None => format!(
+            "{}\n\n{}\n",
+            fill(template.intro, username),
+            fill(template.outro, username)
+        ),
+    };
+
+    let username = escape(username);
+
+    let action = link
+        .map(|link| {
+            format!(
+                "<p><a href=\"{}\">{}</a></p>\n",
+                escape(link),
+                escape(template.action)
+            )
+        })
+        .unwrap_or_default();
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html>
+<body style="font-family: sans-serif;">
+<p>{}</p>
+{action}<p style="color: #666;">{}</p>
+</body>
+</html>
+"#,
+        fill(&escape(template.intro), &username),
+        fill(&escape(template.outro), &username),
+    );
+
+    Mail {
+        to,
+        subject: template.subject.to_string(),
+        text,
+        html,
+    }
+}
+
+pub fn login(to: Mailbox, language: Language, token: &str, minutes: i64) -> Mail {
+    let link = format!("https://stardb.gg/login?token={token}");
+
+    render(to, login_template(language), Some(&link), "", minutes)
+}
+
+pub fn verify_email(
+    to: Mailbox,
+    language: Language,
+    username: &str,
+    token: &str,
+    minutes: i64,
+) -> Mail {
+    let link = format!("https://stardb.gg/verify-email?token={token}");
+
+    render(
+        to,
+        verify_email_template(language),
+        Some(&link),
+        username,
+        minutes,
+    )
+}
+
+pub fn reset_password(
+    to: Mailbox,
+    language: Language,
+    username: &str,
+    token: &str,
+    minutes: i64,
+) -> Mail {
+    let link = format!("https://stardb.gg/reset-password?token={token}");
+
+    render(
+        to,
+        reset_password_template(language),
+        Some(&link),
+        username,
+        minutes,
+    )
+}
+
+pub fn email_changed(to: Mailbox, language: Language, username: &str) -> Mail {
+    render(to, email_changed_template(language), None, username, 0)
+}
+
+pub fn password_changed(to: Mailbox, language: Language, username: &str) -> Mail {
+    render(to, password_changed_template(language), None, username, 0)
+}
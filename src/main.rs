@@ -1,27 +1,35 @@
 #[macro_use]
 extern crate tracing;
 
+mod access_tokens;
+mod account_deletion;
 mod api;
 mod database;
+mod enka;
+mod gacha;
+mod mailer;
 mod mihomo;
+mod permissions;
 mod pg_session_store;
+mod providers;
+mod rate_limit;
+mod scheduler;
+mod signing;
+mod two_factor;
 mod update;
 
-use std::{env, fs, path::Path};
+use std::{env, fs};
 
 use actix_cors::Cors;
 use actix_files::Files;
 use actix_session::{config::PersistentSession, SessionMiddleware};
 use actix_web::{
     cookie::time::Duration,
-    middleware::Compress,
+    middleware::{from_fn, Compress},
     web::{self, Data},
     App, HttpServer,
 };
-use ed25519_dalek::SecretKey;
-use futures::lock::Mutex;
 use pg_session_store::PgSessionStore;
-use rand::RngCore;
 use sqlx::postgres::PgPoolOptions;
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -96,6 +104,25 @@ impl Language {
         }
         .to_string()
     }
+
+    pub fn uigf(&self) -> String {
+        match self {
+            Language::ZhCn => "zh-cn",
+            Language::ZhTw => "zh-tw",
+            Language::De => "de-de",
+            Language::En => "en-us",
+            Language::EsEs => "es-es",
+            Language::Fr => "fr-fr",
+            Language::Id => "id-id",
+            Language::Ja => "ja-jp",
+            Language::Ko => "ko-kr",
+            Language::PtPt => "pt-pt",
+            Language::Ru => "ru-ru",
+            Language::Th => "th-th",
+            Language::Vi => "vi-vn",
+        }
+        .to_string()
+    }
 }
 
 #[derive(
@@ -113,7 +140,7 @@ impl Language {
 )]
 #[strum(serialize_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
-enum GachaType {
+pub enum GachaType {
     Standard,
     Departure,
     Special,
@@ -134,6 +161,9 @@ impl GachaType {
 #[derive(
     Clone,
     Copy,
+    PartialEq,
+    Eq,
+    Hash,
     strum::Display,
     strum::EnumIter,
     strum::EnumString,
@@ -143,7 +173,7 @@ impl GachaType {
 )]
 #[strum(serialize_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
-enum ZzzGachaType {
+pub enum ZzzGachaType {
     Standard,
     Special,
     WEngine,
@@ -176,7 +206,7 @@ impl ZzzGachaType {
 )]
 #[strum(serialize_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
-enum GiGachaType {
+pub enum GiGachaType {
     Beginner,
     Standard,
     Character,
@@ -184,6 +214,18 @@ enum GiGachaType {
     Chronicled,
 }
 
+impl GiGachaType {
+    pub fn id(self) -> i32 {
+        match self {
+            GiGachaType::Beginner => 100,
+            GiGachaType::Standard => 200,
+            GiGachaType::Character => 301,
+            GiGachaType::Weapon => 302,
+            GiGachaType::Chronicled => 500,
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -210,6 +252,7 @@ async fn main() -> anyhow::Result<()> {
     info!("Starting api!");
 
     let _ = fs::create_dir("mihomo");
+    let _ = fs::create_dir("enka");
     let _ = fs::create_dir("dimbreath");
     let _ = fs::create_dir("static");
     let _ = fs::create_dir("cache");
@@ -230,15 +273,20 @@ async fn main() -> anyhow::Result<()> {
     update::dimbreath::gi::spawn(pool.clone()).await;
     update::star_rail_res::spawn().await;
     update::scores::spawn(pool.clone()).await;
+    update::gi_profiles::spawn(pool.clone()).await;
     update::warps_stats::spawn(pool.clone()).await;
     update::signals_stats::spawn(pool.clone()).await;
     update::wishes_stats::spawn(pool.clone()).await;
+    gacha::spawn(pool.clone()).await;
+    account_deletion::spawn(pool.clone()).await;
 
     let pool_data = Data::new(pool.clone());
 
     let session_key = session_key()?;
-    let signing_key = signing_key()?;
-    let signing_key_data = web::Data::new(Mutex::new(signing_key));
+    let signing_keys_data = web::Data::new(signing::SigningKeys::load()?);
+    let rate_limiter_data = web::Data::new(rate_limit::RateLimiter::from_env(pool.clone()));
+    let ip_hash_key_data = web::Data::new(pg_session_store::IpHashKey::load()?);
+    let mailer_data = web::Data::from(mailer::from_env()?);
 
     let openapi = api::openapi();
 
@@ -246,7 +294,13 @@ async fn main() -> anyhow::Result<()> {
         App::new()
             .app_data(web::JsonConfig::default().limit(5 * 1024 * 1024))
             .app_data(pool_data.clone())
-            .app_data(signing_key_data.clone())
+            .app_data(signing_keys_data.clone())
+            .app_data(rate_limiter_data.clone())
+            .app_data(ip_hash_key_data.clone())
+            .app_data(mailer_data.clone())
+            .wrap(from_fn(pg_session_store::track))
+            .wrap(from_fn(rate_limit::rate_limit))
+            .wrap(from_fn(access_tokens::authenticate))
             .wrap(Cors::permissive())
             .wrap(Compress::default())
             .wrap(if cfg!(debug_assertions) {
@@ -294,22 +348,3 @@ fn session_key() -> anyhow::Result<actix_web::cookie::Key> {
 
     Ok(key)
 }
-
-fn signing_key() -> anyhow::Result<ed25519_dalek::SigningKey> {
-    use ed25519_dalek::{
-        pkcs8::{spki::der::pem::LineEnding, DecodePrivateKey, EncodePrivateKey},
-        SigningKey,
-    };
-
-    let path = Path::new("id_ed25519_sign");
-
-    Ok(if path.exists() {
-        SigningKey::read_pkcs8_pem_file(path)?
-    } else {
-        let mut secret_key = SecretKey::default();
-        rand::rng().fill_bytes(&mut secret_key);
-        let signing_key = SigningKey::from_bytes(&secret_key);
-        signing_key.write_pkcs8_pem_file(path, LineEnding::LF)?;
-        signing_key
-    })
-}
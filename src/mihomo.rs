@@ -1,15 +1,23 @@
-use std::{fs::File, path::PathBuf};
+use std::{
+    fs::{self, File},
+    io::ErrorKind,
+    path::PathBuf,
+};
 
 use chrono::{DateTime, Utc};
-use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use sqlx::PgPool;
+use strum::IntoEnumIterator;
 use utoipa::ToSchema;
 
 use anyhow::Result;
 
-use crate::{database, Language};
+use crate::{
+    database,
+    providers::{self, Showcase},
+    Language,
+};
 
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct Mihomo {
@@ -36,98 +44,114 @@ pub struct SpaceInfo {
     pub achievement_count: i32,
 }
 
-pub async fn get(uid: i32, language: Language, pool: &PgPool) -> Result<Value> {
+pub fn read(uid: i32, language: Language) -> Result<Option<Value>> {
     let path = format!("mihomo/{}_{uid}.br", language.mihomo());
 
-    if PathBuf::from(&path).exists() {
-        let decompressor = brotli::Decompressor::new(File::open(&path)?, 4096);
-
-        Ok(serde_json::from_reader(decompressor)?)
-    } else {
-        update_and_get(uid, language, pool).await
+    if !PathBuf::from(&path).exists() {
+        return Ok(None);
     }
-}
 
-pub async fn update_and_get(uid: i32, language: Language, pool: &PgPool) -> Result<Value> {
-    let now = Utc::now();
+    let decompressor = brotli::Decompressor::new(File::open(&path)?, 4096);
 
-    let url = format!(
-        "https://api.mihomo.me/sr_info_parsed/{uid}?lang={}&version=v2",
-        language.mihomo()
-    );
+    Ok(Some(serde_json::from_reader(decompressor)?))
+}
 
-    let mut json: Value = reqwest::get(&url).await?.json().await?;
-    if let Some(o) = json.as_object_mut() {
-        o.insert("updated_at".to_string(), serde_json::to_value(now)?);
+pub fn delete(uid: i32) -> Result<()> {
+    for language in Language::iter() {
+        match fs::remove_file(format!("mihomo/{}_{uid}.br", language.mihomo())) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
     }
 
-    if serde_json::from_value::<Mihomo>(json.clone()).is_ok() {
-        let file = File::create(format!("mihomo/{}_{uid}.br", language.mihomo()))?;
-
-        let writer = brotli::CompressorWriter::new(file, 4096, 4, 22);
+    Ok(())
+}
 
-        serde_json::to_writer(writer, &json)?;
+pub async fn get(uid: i32, language: Language, pool: &PgPool) -> Result<Value> {
+    match read(uid, language)? {
+        Some(json) => Ok(json),
+        None => update_and_get(uid, language, pool).await,
     }
+}
 
-    let mihomo: Mihomo = serde_json::from_value(json.clone())?;
+pub async fn update_and_get(uid: i32, language: Language, pool: &PgPool) -> Result<Value> {
+    let showcase = providers::update(uid, language, pool).await?;
 
-    let re = Regex::new(r"<[^>]*>")?;
+    save(uid, language, showcase, pool).await
+}
 
-    let name = re
-        .replace_all(&mihomo.player.nickname, |_: &Captures| "")
-        .to_string();
-    let region = match uid.to_string().chars().next() {
-        Some('6') => "na",
-        Some('7') => "eu",
-        Some('8') | Some('9') => "asia",
-        _ => "cn",
-    }
-    .to_string();
-    let level = mihomo.player.level;
-    let avatar_icon = mihomo.player.avatar.icon.clone();
-    let signature = re
-        .replace_all(&mihomo.player.signature, |_: &Captures| "")
-        .to_string();
-    let achievement_count = mihomo.player.space_info.achievement_count;
-    let updated_at = mihomo.updated_at;
-    let timestamp = database::achievement_scores::get_timestamp_by_uid(uid, pool)
-        .await
-        .ok()
-        .and_then(|sd| {
-            if sd.achievement_count == achievement_count {
-                Some(sd.timestamp)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(
-            now + match region.as_str() {
-                "na" => chrono::Duration::try_hours(-5).unwrap(),
-                "eu" => chrono::Duration::try_hours(1).unwrap(),
-                _ => chrono::Duration::try_hours(8).unwrap(),
-            },
-        );
-
-    let db_mihomo = database::mihomo::DbMihomo {
-        uid,
-        region,
-        name,
-        level,
-        signature,
-        avatar_icon,
-        achievement_count,
-        updated_at,
+async fn save(uid: i32, language: Language, showcase: Showcase, pool: &PgPool) -> Result<Value> {
+    let Some(json) = showcase.json else {
+        // Enka has no full showcase, so the last one we had or the profile alone has to do
+        return match read(uid, language)? {
+            Some(json) => Ok(json),
+            None => from_db(uid, pool).await,
+        };
     };
 
-    database::mihomo::set(&db_mihomo, pool).await?;
+    let file = File::create(format!("mihomo/{}_{uid}.br", language.mihomo()))?;
 
-    let db_score_achievement = database::achievement_scores::DbScoreAchievement {
-        uid,
-        timestamp,
-        ..Default::default()
-    };
+    let writer = brotli::CompressorWriter::new(file, 4096, 4, 22);
 
-    database::achievement_scores::set(&db_score_achievement, pool).await?;
+    serde_json::to_writer(writer, &json)?;
 
     Ok(json)
 }
+
+async fn from_db(uid: i32, pool: &PgPool) -> Result<Value> {
+    let db_mihomo = database::mihomo::get_one_by_uid(uid, pool).await?;
+
+    Ok(json!({
+        "player": {
+            "uid": uid.to_string(),
+            "nickname": db_mihomo.name,
+            "level": db_mihomo.level,
+            "avatar": {
+                "icon": db_mihomo.avatar_icon,
+            },
+            "signature": db_mihomo.signature,
+            "space_info": {
+                "achievement_count": db_mihomo.achievement_count,
+            },
+        },
+        "characters": [],
+        "updated_at": db_mihomo.updated_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn showcase_without_json_falls_back_to_profile(pool: PgPool) {
+        let uid = 800000003;
+
+        let db_mihomo = database::mihomo::DbMihomo {
+            uid,
+            region: "asia".to_string(),
+            name: "Enka".to_string(),
+            level: 70,
+            signature: "hi".to_string(),
+            avatar_icon: "icon/avatar/1.png".to_string(),
+            achievement_count: 700,
+            updated_at: Utc::now(),
+        };
+        database::mihomo::set(&db_mihomo, &pool).await.unwrap();
+
+        let showcase = Showcase {
+            name: db_mihomo.name,
+            level: db_mihomo.level,
+            signature: db_mihomo.signature,
+            avatar_icon: db_mihomo.avatar_icon,
+            achievement_count: db_mihomo.achievement_count,
+            json: None,
+        };
+
+        let json = save(uid, Language::En, showcase, &pool).await.unwrap();
+
+        let mihomo: Mihomo = serde_json::from_value(json).unwrap();
+        assert_eq!(mihomo.player.nickname, "Enka");
+        assert_eq!(mihomo.player.space_info.achievement_count, 700);
+    }
+}
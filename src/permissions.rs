@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use crate::database;
+
+#[derive(
+    Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, Serialize, Deserialize, ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    DataEditor,
+    Moderator,
+    Superadmin,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, strum::Display, Serialize, Deserialize, ToSchema)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Edit achievement metadata, import the spreadsheets and see hidden achievements
+    EditAchievements,
+    EditBanners,
+    /// See private gacha data and import it for uids that aren't yours
+    AccessAllUids,
+    DeleteUnofficialGacha,
+    /// Mark every achievement completed for a user
+    SelectAll,
+    ViewAuditLog,
+    ManageRoles,
+    /// See background job status and run jobs on demand
+    ManageJobs,
+}
+
+impl Role {
+    pub fn permissions(self) -> &'static [Permission] {
+        match self {
+            Role::DataEditor => &[Permission::EditAchievements, Permission::EditBanners],
+            Role::Moderator => &[
+                Permission::AccessAllUids,
+                Permission::DeleteUnofficialGacha,
+                Permission::ViewAuditLog,
+            ],
+            Role::Superadmin => &[
+                Permission::EditAchievements,
+                Permission::EditBanners,
+                Permission::AccessAllUids,
+                Permission::DeleteUnofficialGacha,
+                Permission::SelectAll,
+                Permission::ViewAuditLog,
+                Permission::ManageRoles,
+                Permission::ManageJobs,
+            ],
+        }
+    }
+}
+
+pub async fn roles(username: &str, pool: &PgPool) -> Result<Vec<Role>> {
+    Ok(database::users_roles::get_by_username(username, pool)
+        .await?
+        .iter()
+        .filter_map(|role| Role::from_str(role).ok())
+        .collect())
+}
+
+pub async fn has(username: &str, permission: Permission, pool: &PgPool) -> Result<bool> {
+    Ok(roles(username, pool)
+        .await?
+        .iter()
+        .any(|role| role.permissions().contains(&permission)))
+}
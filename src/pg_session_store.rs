@@ -1,12 +1,26 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fs, net::IpAddr, path::Path, str::FromStr};
 
-use actix_session::storage::{LoadError, SaveError, SessionKey, SessionStore, UpdateError};
-use actix_web::cookie::time::Duration;
+use actix_session::{
+    storage::{LoadError, SaveError, SessionKey, SessionStore, UpdateError},
+    SessionExt,
+};
+use actix_web::{
+    body::MessageBody,
+    cookie::time::Duration,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header,
+    middleware::Next,
+    web, Error,
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
 use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::database;
+use crate::{database, rate_limit};
 
 pub struct PgSessionStore {
     pool: PgPool,
@@ -20,21 +34,87 @@ impl PgSessionStore {
 
 type SessionState = HashMap<String, String>;
 
+/// Set instead of `username` while a login waits for its second factor
+pub const PENDING_USERNAME: &str = "pending_username";
+
+/// Set on load so handlers can tell which of the users sessions is the current one
+pub const SESSION_UUID: &str = "session_uuid";
+
+const USER_AGENT: &str = "user_agent";
+const IP_HASH: &str = "ip_hash";
+
+// A login has this long to finish the second factor
+const PENDING_TTL: chrono::Duration = chrono::Duration::minutes(10);
+
+const LAST_SEEN_INTERVAL: chrono::Duration = chrono::Duration::minutes(5);
+
+fn get(session_state: &SessionState, key: &str) -> Option<String> {
+    serde_json::from_str(session_state.get(key)?).ok()
+}
+
+fn username(session_state: &SessionState) -> anyhow::Result<(String, bool)> {
+    match (
+        get(session_state, "username"),
+        get(session_state, PENDING_USERNAME),
+    ) {
+        (Some(username), _) => Ok((username, false)),
+        (None, Some(username)) => Ok((username, true)),
+        (None, None) => Err(anyhow::anyhow!("Session has no username")),
+    }
+}
+
+fn expiry(ttl: &Duration, pending: bool) -> chrono::DateTime<Utc> {
+    if pending {
+        Utc::now() + PENDING_TTL
+    } else {
+        Utc::now() + chrono::Duration::try_seconds(ttl.whole_seconds()).unwrap()
+    }
+}
+
 impl SessionStore for PgSessionStore {
     async fn load(&self, session_key: &SessionKey) -> Result<Option<SessionState>, LoadError> {
         let uuid = Uuid::from_str(session_key.as_ref())
             .map_err(anyhow::Error::new)
             .map_err(LoadError::Other)?;
 
-        let db_session = database::sessions::get_one_by_uuid(uuid, &self.pool)
+        // Expired or revoked
+        let Some(db_session) = database::sessions::get_one_by_uuid(uuid, &self.pool)
             .await
-            .map_err(LoadError::Deserialization)?;
+            .map_err(LoadError::Deserialization)?
+        else {
+            return Ok(None);
+        };
+
+        let key = if db_session.pending {
+            PENDING_USERNAME
+        } else {
+            "username"
+        };
+
+        // Runs on every request, so last seen only moves every few minutes
+        if Utc::now() - db_session.last_seen > LAST_SEEN_INTERVAL {
+            database::sessions::update_last_seen_by_uuid(uuid, &self.pool)
+                .await
+                .map_err(LoadError::Other)?;
+        }
 
         let mut session_state = HashMap::new();
-        session_state.insert(
-            "username".to_string(),
-            format!("\"{}\"", db_session.username),
-        );
+
+        for (key, value) in [
+            (key, Some(db_session.username)),
+            (SESSION_UUID, Some(uuid.to_string())),
+            (USER_AGENT, db_session.user_agent),
+            (IP_HASH, db_session.ip_hash),
+        ] {
+            if let Some(value) = value {
+                session_state.insert(
+                    key.to_string(),
+                    serde_json::to_string(&value)
+                        .map_err(anyhow::Error::new)
+                        .map_err(LoadError::Other)?,
+                );
+            }
+        }
 
         Ok(Some(session_state))
     }
@@ -46,19 +126,22 @@ impl SessionStore for PgSessionStore {
     ) -> Result<SessionKey, SaveError> {
         let uuid = Uuid::new_v4();
 
-        let username = &session_state["username"];
-        let username = username[1..username.len() - 1].to_string();
+        let (username, pending) = username(&session_state).map_err(SaveError::Other)?;
 
-        database::sessions::delete_oldest_by_username(&username, &self.pool)
+        database::sessions::delete_oldest_by_username(&username, pending, &self.pool)
             .await
             .map_err(SaveError::Other)?;
 
-        let expiry = Utc::now() + chrono::Duration::try_seconds(ttl.whole_seconds()).unwrap();
+        let expiry = expiry(ttl, pending);
 
         let db_session = database::sessions::DbSession {
             uuid,
             username,
             expiry,
+            pending,
+            user_agent: get(&session_state, USER_AGENT),
+            ip_hash: get(&session_state, IP_HASH),
+            last_seen: Utc::now(),
         };
 
         database::sessions::set(&db_session, &self.pool)
@@ -81,20 +164,30 @@ impl SessionStore for PgSessionStore {
             .map_err(anyhow::Error::new)
             .map_err(UpdateError::Other)?;
 
-        let username = &session_state["username"];
-        let username = username[1..username.len() - 1].to_string();
+        let (username, pending) = username(&session_state).map_err(UpdateError::Other)?;
 
-        let expiry = Utc::now() + chrono::Duration::try_seconds(ttl.whole_seconds()).unwrap();
+        let expiry = expiry(ttl, pending);
 
         let db_session = database::sessions::DbSession {
             uuid,
             username,
             expiry,
+            pending,
+            user_agent: get(&session_state, USER_AGENT),
+            ip_hash: get(&session_state, IP_HASH),
+            last_seen: Utc::now(),
         };
 
-        database::sessions::set(&db_session, &self.pool)
+        // Revoked while this request was running, so it must not be written back. The cookie
+        // still points at the deleted row and the next request starts without a session
+        if !database::sessions::update_by_uuid(&db_session, &self.pool)
             .await
-            .map_err(UpdateError::Other)?;
+            .map_err(UpdateError::Other)?
+        {
+            return Err(UpdateError::Other(anyhow::anyhow!(
+                "Session does not exist"
+            )));
+        }
 
         Ok(session_key)
     }
@@ -115,3 +208,67 @@ impl SessionStore for PgSessionStore {
         database::sessions::delete_by_uuid(uuid, &self.pool).await
     }
 }
+
+/// Secret for the ip hashes, without it every IPv4 hash could be reversed by hashing all addresses
+pub struct IpHashKey(Vec<u8>);
+
+impl IpHashKey {
+    pub fn load() -> anyhow::Result<Self> {
+        let path = Path::new("ip_hash_key");
+
+        let key = if path.exists() {
+            BASE64_STANDARD.decode(fs::read(path)?)?
+        } else {
+            let mut key = vec![0; 32];
+            rand::rng().fill_bytes(&mut key);
+            fs::write(path, BASE64_STANDARD.encode(&key))?;
+            key
+        };
+
+        Ok(Self(key))
+    }
+
+    fn hash(&self, ip: IpAddr) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).unwrap();
+        mac.update(ip.to_string().as_bytes());
+
+        format!("{:x}", mac.finalize().into_bytes())
+    }
+}
+
+/// Records the user agent and a keyed hash of the ip of logged in sessions
+pub async fn track(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let user_agent: Option<String> = req
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|user_agent| user_agent.to_str().ok())
+        .map(|user_agent| user_agent.chars().take(256).collect());
+
+    let ip_hash = req
+        .app_data::<web::Data<IpHashKey>>()
+        .zip(rate_limit::client_ip(req.request()))
+        .map(|(ip_hash_key, ip)| ip_hash_key.hash(ip));
+
+    let response = next.call(req).await?;
+
+    let session = response.request().get_session();
+
+    let logged_in = matches!(session.get::<String>("username"), Ok(Some(_)))
+        || matches!(session.get::<String>(PENDING_USERNAME), Ok(Some(_)));
+
+    if logged_in {
+        // Only insert changes, every insert makes the store write the session
+        for (key, value) in [(USER_AGENT, user_agent), (IP_HASH, ip_hash)] {
+            if let Some(value) = value {
+                if session.get::<String>(key).ok().flatten().as_ref() != Some(&value) {
+                    session.insert(key, value)?;
+                }
+            }
+        }
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use futures::future::BoxFuture;
+use serde::Deserialize;
+
+use super::{ProfileProvider, Showcase};
+use crate::Language;
+
+pub struct Enka;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnkaHsr {
+    detail_info: DetailInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetailInfo {
+    nickname: String,
+    level: i32,
+    signature: Option<String>,
+    head_icon: i32,
+    record_info: RecordInfo,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordInfo {
+    achievement_count: i32,
+}
+
+impl ProfileProvider for Enka {
+    fn name(&self) -> &'static str {
+        "enka"
+    }
+
+    fn fetch(&self, uid: i32, _: Language) -> BoxFuture<'_, Result<Showcase>> {
+        Box::pin(async move {
+            let json =
+                crate::enka::fetch(&format!("https://enka.network/api/hsr/uid/{uid}?info")).await?;
+
+            let enka: EnkaHsr = serde_json::from_value(json)?;
+
+            Ok(Showcase {
+                name: enka.detail_info.nickname,
+                level: enka.detail_info.level,
+                signature: enka.detail_info.signature.unwrap_or_default(),
+                avatar_icon: format!("icon/avatar/{}.png", enka.detail_info.head_icon),
+                achievement_count: enka.detail_info.record_info.achievement_count,
+                json: None,
+            })
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use serde_json::Value;
+
+use super::{ProfileProvider, Showcase};
+use crate::Language;
+
+pub struct Mihomo;
+
+impl ProfileProvider for Mihomo {
+    fn name(&self) -> &'static str {
+        "mihomo"
+    }
+
+    fn fetch(&self, uid: i32, language: Language) -> BoxFuture<'_, Result<Showcase>> {
+        Box::pin(async move {
+            let url = format!(
+                "https://api.mihomo.me/sr_info_parsed/{uid}?lang={}&version=v2",
+                language.mihomo()
+            );
+
+            let json: Value = reqwest::get(&url).await?.json().await?;
+
+            showcase(json)
+        })
+    }
+}
+
+pub fn showcase(mut json: Value) -> Result<Showcase> {
+    if let Some(o) = json.as_object_mut() {
+        o.insert("updated_at".to_string(), serde_json::to_value(Utc::now())?);
+    }
+
+    let mihomo: crate::mihomo::Mihomo = serde_json::from_value(json.clone())?;
+
+    Ok(Showcase {
+        name: mihomo.player.nickname,
+        level: mihomo.player.level,
+        signature: mihomo.player.signature,
+        avatar_icon: mihomo.player.avatar.icon,
+        achievement_count: mihomo.player.space_info.achievement_count,
+        json: Some(json),
+    })
+}
@@ -0,0 +1,347 @@
+mod enka;
+mod mihomo;
+mod stub;
+
+use std::{
+    env,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use actix_web::rt;
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::BoxFuture;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use sqlx::PgPool;
+
+use crate::{database, Language};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const FAILURES: u32 = 5;
+const COOLDOWN: Duration = Duration::from_secs(60);
+
+lazy_static::lazy_static! {
+    static ref PROVIDERS: Providers = Providers::from_env();
+}
+
+pub struct Showcase {
+    pub name: String,
+    pub level: i32,
+    pub signature: String,
+    pub avatar_icon: String,
+    pub achievement_count: i32,
+    /// Full showcase in the Mihomo format, if the provider has one
+    pub json: Option<Value>,
+}
+
+pub trait ProfileProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn fetch(&self, uid: i32, language: Language) -> BoxFuture<'_, Result<Showcase>>;
+}
+
+struct Provider {
+    provider: Box<dyn ProfileProvider>,
+    timeout: Duration,
+    breaker: Breaker,
+}
+
+#[derive(Default)]
+struct Breaker {
+    failures: AtomicU32,
+    open_until: Mutex<Option<Instant>>,
+}
+
+impl Breaker {
+    fn closed(&self) -> bool {
+        self.open_until
+            .lock()
+            .unwrap()
+            .is_none_or(|open_until| Instant::now() >= open_until)
+    }
+
+    fn success(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        *self.open_until.lock().unwrap() = None;
+    }
+
+    fn failure(&self) {
+        if self.failures.fetch_add(1, Ordering::Relaxed) + 1 >= FAILURES {
+            *self.open_until.lock().unwrap() = Some(Instant::now() + COOLDOWN);
+        }
+    }
+}
+
+struct Providers(Vec<Provider>);
+
+impl Providers {
+    // PROFILE_PROVIDERS=mihomo:10,enka:5 where the number is an optional timeout in seconds
+    fn from_env() -> Self {
+        let config = env::var("PROFILE_PROVIDERS").unwrap_or_else(|_| "mihomo,enka".to_string());
+
+        let mut providers = Vec::new();
+
+        for entry in config.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (name, timeout) = match entry.split_once(':') {
+                Some((name, timeout)) => (name, timeout.parse().ok().map(Duration::from_secs)),
+                None => (entry, None),
+            };
+
+            let provider: Box<dyn ProfileProvider> = match name {
+                "mihomo" => Box::new(mihomo::Mihomo),
+                "enka" => Box::new(enka::Enka),
+                "stub" => Box::new(stub::Stub::from_env()),
+                _ => {
+                    error!("Unknown profile provider {name}");
+                    continue;
+                }
+            };
+
+            providers.push(Provider {
+                provider,
+                timeout: timeout.unwrap_or(TIMEOUT),
+                breaker: Breaker::default(),
+            });
+        }
+
+        Self(providers)
+    }
+
+    async fn fetch(&self, uid: i32, language: Language) -> Result<Showcase> {
+        let mut errors = Vec::new();
+
+        for provider in &self.0 {
+            let name = provider.provider.name();
+
+            if !provider.breaker.closed() {
+                errors.push(format!("{name}: circuit open"));
+                continue;
+            }
+
+            match rt::time::timeout(provider.timeout, provider.provider.fetch(uid, language)).await
+            {
+                Ok(Ok(showcase)) => {
+                    provider.breaker.success();
+
+                    return Ok(showcase);
+                }
+                Ok(Err(e)) => {
+                    provider.breaker.failure();
+                    errors.push(format!("{name}: {e}"));
+                }
+                Err(_) => {
+                    provider.breaker.failure();
+                    errors.push(format!("{name}: timed out"));
+                }
+            }
+        }
+
+        Err(anyhow::anyhow!(
+            "No profile provider succeeded for {uid} ({})",
+            errors.join(", ")
+        ))
+    }
+}
+
+pub async fn update(uid: i32, language: Language, pool: &PgPool) -> Result<Showcase> {
+    update_with(&PROVIDERS, uid, language, pool).await
+}
+
+async fn update_with(
+    providers: &Providers,
+    uid: i32,
+    language: Language,
+    pool: &PgPool,
+) -> Result<Showcase> {
+    let now = Utc::now();
+
+    let mut showcase = providers.fetch(uid, language).await?;
+
+    if showcase.json.is_none() {
+        if let Some(mihomo) = crate::mihomo::read(uid, Language::En)?
+            .and_then(|json| serde_json::from_value::<crate::mihomo::Mihomo>(json).ok())
+        {
+            showcase.avatar_icon = mihomo.player.avatar.icon;
+        }
+    }
+
+    let re = Regex::new(r"<[^>]*>")?;
+
+    let name = re
+        .replace_all(&showcase.name, |_: &Captures| "")
+        .to_string();
+    let region = match uid.to_string().chars().next() {
+        Some('6') => "na",
+        Some('7') => "eu",
+        Some('8') | Some('9') => "asia",
+        _ => "cn",
+    }
+    .to_string();
+    let level = showcase.level;
+    let avatar_icon = showcase.avatar_icon.clone();
+    let signature = re
+        .replace_all(&showcase.signature, |_: &Captures| "")
+        .to_string();
+    let achievement_count = showcase.achievement_count;
+    let updated_at = now;
+    let timestamp = database::achievement_scores::get_timestamp_by_uid(uid, pool)
+        .await
+        .ok()
+        .and_then(|sd| {
+            if sd.achievement_count == achievement_count {
+                Some(sd.timestamp)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(
+            now + match region.as_str() {
+                "na" => chrono::Duration::try_hours(-5).unwrap(),
+                "eu" => chrono::Duration::try_hours(1).unwrap(),
+                _ => chrono::Duration::try_hours(8).unwrap(),
+            },
+        );
+
+    let db_mihomo = database::mihomo::DbMihomo {
+        uid,
+        region,
+        name,
+        level,
+        signature,
+        avatar_icon,
+        achievement_count,
+        updated_at,
+    };
+
+    database::mihomo::set(&db_mihomo, pool).await?;
+
+    let db_score_achievement = database::achievement_scores::DbScoreAchievement {
+        uid,
+        timestamp,
+        ..Default::default()
+    };
+
+    database::achievement_scores::set(&db_score_achievement, pool).await?;
+
+    Ok(showcase)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    struct Failing;
+
+    impl ProfileProvider for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn fetch(&self, _: i32, _: Language) -> BoxFuture<'_, Result<Showcase>> {
+            Box::pin(async { Err(anyhow::anyhow!("unreachable")) })
+        }
+    }
+
+    fn providers(providers: Vec<Box<dyn ProfileProvider>>) -> Providers {
+        Providers(
+            providers
+                .into_iter()
+                .map(|provider| Provider {
+                    provider,
+                    timeout: TIMEOUT,
+                    breaker: Breaker::default(),
+                })
+                .collect(),
+        )
+    }
+
+    fn stub(uid: i32) -> Box<dyn ProfileProvider> {
+        let dir = env::temp_dir().join(format!("stardb_stub_{uid}"));
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("{uid}.json")),
+            serde_json::json!({
+                "player": {
+                    "uid": uid.to_string(),
+                    "nickname": "<b>Stub</b>",
+                    "level": 70,
+                    "avatar": { "icon": "icon/avatar/1.png" },
+                    "signature": "hi",
+                    "space_info": { "achievement_count": 700 },
+                },
+                "characters": [],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        Box::new(stub::Stub::new(dir))
+    }
+
+    #[sqlx::test]
+    async fn stub_updates_profile_and_leaderboard(pool: PgPool) {
+        let uid = 800000001;
+
+        let providers = providers(vec![Box::new(Failing), stub(uid)]);
+
+        let showcase = update_with(&providers, uid, Language::En, &pool)
+            .await
+            .unwrap();
+        assert!(showcase.json.is_some());
+
+        let db_mihomo = database::mihomo::get_one_by_uid(uid, &pool).await.unwrap();
+        assert_eq!(db_mihomo.name, "Stub");
+        assert_eq!(db_mihomo.region, "asia");
+        assert_eq!(db_mihomo.achievement_count, 700);
+
+        let score = database::achievement_scores::get_by_uid(uid, &pool)
+            .await
+            .unwrap();
+        assert_eq!(score.global_rank, Some(1));
+        assert_eq!(score.regional_rank, Some(1));
+    }
+
+    #[sqlx::test]
+    async fn unchanged_count_keeps_timestamp(pool: PgPool) {
+        let uid = 800000002;
+
+        let providers = providers(vec![stub(uid)]);
+
+        update_with(&providers, uid, Language::En, &pool)
+            .await
+            .unwrap();
+        let first = database::achievement_scores::get_timestamp_by_uid(uid, &pool)
+            .await
+            .unwrap();
+
+        update_with(&providers, uid, Language::En, &pool)
+            .await
+            .unwrap();
+        let second = database::achievement_scores::get_timestamp_by_uid(uid, &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(first.timestamp, second.timestamp);
+    }
+
+    #[actix_web::test]
+    async fn breaker_skips_failing_provider() {
+        let providers = providers(vec![Box::new(Failing)]);
+
+        for _ in 0..FAILURES {
+            let e = providers.fetch(1, Language::En).await.err().unwrap();
+            assert!(e.to_string().contains("failing: unreachable"));
+        }
+
+        let e = providers.fetch(1, Language::En).await.err().unwrap();
+        assert!(e.to_string().contains("failing: circuit open"));
+    }
+}
@@ -0,0 +1,46 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+
+use super::{ProfileProvider, Showcase};
+use crate::Language;
+
+/// Replays canned Mihomo responses from PROFILE_STUB_DIR (default stub/), as {uid}_{lang}.json or {uid}.json
+pub struct Stub {
+    dir: PathBuf,
+}
+
+impl Stub {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            env::var("PROFILE_STUB_DIR")
+                .unwrap_or_else(|_| "stub".to_string())
+                .into(),
+        )
+    }
+}
+
+impl ProfileProvider for Stub {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    fn fetch(&self, uid: i32, language: Language) -> BoxFuture<'_, Result<Showcase>> {
+        Box::pin(async move {
+            let path = [
+                self.dir.join(format!("{uid}_{}.json", language.mihomo())),
+                self.dir.join(format!("{uid}.json")),
+            ]
+            .into_iter()
+            .find(|path| path.exists())
+            .ok_or_else(|| anyhow::anyhow!("No stub for {uid}"))?;
+
+            super::mihomo::showcase(serde_json::from_str(&fs::read_to_string(path)?)?)
+        })
+    }
+}
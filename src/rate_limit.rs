@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    env,
+    net::{IpAddr, SocketAddr},
+    sync::Mutex,
+    time::Instant,
+};
+
+use actix_session::SessionExt;
+use actix_web::{
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header,
+    middleware::Next,
+    web, Error, HttpRequest, HttpResponse,
+};
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::database;
+
+struct Group {
+    name: &'static str,
+    method: &'static str,
+    prefixes: &'static [&'static str],
+    capacity: f64,
+    // Tokens per second
+    rate: f64,
+}
+
+// Routes that call out to Mihomo/Enka/HoYoverse, hash passwords, send mail or are CPU bound
+const GROUPS: &[Group] = &[
+    Group {
+        name: "showcase",
+        method: "PUT",
+        prefixes: &[
+            "/api/mihomo/",
+            "/api/scores/achievements/",
+            "/api/gi/scores/achievements/",
+            "/api/zzz/scores/achievements/",
+            "/api/pages/profiles/",
+            "/api/pages/gi/profiles/",
+            "/api/pages/zzz/profiles/",
+        ],
+        capacity: 5.0,
+        rate: 1.0 / 30.0,
+    },
+    Group {
+        name: "import",
+        method: "POST",
+        prefixes: &[
+            "/api/warps-import",
+            "/api/gi/wishes-import",
+            "/api/zzz/signals-import",
+            "/api/uigf-import",
+            "/api/srgf-warps-import",
+            "/api/pom-warps-import/",
+            "/api/srs-warps-import/",
+            "/api/gi/paimon-wishes-import",
+            "/api/zzz/rng-signals-import",
+        ],
+        capacity: 5.0,
+        rate: 1.0 / 60.0,
+    },
+    Group {
+        name: "simulate",
+        method: "GET",
+        prefixes: &["/api/simulate/"],
+        capacity: 10.0,
+        rate: 1.0 / 10.0,
+    },
+    Group {
+        name: "auth",
+        method: "POST",
+        prefixes: &[
+            "/api/users/auth/login",
+            "/api/users/auth/register",
+            "/api/users/auth/request-token",
+            "/api/users/auth/request-password-reset",
+            "/api/users/auth/reset-password",
+            "/api/users/auth/verify-email",
+            "/api/users/me/email/verify",
+        ],
+        capacity: 10.0,
+        rate: 1.0 / 30.0,
+    },
+    Group {
+        name: "password",
+        method: "PUT",
+        prefixes: &["/api/users/me/password", "/api/users/me/email"],
+        capacity: 5.0,
+        rate: 1.0 / 60.0,
+    },
+    // The 2fa groups share one bucket since the key is the name
+    Group {
+        name: "2fa",
+        method: "PUT",
+        prefixes: &["/api/users/me/2fa"],
+        capacity: 10.0,
+        rate: 1.0 / 30.0,
+    },
+    Group {
+        name: "2fa",
+        method: "POST",
+        prefixes: &["/api/users/me/2fa/recovery-codes"],
+        capacity: 10.0,
+        rate: 1.0 / 30.0,
+    },
+    Group {
+        name: "2fa",
+        method: "DELETE",
+        prefixes: &["/api/users/me/2fa"],
+        capacity: 10.0,
+        rate: 1.0 / 30.0,
+    },
+];
+
+// Memory buckets are pruned once there are this many keys
+const MAX_BUCKETS: usize = 100000;
+
+pub enum RateLimiter {
+    Memory(Mutex<HashMap<String, (f64, Instant)>>),
+    Postgres(PgPool),
+}
+
+impl RateLimiter {
+    // RATE_LIMIT_STORE=postgres shares the buckets between instances
+    pub fn from_env(pool: PgPool) -> Self {
+        match env::var("RATE_LIMIT_STORE").as_deref() {
+            Ok("postgres") => RateLimiter::Postgres(pool),
+            _ => RateLimiter::Memory(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the seconds until the next token when the bucket is empty
+    async fn take(&self, group: &Group, key: &str) -> Result<Option<u64>> {
+        let key = format!("{}:{key}", group.name);
+
+        let (tokens, allowed) = match self {
+            RateLimiter::Memory(buckets) => {
+                let mut buckets = buckets.lock().unwrap();
+
+                let now = Instant::now();
+
+                if buckets.len() >= MAX_BUCKETS {
+                    buckets.retain(|_, (tokens, updated_at)| {
+                        *tokens + (now - *updated_at).as_secs_f64() * group.rate < group.capacity
+                    });
+                }
+
+                let (tokens, updated_at) = buckets.entry(key).or_insert((group.capacity, now));
+
+                *tokens = group
+                    .capacity
+                    .min(*tokens + (now - *updated_at).as_secs_f64() * group.rate);
+                *updated_at = now;
+
+                let allowed = *tokens >= 1.0;
+
+                if allowed {
+                    *tokens -= 1.0;
+                }
+
+                (*tokens, allowed)
+            }
+            RateLimiter::Postgres(pool) => {
+                if rand::random_ratio(1, 1000) {
+                    database::rate_limits::delete_stale(pool).await?;
+                }
+
+                let rate_limit =
+                    database::rate_limits::take(&key, group.capacity, group.rate, pool).await?;
+
+                (rate_limit.tokens, rate_limit.allowed)
+            }
+        };
+
+        Ok((!allowed).then(|| ((1.0 - tokens) / group.rate).ceil() as u64))
+    }
+}
+
+lazy_static::lazy_static! {
+    // TRUSTED_PROXIES=10.0.0.1,10.0.0.2 may set the client IP through Forwarded/X-Forwarded-For
+    static ref TRUSTED_PROXIES: Vec<IpAddr> = env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect();
+}
+
+/// The peer address, or the forwarded one if the peer is a trusted proxy
+pub fn client_ip(req: &HttpRequest) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+
+    if !TRUSTED_PROXIES.contains(&peer) {
+        return Some(peer);
+    }
+
+    req.connection_info()
+        .realip_remote_addr()
+        .and_then(|ip| {
+            ip.parse::<SocketAddr>()
+                .map(|addr| addr.ip())
+                .or_else(|_| ip.parse())
+                .ok()
+        })
+        .or(Some(peer))
+}
+
+// Keyed by session username, then the private API key, then client IP
+fn key(req: &ServiceRequest) -> String {
+    if let Ok(Some(username)) = req.get_session().get::<String>("username") {
+        return format!("user:{username}");
+    }
+
+    let api_key = env::var("API_KEY").ok();
+
+    if api_key.is_some()
+        && req.headers().get("x-api-key").map(|h| h.as_bytes())
+            == api_key.as_deref().map(str::as_bytes)
+    {
+        return "key:private".to_string();
+    }
+
+    format!(
+        "ip:{}",
+        client_ip(req.request())
+            .map(|ip| ip.to_string())
+            .unwrap_or_default()
+    )
+}
+
+pub async fn rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let path = req.path();
+    let method = req.method().as_str();
+
+    let group = GROUPS.iter().find(|group| {
+        group.method == method && group.prefixes.iter().any(|prefix| path.starts_with(prefix))
+    });
+
+    if let (Some(group), Some(rate_limiter)) = (group, req.app_data::<web::Data<RateLimiter>>()) {
+        match rate_limiter.take(group, &key(&req)).await {
+            Ok(Some(retry_after)) => {
+                let response = HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+                    .finish();
+
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+            Ok(None) => {}
+            Err(e) => error!("Rate limit for {} failed with {e}", group.name),
+        }
+    }
+
+    Ok(next.call(req).await?.map_into_left_body())
+}
@@ -0,0 +1,215 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use actix_web::rt::{self, Runtime};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    future, StreamExt,
+};
+
+const BACKOFF_BASE: Duration = Duration::from_secs(10);
+const BACKOFF_MAX: Duration = Duration::from_secs(60 * 60);
+
+lazy_static::lazy_static! {
+    static ref JOBS: Mutex<Vec<Arc<Job>>> = Mutex::new(Vec::new());
+}
+
+#[derive(Clone, Copy)]
+pub enum Schedule {
+    /// From the start of one run to the start of the next. Zero runs back to back
+    Every(Duration),
+    /// From the end of one run to the start of the next
+    After(Duration),
+}
+
+#[derive(Clone, Default)]
+pub struct JobStatus {
+    pub running: bool,
+    pub last_started_at: Option<DateTime<Utc>>,
+    /// Seconds
+    pub last_duration: Option<f64>,
+    pub last_succeeded_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    /// Failures since the last success
+    pub failures: u32,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+pub struct Job {
+    pub name: &'static str,
+    pub schedule: Schedule,
+    status: Mutex<JobStatus>,
+    trigger: UnboundedSender<()>,
+}
+
+impl Job {
+    pub fn status(&self) -> JobStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Runs the job now. Returns false if it is already running
+    pub fn trigger(&self) -> bool {
+        if self.status.lock().unwrap().running {
+            return false;
+        }
+
+        self.trigger.unbounded_send(()).is_ok()
+    }
+}
+
+pub fn jobs() -> Vec<Arc<Job>> {
+    JOBS.lock().unwrap().clone()
+}
+
+pub fn get(name: &str) -> Option<Arc<Job>> {
+    JOBS.lock()
+        .unwrap()
+        .iter()
+        .find(|job| job.name == name)
+        .cloned()
+}
+
+/// Runs `run` on its own thread according to `schedule`, starting right away. Failed or
+/// panicked runs are retried with exponential backoff instead
+pub fn register<F, Fut>(name: &'static str, schedule: Schedule, mut run: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + 'static,
+{
+    let (trigger, mut triggered) = mpsc::unbounded();
+
+    let job = Arc::new(Job {
+        name,
+        schedule,
+        status: Mutex::new(JobStatus::default()),
+        trigger,
+    });
+
+    JOBS.lock().unwrap().push(job.clone());
+
+    std::thread::spawn(move || {
+        let rt = Runtime::new().unwrap();
+
+        rt.block_on(async move {
+            let mut wait = Duration::ZERO;
+
+            loop {
+                job.status.lock().unwrap().next_run_at =
+                    Some(Utc::now() + chrono::Duration::from_std(wait).unwrap_or_default());
+
+                // Triggers from before this point are already served by the last run
+                while triggered.try_recv().is_ok() {}
+
+                if !wait.is_zero() {
+                    let sleep = Box::pin(rt::time::sleep(wait));
+
+                    future::select(sleep, triggered.next()).await;
+                }
+
+                {
+                    let mut status = job.status.lock().unwrap();
+                    status.running = true;
+                    status.last_started_at = Some(Utc::now());
+                    status.next_run_at = None;
+                }
+
+                let start = Instant::now();
+
+                // Spawned so a panic fails the run instead of the whole scheduler
+                let result = rt::spawn(run()).await.unwrap_or_else(|e| Err(e.into()));
+
+                let elapsed = start.elapsed();
+
+                let mut status = job.status.lock().unwrap();
+                status.running = false;
+                status.last_duration = Some(elapsed.as_secs_f64());
+
+                wait = match result {
+                    Ok(()) => {
+                        info!("{name} succeeded in {}s", elapsed.as_secs_f64());
+
+                        status.last_succeeded_at = Some(Utc::now());
+                        status.last_error = None;
+                        status.failures = 0;
+
+                        match schedule {
+                            Schedule::Every(interval) => interval.saturating_sub(elapsed),
+                            Schedule::After(delay) => delay,
+                        }
+                    }
+                    Err(e) => {
+                        error!("{name} failed with {e} in {}s", elapsed.as_secs_f64());
+
+                        status.last_error = Some(e.to_string());
+                        status.failures += 1;
+
+                        BACKOFF_BASE
+                            .saturating_mul(2u32.saturating_pow(status.failures - 1))
+                            .min(BACKOFF_MAX)
+                    }
+                };
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    async fn panics() -> Result<()> {
+        panic!("boom")
+    }
+
+    fn wait_for(name: &str, done: impl Fn(&JobStatus) -> bool) -> JobStatus {
+        for _ in 0..100 {
+            let status = get(name).unwrap().status();
+
+            if done(&status) {
+                return status;
+            }
+
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        panic!("{name} did not finish");
+    }
+
+    #[test]
+    fn panic_fails_the_run() {
+        register("panics", Schedule::Every(Duration::from_secs(60)), panics);
+
+        let status = wait_for("panics", |status| status.failures == 1 && !status.running);
+        assert!(status.last_error.unwrap().contains("boom"));
+    }
+
+    #[test]
+    fn blocking_job_does_not_stall_others() {
+        register(
+            "blocks",
+            Schedule::Every(Duration::from_secs(60)),
+            || async {
+                thread::sleep(Duration::from_secs(30));
+                Ok(())
+            },
+        );
+
+        wait_for("blocks", |status| status.running);
+
+        register(
+            "succeeds",
+            Schedule::Every(Duration::from_secs(60)),
+            || async { Ok(()) },
+        );
+
+        let status = wait_for("succeeds", |status| status.last_succeeded_at.is_some());
+        assert_eq!(status.failures, 0);
+    }
+}
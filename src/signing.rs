@@ -0,0 +1,111 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use ed25519_dalek::{
+    pkcs8::{
+        spki::der::pem::LineEnding, DecodePrivateKey, DecodePublicKey, EncodePrivateKey,
+        EncodePublicKey,
+    },
+    SecretKey, Signature, Signer, SigningKey, Verifier, VerifyingKey,
+};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub struct SigningKeys {
+    signing_key: SigningKey,
+    kid: String,
+    verifying_keys: Vec<(String, VerifyingKey)>,
+}
+
+impl SigningKeys {
+    // The active key lives in id_ed25519_sign, retired keys (private or public PEM) in signing_keys/
+    pub fn load() -> Result<Self> {
+        let path = Path::new("id_ed25519_sign");
+
+        let signing_key = if path.exists() {
+            SigningKey::read_pkcs8_pem_file(path)?
+        } else {
+            let mut secret_key = SecretKey::default();
+            rand::rng().fill_bytes(&mut secret_key);
+            let signing_key = SigningKey::from_bytes(&secret_key);
+            signing_key.write_pkcs8_pem_file(path, LineEnding::LF)?;
+            signing_key
+        };
+
+        let verifying_key = signing_key.verifying_key();
+        let kid = kid(&verifying_key);
+
+        let mut verifying_keys = vec![(kid.clone(), verifying_key)];
+
+        if let Ok(entries) = fs::read_dir("signing_keys") {
+            let mut paths: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+            paths.sort();
+
+            for path in paths {
+                let verifying_key = match VerifyingKey::read_public_key_pem_file(&path) {
+                    Ok(verifying_key) => verifying_key,
+                    Err(_) => SigningKey::read_pkcs8_pem_file(&path)?.verifying_key(),
+                };
+
+                let kid = self::kid(&verifying_key);
+
+                if verifying_keys.iter().all(|(k, _)| *k != kid) {
+                    verifying_keys.push((kid, verifying_key));
+                }
+            }
+        }
+
+        Ok(Self {
+            signing_key,
+            kid,
+            verifying_keys,
+        })
+    }
+
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.signing_key.sign(message)
+    }
+
+    pub fn verifying_keys(&self) -> &[(String, VerifyingKey)] {
+        &self.verifying_keys
+    }
+
+    pub fn get(&self, kid: &str) -> Option<&VerifyingKey> {
+        self.verifying_keys
+            .iter()
+            .find(|(k, _)| k == kid)
+            .map(|(_, verifying_key)| verifying_key)
+    }
+
+    /// Returns the id of the key that produced the signature. Without a kid every known key is tried.
+    pub fn verify(&self, kid: Option<&str>, message: &[u8], signature: &Signature) -> Option<&str> {
+        self.verifying_keys
+            .iter()
+            .filter(|(k, _)| kid.is_none_or(|kid| k == kid))
+            .find(|(_, verifying_key)| verifying_key.verify(message, signature).is_ok())
+            .map(|(k, _)| k.as_str())
+    }
+}
+
+pub fn x(verifying_key: &VerifyingKey) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(verifying_key.as_bytes())
+}
+
+pub fn pem(verifying_key: &VerifyingKey) -> Result<String> {
+    Ok(verifying_key.to_public_key_pem(LineEnding::LF)?)
+}
+
+// RFC 7638 JWK thumbprint
+fn kid(verifying_key: &VerifyingKey) -> String {
+    let jwk = format!(
+        r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#,
+        x(verifying_key)
+    );
+
+    BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(jwk.as_bytes()))
+}
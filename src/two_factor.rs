@@ -0,0 +1,143 @@
+use anyhow::Result;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::{seq::IndexedRandom, RngCore};
+use sha1::Sha1;
+use sqlx::PgPool;
+
+use crate::{access_tokens, database};
+
+const ISSUER: &str = "StarDB.GG";
+const DIGITS: u32 = 6;
+const PERIOD: i64 = 30;
+// Accept codes from one step before and after to allow for clock drift
+const SKEW: i64 = 1;
+const RECOVERY_CODES: usize = 10;
+
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut s = String::new();
+
+    for chunk in bytes.chunks(5) {
+        let mut buf = [0; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+
+        let n = buf.iter().fold(0u64, |n, &b| n << 8 | b as u64);
+
+        for i in 0..(chunk.len() * 8).div_ceil(5) {
+            s.push(BASE32[(n >> (35 - i * 5) & 31) as usize] as char);
+        }
+    }
+
+    s
+}
+
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    let (mut n, mut bits) = (0u32, 0);
+
+    for c in s.bytes().filter(|&c| c != b'=') {
+        let i = BASE32.iter().position(|&b| b == c.to_ascii_uppercase())?;
+
+        n = n << 5 | i as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0; 20];
+    rand::rng().fill_bytes(&mut bytes);
+
+    base32_encode(&bytes)
+}
+
+/// otpauth:// URI for authenticator apps, usually shown as a QR code
+pub fn uri(username: &str, secret: &str) -> String {
+    let label: String =
+        url::form_urlencoded::byte_serialize(format!("{ISSUER}:{username}").as_bytes()).collect();
+
+    format!(
+        "otpauth://totp/{label}?secret={secret}&issuer={ISSUER}&algorithm=SHA1&digits={DIGITS}&period={PERIOD}"
+    )
+}
+
+fn code(key: &[u8], step: i64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).unwrap();
+    mac.update(&step.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[19] & 0xf) as usize;
+    let n = u32::from_be_bytes(hash[offset..offset + 4].try_into().unwrap()) & 0x7fffffff;
+
+    n % 10u32.pow(DIGITS)
+}
+
+/// Returns the time step the code belongs to, if it is valid right now
+pub fn verify_code(secret: &str, code: &str) -> Option<i64> {
+    let key = base32_decode(secret)?;
+
+    let code: u32 = code.parse().ok()?;
+
+    let step = Utc::now().timestamp() / PERIOD;
+
+    (step - SKEW..=step + SKEW).find(|&step| self::code(&key, step) == code)
+}
+
+pub fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::rng();
+
+    (0..RECOVERY_CODES)
+        .map(|_| {
+            let chars: String = (0..10)
+                .map(|_| *BASE32.choose(&mut rng).unwrap() as char)
+                .collect::<String>()
+                .to_lowercase();
+
+            format!("{}-{}", &chars[..5], &chars[5..])
+        })
+        .collect()
+}
+
+pub fn hash_recovery_code(code: &str) -> String {
+    let code: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+
+    access_tokens::hash(&code)
+}
+
+/// Checks a TOTP code or uses up a recovery code. A TOTP code only works once
+pub async fn verify(username: &str, code: &str, pool: &PgPool) -> Result<bool> {
+    let Some(totp) = database::users_totp::get_one_by_username(username, pool).await? else {
+        return Ok(false);
+    };
+
+    if !totp.enabled {
+        return Ok(false);
+    }
+
+    let code = code.trim();
+
+    if let Some(step) = verify_code(&totp.secret, code) {
+        return database::users_totp::update_last_step_by_username(username, step, pool).await;
+    }
+
+    database::users_recovery_codes::consume(username, &hash_recovery_code(code), pool).await
+}
+
+pub async fn enabled(username: &str, pool: &PgPool) -> Result<bool> {
+    Ok(database::users_totp::get_one_by_username(username, pool)
+        .await?
+        .is_some_and(|totp| totp.enabled))
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use anyhow::Result;
+use async_process::Command;
+use sqlx::PgPool;
+
+use crate::database::{
+    self,
+    changelog_entries::{ChangelogEntryKind, SetAll},
+};
+
+/// The parts of the database a datamine update can change that editors care about
+pub struct Snapshot {
+    /// Achievement id to jades/primogems/polychromes
+    pub achievements: HashMap<i32, i32>,
+    /// (Achievement id, language) to (name, description)
+    pub texts: HashMap<(i32, String), (String, String)>,
+    pub characters: HashSet<i32>,
+    /// Light cones, weapons or W-engines
+    pub items: HashSet<i32>,
+}
+
+/// Commit the datamine repository is checked out at
+pub async fn commit(repository: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(repository)
+        .output()
+        .await?;
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// Stores what changed between the snapshots, if anything. `ids` are the achievements in the
+/// new configs, since achievements that are gone upstream are kept in the database
+pub async fn record(
+    game: &str,
+    commit: &str,
+    before: &Snapshot,
+    after: &Snapshot,
+    ids: &HashSet<i32>,
+    item_kind: ChangelogEntryKind,
+    pool: &PgPool,
+) -> Result<()> {
+    // The first import would list everything as added
+    if before.achievements.is_empty() {
+        return Ok(());
+    }
+
+    // Removed achievements stay in the database and would be listed on every update
+    let removed_before: HashSet<_> = database::changelog_entries::get_ids_by_game_and_kind(
+        game,
+        ChangelogEntryKind::AchievementRemoved,
+        pool,
+    )
+    .await?
+    .into_iter()
+    .collect();
+
+    let mut set_all = SetAll::default();
+
+    let mut push = |kind: ChangelogEntryKind,
+                    id: i32,
+                    language: Option<&str>,
+                    old: Option<String>,
+                    new: Option<String>| {
+        set_all.kind.push(kind.to_string());
+        set_all.id.push(id);
+        set_all.language.push(language.map(ToString::to_string));
+        set_all.before.push(old);
+        set_all.after.push(new);
+    };
+
+    let mut added: Vec<_> = after
+        .achievements
+        .iter()
+        .filter(|(id, _)| !before.achievements.contains_key(id))
+        .collect();
+    added.sort();
+
+    for (&id, reward) in added {
+        push(
+            ChangelogEntryKind::AchievementAdded,
+            id,
+            None,
+            None,
+            Some(reward.to_string()),
+        );
+    }
+
+    let mut removed: Vec<_> = before
+        .achievements
+        .keys()
+        .filter(|id| !ids.contains(id) && !removed_before.contains(id))
+        .collect();
+    removed.sort();
+
+    for &id in removed {
+        push(ChangelogEntryKind::AchievementRemoved, id, None, None, None);
+    }
+
+    let mut rewards: Vec<_> = after
+        .achievements
+        .iter()
+        .filter_map(|(id, reward)| {
+            let old = before.achievements.get(id)?;
+
+            (old != reward).then_some((id, old, reward))
+        })
+        .collect();
+    rewards.sort();
+
+    for (&id, old, new) in rewards {
+        push(
+            ChangelogEntryKind::AchievementReward,
+            id,
+            None,
+            Some(old.to_string()),
+            Some(new.to_string()),
+        );
+    }
+
+    let mut texts: Vec<_> = after
+        .texts
+        .iter()
+        .filter_map(|(key, text)| Some((key, before.texts.get(key)?, text)))
+        .filter(|(_, old, new)| old != new)
+        .collect();
+    texts.sort();
+
+    for ((id, language), (old_name, old_description), (new_name, new_description)) in texts {
+        if old_name != new_name {
+            push(
+                ChangelogEntryKind::AchievementName,
+                *id,
+                Some(language),
+                Some(old_name.clone()),
+                Some(new_name.clone()),
+            );
+        }
+
+        if old_description != new_description {
+            push(
+                ChangelogEntryKind::AchievementDescription,
+                *id,
+                Some(language),
+                Some(old_description.clone()),
+                Some(new_description.clone()),
+            );
+        }
+    }
+
+    for (kind, old, new) in [
+        (
+            ChangelogEntryKind::CharacterAdded,
+            &before.characters,
+            &after.characters,
+        ),
+        (item_kind, &before.items, &after.items),
+    ] {
+        let mut added: Vec<_> = new.difference(old).collect();
+        added.sort();
+
+        for &id in added {
+            push(kind, id, None, None, None);
+        }
+    }
+
+    if set_all.id.is_empty() {
+        return Ok(());
+    }
+
+    let changelog = database::changelogs::set(game, commit, pool).await?;
+
+    database::changelog_entries::set_all(changelog, &set_all, pool).await?;
+
+    info!(
+        "Recorded {} {game} changelog entries for {commit}",
+        set_all.id.len()
+    );
+
+    Ok(())
+}
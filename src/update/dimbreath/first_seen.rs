@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+
+use crate::database::{self, first_seen::FirstSeenKind};
+
+use super::changelog::Snapshot;
+
+/// Datamines usually land with the pre-download, a few days before the version releases
+const LEAD_DAYS: i64 = 7;
+
+/// Records everything in `after` that wasn't seen before. Returns the current version and the
+/// achievements that are new with it, if the version is known
+pub async fn record(
+    game: &str,
+    before: &Snapshot,
+    after: &Snapshot,
+    item_kind: FirstSeenKind,
+    pool: &PgPool,
+) -> Result<Option<(String, Vec<i32>)>> {
+    // The first import would tag everything with the current version
+    let (seen_at, version) = if before.achievements.is_empty() {
+        (None, None)
+    } else {
+        let now = Utc::now();
+
+        let version =
+            database::versions::get_current_by_game(game, now + Duration::days(LEAD_DAYS), pool)
+                .await?;
+
+        (Some(now), version)
+    };
+
+    let mut achievements = Vec::new();
+
+    for (kind, ids) in [
+        (
+            FirstSeenKind::Achievement,
+            after.achievements.keys().copied().collect::<Vec<_>>(),
+        ),
+        (
+            FirstSeenKind::Character,
+            after.characters.iter().copied().collect(),
+        ),
+        (item_kind, after.items.iter().copied().collect()),
+    ] {
+        let new =
+            database::first_seen::set_all(game, kind, &ids, seen_at, version.as_deref(), pool)
+                .await?;
+
+        if !new.is_empty() {
+            info!("First seen {} {game} {kind}s", new.len());
+        }
+
+        if matches!(kind, FirstSeenKind::Achievement) {
+            achievements = new;
+        }
+    }
+
+    Ok(version
+        .filter(|_| !achievements.is_empty())
+        .map(|version| (version, achievements)))
+}
+
+/// Re-tags everything seen by the updater once release dates change, e.g. when a version is only
+/// added after its achievements were seen. Achievement versions that were edited since are kept
+pub async fn retag(game: &str, pool: &PgPool) -> Result<()> {
+    let mut achievements: HashMap<_, Vec<_>> = HashMap::new();
+
+    for change in
+        database::first_seen::update_versions_by_game(game, LEAD_DAYS as i32, pool).await?
+    {
+        if change.kind == FirstSeenKind::Achievement.to_string() {
+            achievements
+                .entry((change.old_version, change.version))
+                .or_default()
+                .push(change.id);
+        }
+    }
+
+    for ((old_version, version), ids) in achievements {
+        let (old_version, version) = (old_version.as_deref(), version.as_deref());
+
+        match game {
+            "hsr" => {
+                database::achievements::update_version_where_version_by_ids(
+                    &ids,
+                    old_version,
+                    version,
+                    pool,
+                )
+                .await?
+            }
+            "gi" => {
+                database::gi::achievements::update_version_where_version_by_ids(
+                    &ids,
+                    old_version,
+                    version,
+                    pool,
+                )
+                .await?
+            }
+            "zzz" => {
+                database::zzz::achievements::update_version_where_version_by_ids(
+                    &ids,
+                    old_version,
+                    version,
+                    pool,
+                )
+                .await?
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
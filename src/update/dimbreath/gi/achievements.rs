@@ -4,7 +4,8 @@ use crate::database;
 
 use super::Configs;
 
-pub async fn update(configs: &Configs, pool: &PgPool) -> anyhow::Result<()> {
+/// Returns the ids of all achievements in the configs
+pub async fn update(configs: &Configs, pool: &PgPool) -> anyhow::Result<Vec<i32>> {
     let mut achievements_id = Vec::new();
     let mut achievements_series = Vec::new();
     let mut achievements_primogems = Vec::new();
@@ -53,5 +54,5 @@ pub async fn update(configs: &Configs, pool: &PgPool) -> anyhow::Result<()> {
     )
     .await?;
 
-    Ok(())
+    Ok(achievements_id)
 }
@@ -2,7 +2,11 @@ use std::{
     fs::File,
     io::BufReader,
     path::Path,
-    time::{Duration, Instant},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 mod achievement_series;
@@ -11,40 +15,33 @@ mod avatars;
 mod texts;
 mod weapons;
 
-use actix_web::rt::{self, Runtime};
 use async_process::Command;
 use sqlx::PgPool;
 
+use crate::{
+    database::{self, changelog_entries::ChangelogEntryKind, first_seen::FirstSeenKind},
+    scheduler::{self, Schedule},
+};
+
+use super::{
+    changelog::{self, Snapshot},
+    first_seen,
+};
+
 pub async fn spawn(pool: PgPool) {
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            let mut interval = rt::time::interval(Duration::from_secs(60 * 10));
-
-            let mut up_to_date = false;
-
-            loop {
-                interval.tick().await;
-
-                let start = Instant::now();
-
-                if let Err(e) = update(&mut up_to_date, pool.clone()).await {
-                    error!(
-                        "Dimbreath gi update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "Dimbreath gi update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
-
-        rt.block_on(handle).unwrap();
-    });
+    let up_to_date = Arc::new(AtomicBool::new(false));
+
+    scheduler::register(
+        "dimbreath_gi",
+        Schedule::Every(Duration::from_secs(60 * 10)),
+        move || {
+            let up_to_date = up_to_date.clone();
+
+            let pool = pool.clone();
+
+            async move { update(&up_to_date, pool).await }
+        },
+    );
 }
 
 #[derive(serde::Deserialize)]
@@ -118,7 +115,7 @@ struct Configs {
     weapon_data: Vec<WeaponData>,
 }
 
-async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
+async fn update(up_to_date: &AtomicBool, pool: PgPool) -> anyhow::Result<()> {
     if !Path::new("dimbreath").join("AnimeGameData").exists() {
         Command::new("git")
             .args([
@@ -131,7 +128,7 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
             .output()
             .await?;
 
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
     let output = String::from_utf8(
@@ -144,10 +141,10 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
     )?;
 
     if !output.contains("Already up to date.") {
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
-    if *up_to_date {
+    if up_to_date.load(Ordering::Relaxed) {
         return Ok(());
     }
 
@@ -179,12 +176,14 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
         weapon_data,
     };
 
+    let before = snapshot(&pool).await?;
+
     info!("Starting achievement series");
     achievement_series::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting achievements");
-    achievements::update(&configs, &pool).await?;
+    let ids = achievements::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting avatars");
@@ -199,7 +198,50 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
     texts::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
-    *up_to_date = true;
+    info!("Starting changelog");
+    let after = snapshot(&pool).await?;
+    let commit = changelog::commit(&Path::new("dimbreath").join("AnimeGameData")).await?;
+    changelog::record(
+        "gi",
+        &commit,
+        &before,
+        &after,
+        &ids.into_iter().collect(),
+        ChangelogEntryKind::WeaponAdded,
+        &pool,
+    )
+    .await?;
+
+    info!("Starting versions");
+    if let Some((version, ids)) =
+        first_seen::record("gi", &before, &after, FirstSeenKind::Weapon, &pool).await?
+    {
+        database::gi::achievements::update_version_where_null_by_ids(&ids, &version, &pool).await?;
+    }
+
+    up_to_date.store(true, Ordering::Relaxed);
 
     Ok(())
 }
+
+async fn snapshot(pool: &PgPool) -> anyhow::Result<Snapshot> {
+    Ok(Snapshot {
+        achievements: database::gi::achievements::get_all_rewards(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        texts: database::gi::achievements_text::get_all(pool)
+            .await?
+            .into_iter()
+            .map(|text| ((text.id, text.language), (text.name, text.description)))
+            .collect(),
+        characters: database::gi::characters::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        items: database::gi::weapons::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+    })
+}
@@ -2,7 +2,11 @@ use std::{
     fs::File,
     io::BufReader,
     path::Path,
-    time::{Duration, Instant},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 mod achievement_series;
@@ -11,12 +15,21 @@ mod avatars;
 mod light_cones;
 mod texts;
 
-use actix_web::rt::{self, Runtime};
 use anyhow::Result;
 use async_process::Command;
 use serde::Deserialize;
 use sqlx::PgPool;
 
+use crate::{
+    database::{self, changelog_entries::ChangelogEntryKind, first_seen::FirstSeenKind},
+    scheduler::{self, Schedule},
+};
+
+use super::{
+    changelog::{self, Snapshot},
+    first_seen,
+};
+
 #[derive(Deserialize)]
 struct AchievementData {
     #[serde(rename = "AchievementID")]
@@ -127,38 +140,22 @@ struct Configs {
 }
 
 pub async fn spawn(pool: PgPool) {
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            let mut interval = rt::time::interval(Duration::from_secs(60 * 10));
-
-            let mut up_to_date = false;
-
-            loop {
-                interval.tick().await;
-
-                let start = Instant::now();
-
-                if let Err(e) = update(&mut up_to_date, pool.clone()).await {
-                    error!(
-                        "Dimbreath hsr update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "Dimbreath hsr update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
-
-        rt.block_on(handle).unwrap();
-    });
+    let up_to_date = Arc::new(AtomicBool::new(false));
+
+    scheduler::register(
+        "dimbreath_hsr",
+        Schedule::Every(Duration::from_secs(60 * 10)),
+        move || {
+            let up_to_date = up_to_date.clone();
+
+            let pool = pool.clone();
+
+            async move { update(&up_to_date, pool).await }
+        },
+    );
 }
 
-async fn update(up_to_date: &mut bool, pool: PgPool) -> Result<()> {
+async fn update(up_to_date: &AtomicBool, pool: PgPool) -> Result<()> {
     if !Path::new("dimbreath").join("TurnBasedGameData").exists() {
         Command::new("git")
             .args([
@@ -171,7 +168,7 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> Result<()> {
             .output()
             .await?;
 
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
     let output = String::from_utf8(
@@ -184,10 +181,10 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> Result<()> {
     )?;
 
     if !output.contains("Already up to date.") {
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
-    if *up_to_date {
+    if up_to_date.load(Ordering::Relaxed) {
         return Ok(());
     }
 
@@ -236,12 +233,14 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> Result<()> {
 
     info!("Parsed all json");
 
+    let before = snapshot(&pool).await?;
+
     info!("Starting achievement series");
     achievement_series::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting achievements");
-    achievements::update(&configs, &pool).await?;
+    let ids = achievements::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting avatars");
@@ -256,7 +255,50 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> Result<()> {
     texts::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
-    *up_to_date = true;
+    info!("Starting changelog");
+    let after = snapshot(&pool).await?;
+    let commit = changelog::commit(&Path::new("dimbreath").join("TurnBasedGameData")).await?;
+    changelog::record(
+        "hsr",
+        &commit,
+        &before,
+        &after,
+        &ids.into_iter().collect(),
+        ChangelogEntryKind::LightConeAdded,
+        &pool,
+    )
+    .await?;
+
+    info!("Starting versions");
+    if let Some((version, ids)) =
+        first_seen::record("hsr", &before, &after, FirstSeenKind::LightCone, &pool).await?
+    {
+        database::achievements::update_version_where_null_by_ids(&ids, &version, &pool).await?;
+    }
+
+    up_to_date.store(true, Ordering::Relaxed);
 
     Ok(())
 }
+
+async fn snapshot(pool: &PgPool) -> Result<Snapshot> {
+    Ok(Snapshot {
+        achievements: database::achievements::get_all_rewards(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        texts: database::achievements_text::get_all(pool)
+            .await?
+            .into_iter()
+            .map(|text| ((text.id, text.language), (text.name, text.description)))
+            .collect(),
+        characters: database::characters::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        items: database::light_cones::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+    })
+}
@@ -1,3 +1,5 @@
+mod changelog;
+pub mod first_seen;
 pub mod gi;
 pub mod hsr;
 pub mod zzz;
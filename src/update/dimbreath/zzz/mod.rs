@@ -4,7 +4,11 @@ use std::{
     fs::File,
     io::BufReader,
     path::Path,
-    time::{Duration, Instant},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 mod achievement_series;
@@ -14,10 +18,19 @@ mod buddys;
 mod texts;
 mod w_engines;
 
-use actix_web::rt::{self, Runtime};
 use async_process::Command;
 use sqlx::PgPool;
 
+use crate::{
+    database::{self, changelog_entries::ChangelogEntryKind, first_seen::FirstSeenKind},
+    scheduler::{self, Schedule},
+};
+
+use super::{
+    changelog::{self, Snapshot},
+    first_seen,
+};
+
 #[derive(serde::Deserialize)]
 struct AchieveSecondClass {
     #[serde(rename = "MFLDCJDEMOD")]
@@ -127,38 +140,22 @@ struct Configs {
 }
 
 pub async fn spawn(pool: PgPool) {
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            let mut interval = rt::time::interval(Duration::from_secs(60 * 10));
-
-            let mut up_to_date = false;
-
-            loop {
-                interval.tick().await;
-
-                let start = Instant::now();
-
-                if let Err(e) = update(&mut up_to_date, pool.clone()).await {
-                    error!(
-                        "Dimbreath zzz update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "Dimbreath zzz update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
-
-        rt.block_on(handle).unwrap();
-    });
+    let up_to_date = Arc::new(AtomicBool::new(false));
+
+    scheduler::register(
+        "dimbreath_zzz",
+        Schedule::Every(Duration::from_secs(60 * 10)),
+        move || {
+            let up_to_date = up_to_date.clone();
+
+            let pool = pool.clone();
+
+            async move { update(&up_to_date, pool).await }
+        },
+    );
 }
 
-async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
+async fn update(up_to_date: &AtomicBool, pool: PgPool) -> anyhow::Result<()> {
     if !Path::new("dimbreath").join("ZenlessData").exists() {
         Command::new("git")
             .args(["clone", "--depth", "1", &env::var("ZENLESS_REPO")?])
@@ -166,7 +163,7 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
             .output()
             .await?;
 
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
     let output = String::from_utf8(
@@ -179,10 +176,10 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
     )?;
 
     if !output.contains("Already up to date.") {
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
-    if *up_to_date {
+    if up_to_date.load(Ordering::Relaxed) {
         return Ok(());
     }
 
@@ -237,12 +234,14 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
         buddy,
     };
 
+    let before = snapshot(&pool).await?;
+
     info!("Starting achievement series");
     achievement_series::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting achievements");
-    achievements::update(&configs, &pool).await?;
+    let ids = achievements::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
     info!("Starting avatars");
@@ -261,7 +260,51 @@ async fn update(up_to_date: &mut bool, pool: PgPool) -> anyhow::Result<()> {
     texts::update(&configs, &pool).await?;
     actix_web::rt::task::yield_now().await;
 
-    *up_to_date = true;
+    info!("Starting changelog");
+    let after = snapshot(&pool).await?;
+    let commit = changelog::commit(&Path::new("dimbreath").join("ZenlessData")).await?;
+    changelog::record(
+        "zzz",
+        &commit,
+        &before,
+        &after,
+        &ids.into_iter().collect(),
+        ChangelogEntryKind::WEngineAdded,
+        &pool,
+    )
+    .await?;
+
+    info!("Starting versions");
+    if let Some((version, ids)) =
+        first_seen::record("zzz", &before, &after, FirstSeenKind::WEngine, &pool).await?
+    {
+        database::zzz::achievements::update_version_where_null_by_ids(&ids, &version, &pool)
+            .await?;
+    }
+
+    up_to_date.store(true, Ordering::Relaxed);
 
     Ok(())
 }
+
+async fn snapshot(pool: &PgPool) -> anyhow::Result<Snapshot> {
+    Ok(Snapshot {
+        achievements: database::zzz::achievements::get_all_rewards(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        texts: database::zzz::achievements_text::get_all(pool)
+            .await?
+            .into_iter()
+            .map(|text| ((text.id, text.language), (text.name, text.description)))
+            .collect(),
+        characters: database::zzz::characters::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+        items: database::zzz::w_engines::get_all_ids(pool)
+            .await?
+            .into_iter()
+            .collect(),
+    })
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use actix_web::rt;
+use anyhow::Result;
+use sqlx::PgPool;
+
+use crate::{
+    database, enka,
+    scheduler::{self, Schedule},
+};
+
+pub async fn spawn(pool: PgPool) {
+    scheduler::register(
+        "gi_profiles",
+        Schedule::After(Duration::from_secs(60)),
+        move || {
+            let pool = pool.clone();
+
+            async move { update(&pool).await }
+        },
+    );
+}
+
+async fn update(pool: &PgPool) -> Result<()> {
+    for uid in database::gi::profiles::get_uids(pool).await? {
+        rt::time::sleep(Duration::from_secs(5)).await;
+
+        if let Err(e) = enka::gi::update_and_get(uid, pool).await {
+            error!("GI profile {uid} update failed with {e}");
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 pub mod achievements_percent;
 pub mod dimbreath;
 pub mod gi_achievements_percent;
+pub mod gi_profiles;
 pub mod scores;
 pub mod signals_stats;
 pub mod star_rail_res;
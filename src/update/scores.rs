@@ -1,65 +1,31 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use actix_web::rt::{self, Runtime};
+use actix_web::rt;
 use anyhow::Result;
-use chrono::Utc;
-use regex::{Captures, Regex};
 use sqlx::PgPool;
 
-use crate::{database, mihomo, Language};
+use crate::{
+    database, providers,
+    scheduler::{self, Schedule},
+    Language,
+};
 
 pub async fn spawn(pool: PgPool) {
     {
         let pool = pool.clone();
 
-        std::thread::spawn(move || {
-            let rt = Runtime::new().unwrap();
-
-            let handle = rt.spawn(async move {
-                loop {
-                    let start = Instant::now();
-
-                    if let Err(e) = update_top_100(pool.clone()).await {
-                        error!(
-                            "Scores top 100 update failed with {e} in {}s",
-                            start.elapsed().as_secs_f64()
-                        );
-                    } else {
-                        info!(
-                            "Scores top 100 update succeeded in {}s",
-                            start.elapsed().as_secs_f64()
-                        );
-                    }
-                }
-            });
-
-            rt.block_on(handle).unwrap();
-        });
+        scheduler::register(
+            "scores_top_100",
+            Schedule::Every(Duration::ZERO),
+            move || update_top_100(pool.clone()),
+        );
     }
 
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            loop {
-                let start = Instant::now();
-
-                if let Err(e) = update_lower_100(pool.clone()).await {
-                    error!(
-                        "Scores lower 100 update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "Scores lower 100 update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
-
-        rt.block_on(handle).unwrap();
-    });
+    scheduler::register(
+        "scores_lower_100",
+        Schedule::Every(Duration::ZERO),
+        move || update_lower_100(pool.clone()),
+    );
 }
 
 async fn update_top_100(pool: PgPool) -> Result<()> {
@@ -102,28 +68,6 @@ async fn update_lower_100(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
-#[derive(serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Enka {
-    detail_info: DetailInfo,
-}
-
-#[derive(serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DetailInfo {
-    nickname: String,
-    level: i32,
-    signature: Option<String>,
-    head_icon: i32,
-    record_info: RecordInfo,
-}
-
-#[derive(serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct RecordInfo {
-    achievement_count: i32,
-}
-
 async fn update_scores(uids: Vec<i32>, pool: &PgPool) -> Result<()> {
     for uid in uids {
         loop {
@@ -139,100 +83,9 @@ async fn update_scores(uids: Vec<i32>, pool: &PgPool) -> Result<()> {
 }
 
 async fn update_score(uid: i32, pool: &PgPool) -> Result<()> {
-    let now = Utc::now();
-
-    if mihomo::update_and_get(uid, Language::En, pool)
-        .await
-        .is_ok()
-    {
-        return Ok(());
-    }
-
-    let client = reqwest::Client::new();
-
-    let enka: Enka = match client
-        .get(format!("https://enka.network/api/hsr/uid/{uid}?info"))
-        .header(reqwest::header::USER_AGENT, "stardb")
-        .send()
-        .await
-    {
-        Ok(r) => match r.json().await {
-            Ok(enka) => enka,
-            Err(e) => {
-                error!("{e}");
-                return Ok(());
-            }
-        },
-        Err(e) => {
-            error!("{e}");
-            return Ok(());
-        }
-    };
-
-    let re = Regex::new(r"<[^>]*>")?;
-
-    let name = re
-        .replace_all(&enka.detail_info.nickname, |_: &Captures| "")
-        .to_string();
-    let region = match uid.to_string().chars().next() {
-        Some('6') => "na",
-        Some('7') => "eu",
-        Some('8') | Some('9') => "asia",
-        _ => "cn",
+    if let Err(e) = providers::update(uid, Language::En, pool).await {
+        error!("{e}");
     }
-    .to_string();
-    let level = enka.detail_info.level;
-    let signature = re
-        .replace_all(
-            &enka.detail_info.signature.unwrap_or_default(),
-            |_: &Captures| "",
-        )
-        .to_string();
-    let avatar_icon = mihomo::get(uid, Language::En, pool)
-        .await
-        .and_then(|v| serde_json::from_value::<mihomo::Mihomo>(v).map_err(Into::into))
-        .map(|m| m.player.avatar.icon)
-        .unwrap_or(format!("icon/avatar/{}.png", enka.detail_info.head_icon));
-    let achievement_count = enka.detail_info.record_info.achievement_count;
-    let updated_at = now;
-    let timestamp = database::achievement_scores::get_timestamp_by_uid(uid, pool)
-        .await
-        .ok()
-        .and_then(|sd| {
-            if sd.achievement_count == achievement_count {
-                Some(sd.timestamp)
-            } else {
-                None
-            }
-        })
-        .unwrap_or(
-            now + match region.as_str() {
-                "na" => chrono::Duration::try_hours(-5).unwrap(),
-                "eu" => chrono::Duration::try_hours(1).unwrap(),
-                _ => chrono::Duration::try_hours(8).unwrap(),
-            },
-        );
-
-    let db_mihomo = database::mihomo::DbMihomo {
-        uid,
-        region,
-        name,
-        level,
-        signature,
-        avatar_icon,
-        achievement_count,
-        updated_at,
-    };
-
-    database::mihomo::set(&db_mihomo, pool).await?;
-
-    let db_score_achievement = database::achievement_scores::DbScoreAchievement {
-        uid,
-        timestamp,
-        ..Default::default()
-    };
-
-    database::achievement_scores::set(&db_score_achievement, pool).await?;
 
     Ok(())
 }
@@ -1,45 +1,19 @@
-use std::{
-    cmp::Ordering,
-    collections::HashMap,
-    time::{Duration, Instant},
-};
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
 
-use actix_web::rt;
 use anyhow::Result;
 use sqlx::PgPool;
 
-use crate::database;
+use crate::{
+    database,
+    scheduler::{self, Schedule},
+};
 
 pub async fn spawn(pool: PgPool) {
-    actix::Arbiter::new().spawn(async move {
-        let mut success = true;
-
-        let mut interval = rt::time::interval(Duration::from_secs(60 * 60));
-
-        loop {
-            if success {
-                interval.tick().await;
-            }
-
-            let start = Instant::now();
-
-            if let Err(e) = update(pool.clone()).await {
-                error!(
-                    "Signals stats update failed with {e} in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = false;
-            } else {
-                info!(
-                    "Signals stats update succeeded in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = true;
-            }
-        }
-    });
+    scheduler::register(
+        "signals_stats",
+        Schedule::Every(Duration::from_secs(60 * 60)),
+        move || update(pool.clone()),
+    );
 }
 
 async fn update(pool: PgPool) -> Result<()> {
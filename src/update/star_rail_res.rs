@@ -2,10 +2,14 @@ use std::{
     fs::{self, File},
     io::BufReader,
     path::{Path, PathBuf},
-    time::{Duration, Instant},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
-use actix_web::rt::{self, Runtime};
+use actix_web::rt;
 use anyhow::{anyhow, Result};
 use async_process::Command;
 use image::{EncodableLayout, ImageFormat};
@@ -13,39 +17,23 @@ use image::{EncodableLayout, ImageFormat};
 use walkdir::WalkDir;
 use webp::Encoder;
 
+use crate::scheduler::{self, Schedule};
+
 pub async fn spawn() {
-    std::thread::spawn(move || {
-        let rt = Runtime::new().unwrap();
-
-        let handle = rt.spawn(async move {
-            let mut interval = rt::time::interval(Duration::from_secs(60 * 10));
-
-            let mut up_to_date = false;
-
-            loop {
-                interval.tick().await;
-
-                let start = Instant::now();
-
-                if let Err(e) = update(&mut up_to_date).await {
-                    error!(
-                        "StarRailRes update failed with {e} in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                } else {
-                    info!(
-                        "StarRailRes update succeeded in {}s",
-                        start.elapsed().as_secs_f64()
-                    );
-                }
-            }
-        });
+    let up_to_date = Arc::new(AtomicBool::new(false));
+
+    scheduler::register(
+        "star_rail_res",
+        Schedule::Every(Duration::from_secs(60 * 10)),
+        move || {
+            let up_to_date = up_to_date.clone();
 
-        rt.block_on(handle).unwrap();
-    });
+            async move { update(&up_to_date).await }
+        },
+    );
 }
 
-async fn update(up_to_date: &mut bool) -> Result<()> {
+async fn update(up_to_date: &AtomicBool) -> Result<()> {
     if !Path::new("static/StarRailRes").exists() {
         Command::new("git")
             .args([
@@ -58,7 +46,7 @@ async fn update(up_to_date: &mut bool) -> Result<()> {
             .output()
             .await?;
 
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
     let output = String::from_utf8(
@@ -71,10 +59,10 @@ async fn update(up_to_date: &mut bool) -> Result<()> {
     )?;
 
     if !output.contains("Already up to date.") {
-        *up_to_date = false;
+        up_to_date.store(false, Ordering::Relaxed);
     }
 
-    if *up_to_date {
+    if up_to_date.load(Ordering::Relaxed) {
         return Ok(());
     }
 
@@ -116,7 +104,7 @@ async fn update(up_to_date: &mut bool) -> Result<()> {
         rt::task::yield_now().await;
     }
 
-    *up_to_date = true;
+    up_to_date.store(true, Ordering::Relaxed);
 
     Ok(())
 }
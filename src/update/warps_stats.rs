@@ -1,48 +1,27 @@
-use std::{
-    cmp::Ordering,
-    collections::HashMap,
-    time::{Duration, Instant},
-};
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
 
-use actix_web::rt;
 use anyhow::Result;
 use sqlx::PgPool;
 
-use crate::database;
+use crate::{
+    database,
+    gacha::{self, hsr::Warps},
+    scheduler::{self, Schedule},
+    GachaType,
+};
 
 pub async fn spawn(pool: PgPool) {
-    actix::Arbiter::new().spawn(async move {
-        let mut success = true;
-
-        let mut interval = rt::time::interval(Duration::from_secs(60 * 60));
-
-        loop {
-            if success {
-                interval.tick().await;
-            }
-
-            let start = Instant::now();
-
-            if let Err(e) = update(pool.clone()).await {
-                error!(
-                    "Warps stats update failed with {e} in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = false;
-            } else {
-                info!(
-                    "Warps stats update succeeded in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = true;
-            }
-        }
-    });
+    scheduler::register(
+        "warps_stats",
+        Schedule::Every(Duration::from_secs(60 * 60)),
+        move || update(pool.clone()),
+    );
 }
 
 async fn update(pool: PgPool) -> Result<()> {
+    info!("Starting recalculation");
+    recalculate(&pool).await?;
+
     info!("Starting standard");
     standard(&pool).await?;
 
@@ -55,6 +34,50 @@ async fn update(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
+// Fills in the luck likelihoods for stats calculated before they existed and keeps them in line
+// with the current model. Only uids whose warps changed since their stats were calculated are redone
+async fn recalculate(pool: &PgPool) -> Result<()> {
+    for warp_stat in database::warps_stats::standard::get_all(pool).await? {
+        let uid = warp_stat.uid;
+
+        if let Err(e) = recalculate_uid(uid, GachaType::Standard, warp_stat.count, pool).await {
+            error!("Recalculating standard {uid} failed with {e}");
+        }
+    }
+
+    for warp_stat in database::warps_stats::special::get_all(pool).await? {
+        let uid = warp_stat.uid;
+
+        if let Err(e) = recalculate_uid(uid, GachaType::Special, warp_stat.count, pool).await {
+            error!("Recalculating special {uid} failed with {e}");
+        }
+    }
+
+    for warp_stat in database::warps_stats::lc::get_all(pool).await? {
+        let uid = warp_stat.uid;
+
+        if let Err(e) = recalculate_uid(uid, GachaType::Lc, warp_stat.count, pool).await {
+            error!("Recalculating lc {uid} failed with {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn recalculate_uid(uid: i32, gacha_type: GachaType, count: i32, pool: &PgPool) -> Result<()> {
+    let current = match gacha_type {
+        GachaType::Standard => database::warps::standard::get_count_by_uid(uid, pool).await?,
+        GachaType::Special => database::warps::special::get_count_by_uid(uid, pool).await?,
+        _ => database::warps::lc::get_count_by_uid(uid, pool).await?,
+    };
+
+    if current != count as i64 {
+        gacha::update_stats::<Warps>(uid, gacha_type, pool).await?;
+    }
+
+    Ok(())
+}
+
 async fn standard(pool: &PgPool) -> Result<()> {
     let mut count_map = HashMap::new();
     let mut luck_4_map = HashMap::new();
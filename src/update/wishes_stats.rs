@@ -1,48 +1,27 @@
-use std::{
-    cmp::Ordering,
-    collections::HashMap,
-    time::{Duration, Instant},
-};
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
 
-use actix_web::rt;
 use anyhow::Result;
 use sqlx::PgPool;
 
-use crate::database;
+use crate::{
+    database,
+    gacha::{self, gi::Wishes},
+    scheduler::{self, Schedule},
+    GiGachaType,
+};
 
 pub async fn spawn(pool: PgPool) {
-    actix::Arbiter::new().spawn(async move {
-        let mut success = true;
-
-        let mut interval = rt::time::interval(Duration::from_secs(60 * 60));
-
-        loop {
-            if success {
-                interval.tick().await;
-            }
-
-            let start = Instant::now();
-
-            if let Err(e) = update(pool.clone()).await {
-                error!(
-                    "Wishes stats update failed with {e} in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = false;
-            } else {
-                info!(
-                    "Wishes stats update succeeded in {}s",
-                    start.elapsed().as_secs_f64()
-                );
-
-                success = true;
-            }
-        }
-    });
+    scheduler::register(
+        "wishes_stats",
+        Schedule::Every(Duration::from_secs(60 * 60)),
+        move || update(pool.clone()),
+    );
 }
 
 async fn update(pool: PgPool) -> Result<()> {
+    info!("Starting recalculation");
+    recalculate(&pool).await?;
+
     info!("Starting standard");
     standard(&pool).await?;
 
@@ -58,6 +37,48 @@ async fn update(pool: PgPool) -> Result<()> {
     Ok(())
 }
 
+// Keeps win rates in line with the current radiance and path model. Only uids whose wishes changed
+// since their stats were calculated are redone, rows from before the model start out stale
+async fn recalculate(pool: &PgPool) -> Result<()> {
+    for wish_stat in database::gi::wishes_stats::character::get_all(pool).await? {
+        let uid = wish_stat.uid;
+
+        if let Err(e) = recalculate_uid(uid, GiGachaType::Character, wish_stat.count, pool).await {
+            error!("Recalculating character {uid} failed with {e}");
+        }
+    }
+
+    for wish_stat in database::gi::wishes_stats::weapon::get_all(pool).await? {
+        let uid = wish_stat.uid;
+
+        if let Err(e) = recalculate_uid(uid, GiGachaType::Weapon, wish_stat.count, pool).await {
+            error!("Recalculating weapon {uid} failed with {e}");
+        }
+    }
+
+    Ok(())
+}
+
+async fn recalculate_uid(
+    uid: i32,
+    gacha_type: GiGachaType,
+    count: i32,
+    pool: &PgPool,
+) -> Result<()> {
+    let current = match gacha_type {
+        GiGachaType::Character => {
+            database::gi::wishes::character::get_count_by_uid(uid, pool).await?
+        }
+        _ => database::gi::wishes::weapon::get_count_by_uid(uid, pool).await?,
+    };
+
+    if current != count as i64 {
+        gacha::update_stats::<Wishes>(uid, gacha_type, pool).await?;
+    }
+
+    Ok(())
+}
+
 async fn standard(pool: &PgPool) -> Result<()> {
     let mut count_map = HashMap::new();
     let mut luck_4_map = HashMap::new();